@@ -1,13 +1,19 @@
 import { useState, useCallback } from 'react';
 import { useConnection, useWallet } from '@solana/wallet-adapter-react';
 import { 
+  ComputeBudgetProgram,
   Transaction, 
   TransactionInstruction, 
   SystemProgram, 
   PublicKey,
 } from '@solana/web3.js';
 import { generateProof } from '@/lib/zkProof';
-import { fetchCensusState, deriveCensusStatePda, deriveNullifierPda } from '@/lib/census';
+import {
+  fetchCensusState,
+  deriveCensusStatePda,
+  deriveNullifierPda,
+  RECOMMENDED_COMPUTE_UNITS_SUBMIT_CENSUS,
+} from '@/lib/census';
 
 const CENSUS_PROGRAM_ID = new PublicKey('9TNbyekg5Ck8Hx9EMwDNcH97sDZYZJamdvnGFhwH1UsH');
 const VERIFIER_API_URL = process.env.NEXT_PUBLIC_VERIFIER_API_URL || 'http://localhost:3001';
@@ -169,6 +175,9 @@ export function ProveButton({ onProofSubmitted, onError, disabled }: ProveButton
         Buffer.from(proofB),
         Buffer.from(proofC),
         ...publicInputs.map((p: Uint8Array) => Buffer.from(p)),
+        Buffer.from([0]), // expected_root_version: None
+        Buffer.from([0]), // extra: None
+        Buffer.from([0]), // bucket_index: None
       ]);
 
       const instruction = new TransactionInstruction({
@@ -188,7 +197,10 @@ export function ProveButton({ onProofSubmitted, onError, disabled }: ProveButton
       const transaction = new Transaction({
         recentBlockhash: blockhash,
         feePayer: publicKey,
-      }).add(instruction);
+      }).add(
+        ComputeBudgetProgram.setComputeUnitLimit({ units: RECOMMENDED_COMPUTE_UNITS_SUBMIT_CENSUS }),
+        instruction
+      );
 
       console.log('✅ Transaction built');
 
@@ -358,16 +370,13 @@ function hexToBytes64(point: string[]): Uint8Array {
 function hexToBytes128(point: string[][]): Uint8Array {
   const bytes = new Uint8Array(128);
   // G2 point has 4 components: [[x.c0, x.c1], [y.c0, y.c1], [z.c0, z.c1]]
-  const x0 = BigInt(point[0][0]);
-  const x1 = BigInt(point[0][1]);
-  const y0 = BigInt(point[1][0]);
-  const y1 = BigInt(point[1][1]);
+  // The program takes x.c0 || x.c1 || y.c0 || y.c1, big-endian, unswapped
+  const coordinates = [point[0][0], point[0][1], point[1][0], point[1][1]].map(BigInt);
   
-  for (let i = 0; i < 32; i++) {
-    bytes[i] = Number((x0 >> BigInt(i * 8)) & 0xFFn);
-    bytes[32 + i] = Number((x1 >> BigInt(i * 8)) & 0xFFn);
-    bytes[64 + i] = Number((y0 >> BigInt(i * 8)) & 0xFFn);
-    bytes[96 + i] = Number((y1 >> BigInt(i * 8)) & 0xFFn);
-  }
+  coordinates.forEach((value, index) => {
+    for (let i = 0; i < 32; i++) {
+      bytes[index * 32 + 31 - i] = Number((value >> BigInt(i * 8)) & 0xFFn);
+    }
+  });
   return bytes;
 }
\ No newline at end of file
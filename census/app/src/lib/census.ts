@@ -4,7 +4,7 @@
  * Builds transactions for interacting with the zk-Census Anchor program
  */
 
-import { Connection, PublicKey, Transaction, TransactionInstruction, SystemProgram } from '@solana/web3.js';
+import { ComputeBudgetProgram, Connection, PublicKey, Transaction, TransactionInstruction, SystemProgram } from '@solana/web3.js';
 import { Program, AnchorProvider, Idl } from '@coral-xyz/anchor';
 
 // Program ID (update after deployment)
@@ -21,6 +21,13 @@ const CENSUS_STATE_SEED = Buffer.from('census_state');
 const NULLIFIER_SEED = Buffer.from('nullifier');
 const MERKLE_TREE_SEED = Buffer.from('merkle_tree');
 
+// Compute budget for submit_census (matches RECOMMENDED_COMPUTE_UNITS_SUBMIT_CENSUS)
+// Groth16 verification does not fit the default 200k CU limit
+export const RECOMMENDED_COMPUTE_UNITS_SUBMIT_CENSUS = 1_000_000;
+
+// Commitment format generateIdentityCommitment produces (matches COMMITMENT_VERSION_DEFAULT)
+export const COMMITMENT_VERSION = 1;
+
 /**
  * Derive Census State PDA
  */
@@ -68,11 +75,12 @@ export async function buildRegisterCitizenTx({
   const [censusStatePda] = deriveCensusStatePda();
 
   // Create instruction data with correct discriminator
-  // Format: discriminator (8 bytes) + identity_commitment (32 bytes)
+  // Format: discriminator (8 bytes) + identity_commitment (32 bytes) + commitment_version (1 byte)
   const discriminator = Buffer.from([241, 250, 3, 236, 214, 25, 141, 170]);
   const data = Buffer.concat([
     discriminator,
     Buffer.from(identityCommitment),
+    Buffer.from([COMMITMENT_VERSION]),
   ]);
 
   const instruction = new TransactionInstruction({
@@ -128,6 +136,9 @@ export async function buildSubmitCensusTx({
     Buffer.from(proofB),
     Buffer.from(proofC),
     ...publicInputs.map(p => Buffer.from(p)),
+    Buffer.from([0]), // expected_root_version: None
+    Buffer.from([0]), // extra: None
+    Buffer.from([0]), // bucket_index: None
   ]);
 
   const instruction = new TransactionInstruction({
@@ -142,6 +153,7 @@ export async function buildSubmitCensusTx({
   });
 
   const tx = new Transaction();
+  tx.add(ComputeBudgetProgram.setComputeUnitLimit({ units: RECOMMENDED_COMPUTE_UNITS_SUBMIT_CENSUS }));
   tx.add(instruction);
   tx.feePayer = payer;
   tx.recentBlockhash = (await connection.getLatestBlockhash()).blockhash;
@@ -48,6 +48,8 @@ interface SubmitCensusParams {
   proofB: number[];
   proofC: number[];
   publicInputs: number[][];
+  /// root_version the proof was built against, to tell a root rotation apart from a bad root
+  expectedRootVersion?: BN;
 }
 
 /**
@@ -60,6 +62,7 @@ export async function buildSubmitCensusIx({
   proofB,
   proofC,
   publicInputs,
+  expectedRootVersion,
 }: SubmitCensusParams) {
   const [censusStatePda] = deriveCensusStatePda();
   
@@ -68,11 +71,16 @@ export async function buildSubmitCensusIx({
   const [nullifierPda] = deriveNullifierPda(nullifierHash);
 
   return program.methods
-    .submitCensus(proofA, proofB, proofC, publicInputs)
+    .submitCensus(proofA, proofB, proofC, publicInputs, expectedRootVersion ?? null, null, null, null)
     .accounts({
       payer,
       censusState: censusStatePda,
       nullifier: nullifierPda,
+      numericAggregate: null,
+      previousScopeSnapshot: null,
+      payerCooldown: null,
+      nullifierBitmap: null,
+      aggregate: null,
     })
     .instruction();
 }
@@ -175,7 +175,7 @@ export function formatProofForSolana(
     BigInt(proof.pi_a[1])
   );
 
-  // 2. Convert proof.pi_b (G2 point) - Note: coordinates are swapped in snarkjs output
+  // 2. Convert proof.pi_b (G2 point) - snarkjs order, big-endian
   const proofB = formatG2Point(proof.pi_b);
 
   // 3. Convert proof.pi_c (G1 point)
@@ -222,20 +222,18 @@ function formatG1Point(x: bigint, y: bigint): Uint8Array {
 }
 
 /**
- * Format G2 point for Solana
- * G2 points have 2 coordinates, each with real and imaginary parts
- * snarkjs outputs: [[x_im, x_re], [y_im, y_re]]
+ * Format G2 point for the census program
+ * snarkjs outputs pi_b as [[x.c0, x.c1], [y.c0, y.c1]]; the program takes
+ * x.c0 || x.c1 || y.c0 || y.c1 (big-endian) and reorders for the pairing
+ * syscall itself, so no swap here
  */
 function formatG2Point(piB: string[][]): Uint8Array {
   const result = new Uint8Array(128);
   
-  // x coordinate (64 bytes): [x_im, x_re] -> [x_re, x_im] for Solana
-  result.set(bigIntToBytes(BigInt(piB[0][1]), 32), 0);   // x_re
-  result.set(bigIntToBytes(BigInt(piB[0][0]), 32), 32);  // x_im
-  
-  // y coordinate (64 bytes): [y_im, y_re] -> [y_re, y_im] for Solana
-  result.set(bigIntToBytes(BigInt(piB[1][1]), 32), 64);  // y_re
-  result.set(bigIntToBytes(BigInt(piB[1][0]), 32), 96);  // y_im
+  result.set(bigIntToBytes(BigInt(piB[0][0]), 32), 0);   // x.c0
+  result.set(bigIntToBytes(BigInt(piB[0][1]), 32), 32);  // x.c1
+  result.set(bigIntToBytes(BigInt(piB[1][0]), 32), 64);  // y.c0
+  result.set(bigIntToBytes(BigInt(piB[1][1]), 32), 96);  // y.c1
   
   return result;
 }
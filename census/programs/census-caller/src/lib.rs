@@ -0,0 +1,92 @@
+//! Example composing program: counts a participant in zk-Census by CPI into
+//! submit_attestation, paying the nullifier rent from its own PDA.
+//! Exercised by tests/census.ts.
+
+use anchor_lang::prelude::*;
+use census::{constants::ATTESTATION_NONCE_LEN, program::Census};
+
+declare_id!("4m36zRbLjPwR69uo7G6WVQYj44D59jZdzbYHAJP4PVaT");
+
+/// Seed of the system-owned PDA that pays for relayed counts
+pub const RELAYER_SEED: &[u8] = b"relayer";
+
+#[program]
+pub mod census_caller {
+    use super::*;
+
+    /// Submit a verifier-signed attestation with the relayer PDA as payer.
+    /// The Ed25519 instruction must directly precede this one.
+    #[allow(clippy::too_many_arguments)]
+    pub fn relay_attestation(
+        ctx: Context<RelayAttestation>,
+        timestamp: i64,
+        nonce: [u8; ATTESTATION_NONCE_LEN],
+        merkle_root: [u8; 32],
+        nullifier_hash: [u8; 32],
+        external_nullifier: [u8; 32],
+        signal_hash: [u8; 32],
+        signature: [u8; 64],
+    ) -> Result<()> {
+        let accounts = census::cpi::accounts::SubmitAttestation {
+            payer: ctx.accounts.relayer.to_account_info(),
+            verifier: ctx.accounts.verifier.to_account_info(),
+            census_state: ctx.accounts.census_state.to_account_info(),
+            nullifier: ctx.accounts.nullifier.to_account_info(),
+            instructions_sysvar: ctx.accounts.instructions_sysvar.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            previous_scope_snapshot: None,
+            payer_cooldown: None,
+            nullifier_bitmap: None,
+            merkle_tree: None,
+            compression_program: None,
+            verifier_registry: None,
+            citizen_record: None,
+            aggregate: None,
+        };
+        let bump = [ctx.bumps.relayer];
+        let signer_seeds: &[&[&[u8]]] = &[&[RELAYER_SEED, &bump]];
+
+        census::cpi::submit_attestation(
+            CpiContext::new_with_signer(
+                ctx.accounts.census_program.to_account_info(),
+                accounts,
+                signer_seeds,
+            ),
+            timestamp,
+            nonce,
+            merkle_root,
+            nullifier_hash,
+            external_nullifier,
+            signal_hash,
+            signature,
+            None,
+            None,
+            None,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct RelayAttestation<'info> {
+    /// Pays the nullifier rent; fund it before relaying
+    #[account(mut, seeds = [RELAYER_SEED], bump)]
+    pub relayer: SystemAccount<'info>,
+
+    /// Verifier that signed the attestation
+    pub verifier: Signer<'info>,
+
+    /// CHECK: validated by the census program
+    #[account(mut)]
+    pub census_state: UncheckedAccount<'info>,
+
+    /// CHECK: created and validated by the census program
+    #[account(mut)]
+    pub nullifier: UncheckedAccount<'info>,
+
+    /// CHECK: validated by the census program
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    pub census_program: Program<'info, Census>,
+}
@@ -0,0 +1,127 @@
+//! Attestation message encoding shared by the program and off-chain verifiers
+//!
+//! Verifiers sign exactly these bytes; any divergence silently fails the
+//! Ed25519 check, so both sides must encode through this function.
+//!
+//! Nonces: the verifier server draws a fresh `nonce` from a CSPRNG for every
+//! attestation it signs and never signs two messages with the same nonce.
+//! It only needs to remember nonces for ATTESTATION_VALIDITY_SECONDS, since
+//! older attestations are rejected on-chain anyway. The program stores the
+//! nonce on the nullifier it records, binding the signature to that count.
+//!
+//! Payer binding: an attestation broadcast before it lands could be submitted
+//! by anyone, letting a front-runner record the nullifier first. A verifier
+//! that knows the intended submitter appends its pubkey to the message
+//! (`bound_payer`); submit_attestation then only accepts it from that payer.
+//!
+//! Circuit binding: the message carries CensusState::circuit_hash, a
+//! commitment to the circuit/vkey the verifier checked the proof against.
+//! submit_attestation always encodes the on-chain value, so an attestation
+//! signed for another circuit fails the signature check.
+//!
+//! Membership binding: a submission that proves its identity leaf on-chain
+//! (`strict_membership`) appends the leaf and its index to the message, so
+//! the verifier vouches for which leaf the nullifier belongs to and a
+//! submitter can't pair the attestation with some other public leaf.
+//!
+//! With the `std` feature (off-chain only), `sign_attestation` and
+//! `verify_attestation` produce and check the signature over this encoding.
+
+use anchor_lang::prelude::Pubkey;
+use crate::{
+    constants::{ATTESTATION_MESSAGE_VERSION, ATTESTATION_NONCE_LEN},
+    state::MembershipProof,
+};
+
+/// Wire length of an unbound attestation message. Spelled out rather than
+/// derived from the builder, so an encoding change must change it too and
+/// submit_attestation's length check catches the drift until it does
+pub const ATTESTATION_MESSAGE_LEN: usize = 185;
+
+/// Wire length of a payer-bound attestation message
+pub const ATTESTATION_BOUND_MESSAGE_LEN: usize = ATTESTATION_MESSAGE_LEN + 32;
+
+/// Bytes a membership-bound message appends: the leaf, then its index (LE u32)
+pub const ATTESTATION_MEMBERSHIP_LEN: usize = 36;
+
+/// `[ATTESTATION_MESSAGE_VERSION, timestamp (LE), nonce, root, nullifier, external, signal, circuit, (payer), (leaf, leaf index (LE))]`
+#[allow(clippy::too_many_arguments)]
+pub fn build_attestation_message(
+    timestamp: i64,
+    nonce: &[u8; ATTESTATION_NONCE_LEN],
+    merkle_root: &[u8; 32],
+    nullifier_hash: &[u8; 32],
+    external_nullifier: &[u8; 32],
+    signal_hash: &[u8; 32],
+    circuit_hash: &[u8; 32],
+    bound_payer: Option<&Pubkey>,
+    membership: Option<&MembershipProof>,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(ATTESTATION_BOUND_MESSAGE_LEN + ATTESTATION_MEMBERSHIP_LEN);
+    message.push(ATTESTATION_MESSAGE_VERSION);
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message.extend_from_slice(nonce);
+    message.extend_from_slice(merkle_root);
+    message.extend_from_slice(nullifier_hash);
+    message.extend_from_slice(external_nullifier);
+    message.extend_from_slice(signal_hash);
+    message.extend_from_slice(circuit_hash);
+    if let Some(payer) = bound_payer {
+        message.extend_from_slice(payer.as_ref());
+    }
+    if let Some(membership) = membership {
+        message.extend_from_slice(&membership.leaf);
+        message.extend_from_slice(&membership.leaf_index.to_le_bytes());
+    }
+    message
+}
+
+/// Fields a verifier attests to, in wire order
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttestationFields {
+    pub timestamp: i64,
+    pub nonce: [u8; ATTESTATION_NONCE_LEN],
+    pub merkle_root: [u8; 32],
+    pub nullifier_hash: [u8; 32],
+    pub external_nullifier: [u8; 32],
+    pub signal_hash: [u8; 32],
+    pub circuit_hash: [u8; 32],
+    pub bound_payer: Option<Pubkey>,
+    pub membership: Option<MembershipProof>,
+}
+
+impl AttestationFields {
+    /// Encoded message, via build_attestation_message
+    pub fn message(&self) -> Vec<u8> {
+        build_attestation_message(
+            self.timestamp,
+            &self.nonce,
+            &self.merkle_root,
+            &self.nullifier_hash,
+            &self.external_nullifier,
+            &self.signal_hash,
+            &self.circuit_hash,
+            self.bound_payer.as_ref(),
+            self.membership.as_ref(),
+        )
+    }
+}
+
+/// Ed25519 signature over the attestation message, as submit_attestation expects
+#[cfg(feature = "std")]
+pub fn sign_attestation(keypair: &ed25519_dalek::Keypair, fields: &AttestationFields) -> [u8; 64] {
+    use ed25519_dalek::Signer;
+    keypair.sign(&fields.message()).to_bytes()
+}
+
+/// Does `signature` by `pubkey` cover exactly this attestation?
+#[cfg(feature = "std")]
+pub fn verify_attestation(pubkey: &[u8; 32], fields: &AttestationFields, signature: &[u8; 64]) -> bool {
+    let (Ok(pubkey), Ok(signature)) = (
+        ed25519_dalek::PublicKey::from_bytes(pubkey),
+        ed25519_dalek::Signature::from_bytes(signature),
+    ) else {
+        return false;
+    };
+    pubkey.verify_strict(&fields.message(), &signature).is_ok()
+}
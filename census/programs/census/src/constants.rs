@@ -1,3 +1,5 @@
+use anchor_lang::prelude::*;
+
 // ============================================================================
 // SEEDS FOR PDA DERIVATION
 // ============================================================================
@@ -8,15 +10,177 @@ pub const NULLIFIER_SEED: &[u8] = b"nullifier";
 
 pub const MERKLE_TREE_SEED: &[u8] = b"merkle_tree";
 
+pub const ATTRIBUTE_SCHEMA_SEED: &[u8] = b"attribute_schema";
+
+pub const SCOPE_SNAPSHOT_SEED: &[u8] = b"scope_snapshot";
+
+pub const AGGREGATE_SEED: &[u8] = b"aggregate";
+
+pub const VERIFIER_STATS_SEED: &[u8] = b"verifier_stats";
+
+pub const PAYER_COOLDOWN_SEED: &[u8] = b"payer_cooldown";
+
+pub const NUMERIC_AGGREGATE_SEED: &[u8] = b"numeric_aggregate";
+
+pub const CENSUS_METADATA_SEED: &[u8] = b"census_metadata";
+
+pub const NULLIFIER_BITMAP_SEED: &[u8] = b"nullifier_bitmap";
+
+pub const VERIFIER_REGISTRY_SEED: &[u8] = b"verifier_registry";
+
+pub const CITIZEN_RECORD_SEED: &[u8] = b"citizen_record";
+
+// ============================================================================
+// STATE VERSIONING
+// ============================================================================
+
+/// Current CensusState layout version (see instructions/migrate_state.rs)
+pub const CENSUS_STATE_VERSION: u8 = 8;
+
+// ============================================================================
+// TIME HANDLING
+// ============================================================================
+
+/// Seconds a timestamp may run ahead of the cluster clock before it's rejected
+pub const MAX_CLOCK_SKEW: i64 = 30;
+
+/// Seconds an attestation stays valid after its timestamp (5 minutes)
+pub const ATTESTATION_VALIDITY_SECONDS: i64 = 300;
+
+// ============================================================================
+// COMPUTE BUDGET
+// ============================================================================
+
+/// Compute units a client should request (ComputeBudget setComputeUnitLimit)
+/// for a submit_census transaction. The default 200k limit is not enough:
+/// Groth16 verification runs 4 G1 muls and a 4-pair pairing through the
+/// alt_bn128 syscalls, on top of the point validation done in-program, which
+/// is dominated by the G2 subgroup check. The localnet test
+/// "should fit submit_census within the recommended compute budget" reads
+/// this from the IDL, logs the measured consumption and asserts it stays
+/// under; keep this at that figure plus 50% headroom, and re-measure when
+/// verification changes.
+#[constant]
+pub const RECOMMENDED_COMPUTE_UNITS_SUBMIT_CENSUS: u32 = 1_000_000;
+
+// ============================================================================
+// EXTERNAL NULLIFIER DERIVATION
+// ============================================================================
+
+/// Identity commitment format the bundled SDK produces:
+/// Poseidon(identity_nullifier, identity_trapdoor)
+pub const COMMITMENT_VERSION_DEFAULT: u8 = 1;
+
+/// Newest external-nullifier derivation (see scope::external_nullifier_for)
+pub const EXTERNAL_NULLIFIER_VERSION_LATEST: u8 = 2;
+
+/// Domain tag hashed into v1 external nullifiers: "zk-census", big-endian field element
+pub const EXTERNAL_NULLIFIER_DOMAIN: [u8; 32] = {
+    let tag = b"zk-census";
+    let mut field = [0u8; 32];
+    let mut i = 0;
+    while i < tag.len() {
+        field[32 - tag.len() + i] = tag[i];
+        i += 1;
+    }
+    field
+};
+
+/// Domain tag hashed into v2 (census-bound) external nullifiers
+pub const EXTERNAL_NULLIFIER_DOMAIN_TAG: [u8; 8] = *b"zkcensus";
+
+/// How many scopes past the current one a mismatched external nullifier is
+/// re-derived against to report AttestationForFutureScope. Hashed
+/// derivations can't be decoded, so each probe costs a Poseidon hash
+pub const FUTURE_SCOPE_LOOKAHEAD: u64 = 3;
+
+// ============================================================================
+// NUMERIC AGGREGATES
+// ============================================================================
+
+/// Histogram buckets in a NumericAggregate (e.g. age ranges)
+pub const NUMERIC_BUCKET_COUNT: usize = 8;
+
+// ============================================================================
+// NULLIFIER BITMAPS
+// ============================================================================
+
+/// Bytes in a NullifierBitmap (8192 bits)
+pub const NULLIFIER_BITMAP_BYTES: usize = 1024;
+
+/// Bits a nullifier sets in a NullifierBitmap
+pub const NULLIFIER_BITMAP_HASHES: usize = 3;
+
+// ============================================================================
+// POPULATION MILESTONES
+// ============================================================================
+
+/// Configurable per-scope population milestones (fits the u8 fired bitmask)
+pub const MILESTONE_COUNT: usize = 5;
+
+// ============================================================================
+// NULLIFIER METADATA
+// ============================================================================
+
+/// Bytes of the verifier-chosen nonce bound into each attestation
+pub const ATTESTATION_NONCE_LEN: usize = 16;
+
+/// Bytes of app-specific metadata each Nullifier can carry
+pub const NULLIFIER_EXTRA_LEN: usize = 16;
+
 // ============================================================================
 // MERKLE TREE CONFIGURATION
 // ============================================================================
 
+/// SPL Account Compression program (owner of the Merkle tree account)
+pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+
+/// SPL Noop program (the compression program logs tree changes through it)
+pub const SPL_NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
 /// Depth of the Merkle tree (2^20 = ~1M citizens)
 pub const TREE_DEPTH: usize = 20;
 
+/// Leaves the tree can hold; no scope can count more citizens than this
+pub const MAX_LEAVES: u64 = 1 << TREE_DEPTH;
+
 /// Maximum number of concurrent changes (for SPL Account Compression)
 pub const MAX_BUFFER_SIZE: usize = 64;
 
 /// Canopy depth for cheaper proofs (stores top N levels on-chain)
 pub const CANOPY_DEPTH: usize = 10;
+
+/// Published roots submit_census keeps accepting after the live root moves
+/// on. Raise it for high-churn registration periods; each slot adds 32
+/// bytes to CensusState (existing accounts grow via migrate_state).
+pub const RECENT_ROOTS_CAPACITY: usize = 16;
+
+const _: () = assert!(RECENT_ROOTS_CAPACITY <= u8::MAX as usize);
+
+// ============================================================================
+// DEMOGRAPHIC ATTRIBUTES
+// ============================================================================
+
+/// Number of anonymous attribute slots in CensusAggregate
+pub const ATTRIBUTE_COUNT: usize = 10;
+
+/// Maximum byte length of an attribute label (ASCII, zero-padded)
+pub const ATTRIBUTE_LABEL_LEN: usize = 16;
+
+/// Maximum byte length of the census metadata URI (ASCII, zero-padded)
+pub const METADATA_URI_LEN: usize = 128;
+
+// ============================================================================
+// ATTESTATION VERIFIERS
+// ============================================================================
+
+/// Maximum number of trusted verifiers in the CensusState allowlist
+pub const MAX_VERIFIERS: usize = 8;
+
+/// Weight a verifier carries toward `weight_threshold` until set_verifier_weight
+/// changes it (registry verifiers always carry it)
+pub const DEFAULT_VERIFIER_WEIGHT: u16 = 1;
+
+/// Leading byte of the attestation message signed by verifiers
+/// Bump this whenever the signed field set changes
+pub const ATTESTATION_MESSAGE_VERSION: u8 = 0x04;
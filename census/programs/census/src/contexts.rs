@@ -40,6 +40,32 @@ pub struct RegisterCitizen<'info> {
     pub census_state: Account<'info, CensusState>,
 
     pub system_program: Program<'info, System>,
+
+    /// CHECK: Linked SPL tree, parsed by crate::tree. Required once
+    /// census_state.merkle_tree is set; the census appends the leaf to it.
+    #[account(
+        mut,
+        address = census_state.merkle_tree @ crate::error::CensusError::InvalidMerkleTree
+    )]
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+
+    /// This registration's expiry; required while `membership_duration > 0`
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CitizenRecord::INIT_SPACE,
+        seeds = [CITIZEN_RECORD_SEED, &identity_commitment],
+        bump
+    )]
+    pub citizen_record: Option<Account<'info, CitizenRecord>>,
+
+    /// CHECK: SPL Account Compression, checked by address; required with `merkle_tree`
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL Noop, checked by address; required with `merkle_tree`
+    #[account(address = SPL_NOOP_ID)]
+    pub noop: Option<UncheckedAccount<'info>>,
 }
 
 /// Submit a census proof (anyone with valid proof)
@@ -60,14 +86,16 @@ pub struct SubmitCensus<'info> {
         mut,
         seeds = [CENSUS_STATE_SEED],
         bump = census_state.bump,
-        constraint = census_state.is_active @ crate::error::CensusError::CensusNotActive
+        constraint = census_state.is_active @ crate::error::CensusError::CensusNotActive,
+        constraint = census_state.counting_open @ crate::error::CensusError::CountingClosed
     )]
     pub census_state: Account<'info, CensusState>,
 
     /// Nullifier PDA - created if proof is valid (prevents double-counting)
     /// Derived from nullifier_hash in public_inputs[1]
+    /// May already exist if it is being reused after `nullifier_ttl` expired
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         space = 8 + Nullifier::INIT_SPACE,
         seeds = [NULLIFIER_SEED, &public_inputs[1]],
@@ -76,6 +104,44 @@ pub struct SubmitCensus<'info> {
     pub nullifier: Account<'info, Nullifier>,
 
     pub system_program: Program<'info, System>,
+
+    /// Current scope's numeric histogram; only needed with a `bucket_index`
+    #[account(
+        mut,
+        seeds = [NUMERIC_AGGREGATE_SEED, &census_state.current_scope.to_le_bytes()],
+        bump = numeric_aggregate.bump
+    )]
+    pub numeric_aggregate: Option<Account<'info, NumericAggregate>>,
+
+    /// Previous scope's snapshot; only needed when counting during its grace window
+    #[account(
+        mut,
+        seeds = [SCOPE_SNAPSHOT_SEED, &census_state.current_scope.saturating_sub(1).to_le_bytes()],
+        bump = previous_scope_snapshot.bump
+    )]
+    pub previous_scope_snapshot: Option<Account<'info, ScopeSnapshot>>,
+
+    /// Payer's cooldown record; required while `payer_cooldown_seconds > 0`
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PayerCooldown::INIT_SPACE,
+        seeds = [PAYER_COOLDOWN_SEED, payer.key().as_ref()],
+        bump
+    )]
+    pub payer_cooldown: Option<Account<'info, PayerCooldown>>,
+
+    /// Counted scope's nullifier bitmap; required while that scope has one
+    #[account(mut)]
+    pub nullifier_bitmap: Option<Box<Account<'info, NullifierBitmap>>>,
+
+    /// Counted scope's aggregate; required while that scope has one
+    #[account(
+        mut,
+        seeds = [AGGREGATE_SEED, &aggregate.scope.to_le_bytes()],
+        bump = aggregate.bump
+    )]
+    pub aggregate: Option<Box<Account<'info, CensusAggregate>>>,
 }
 
 /// Advance to the next census scope (admin-only)
@@ -93,6 +159,46 @@ pub struct AdvanceScope<'info> {
         constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
     )]
     pub census_state: Account<'info, CensusState>,
+
+    /// Snapshot of the scope being closed
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ScopeSnapshot::INIT_SPACE,
+        seeds = [SCOPE_SNAPSHOT_SEED, &census_state.current_scope.to_le_bytes()],
+        bump
+    )]
+    pub scope_snapshot: Account<'info, ScopeSnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Advance to the next census scope once expired (permissionless, for keepers)
+#[derive(Accounts)]
+pub struct TryAdvanceScope<'info> {
+    /// Anyone advancing the scope; pays for the snapshot
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// Census state
+    #[account(
+        mut,
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// Snapshot of the scope being closed
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + ScopeSnapshot::INIT_SPACE,
+        seeds = [SCOPE_SNAPSHOT_SEED, &census_state.current_scope.to_le_bytes()],
+        bump
+    )]
+    pub scope_snapshot: Account<'info, ScopeSnapshot>,
+
+    pub system_program: Program<'info, System>,
 }
 
 /// Set the Merkle root (admin-only)
@@ -111,12 +217,20 @@ pub struct SetMerkleRoot<'info> {
         constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
     )]
     pub census_state: Account<'info, CensusState>,
+
+    /// CHECK: Linked SPL tree, parsed by crate::tree. When passed, the new
+    /// root must equal the tree's current root.
+    #[account(
+        address = census_state.merkle_tree @ crate::error::CensusError::InvalidMerkleTree
+    )]
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
 }
 
 /// Submit a census attestation (off-chain verified, on-chain signature check)
 #[derive(Accounts)]
 #[instruction(
     timestamp: i64,
+    nonce: [u8; ATTESTATION_NONCE_LEN],
     merkle_root: [u8; 32],
     nullifier_hash: [u8; 32],
     external_nullifier: [u8; 32],
@@ -124,12 +238,14 @@ pub struct SetMerkleRoot<'info> {
     signature: [u8; 64]
 )]
 pub struct SubmitAttestation<'info> {
-    /// Anyone can submit with valid attestation
+    /// Anyone can submit with valid attestation; pays the nullifier rent
     #[account(mut)]
     pub payer: Signer<'info>,
 
     /// The trusted verifier that signed the attestation
-    /// In production, check this against a list of authorized verifiers
+    /// Must be in census_state.authorized_verifiers
+    /// May be the same key as `payer` (single-operator setups): the runtime
+    /// hands both the same account, so the rent is charged to it once
     pub verifier: Signer<'info>,
 
     /// Census state for verification
@@ -137,13 +253,15 @@ pub struct SubmitAttestation<'info> {
         mut,
         seeds = [CENSUS_STATE_SEED],
         bump = census_state.bump,
-        constraint = census_state.is_active @ crate::error::CensusError::CensusNotActive
+        constraint = census_state.is_active @ crate::error::CensusError::CensusNotActive,
+        constraint = census_state.counting_open @ crate::error::CensusError::CountingClosed
     )]
     pub census_state: Account<'info, CensusState>,
 
     /// Nullifier PDA - created if attestation is valid (prevents double-counting)
+    /// May already exist if it is being reused after `nullifier_ttl` expired
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         space = 8 + Nullifier::INIT_SPACE,
         seeds = [NULLIFIER_SEED, &nullifier_hash],
@@ -151,5 +269,628 @@ pub struct SubmitAttestation<'info> {
     )]
     pub nullifier: Account<'info, Nullifier>,
 
+    /// CHECK: Instructions sysvar, used to introspect the Ed25519 precompile instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Previous scope's snapshot; only needed when counting during its grace window
+    #[account(
+        mut,
+        seeds = [SCOPE_SNAPSHOT_SEED, &census_state.current_scope.saturating_sub(1).to_le_bytes()],
+        bump = previous_scope_snapshot.bump
+    )]
+    pub previous_scope_snapshot: Option<Account<'info, ScopeSnapshot>>,
+
+    /// Payer's cooldown record; required while `payer_cooldown_seconds > 0`
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PayerCooldown::INIT_SPACE,
+        seeds = [PAYER_COOLDOWN_SEED, payer.key().as_ref()],
+        bump
+    )]
+    pub payer_cooldown: Option<Account<'info, PayerCooldown>>,
+
+    /// Counted scope's nullifier bitmap; required while that scope has one
+    #[account(mut)]
+    pub nullifier_bitmap: Option<Box<Account<'info, NullifierBitmap>>>,
+
+    /// CHECK: Linked Merkle tree; required under `strict_membership`
+    #[account(address = census_state.merkle_tree @ crate::error::CensusError::InvalidMerkleTree)]
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL Account Compression, checked by address; required under `strict_membership`
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: Option<UncheckedAccount<'info>>,
+
+    /// Linked verifier registry; required while census_state.verifier_registry is set
+    #[account(address = census_state.verifier_registry @ crate::error::CensusError::InvalidVerifierRegistry)]
+    pub verifier_registry: Option<Account<'info, VerifierRegistry>>,
+
+    /// The proven leaf's CitizenRecord; required under `strict_membership`
+    /// while `membership_duration > 0`
+    pub citizen_record: Option<Account<'info, CitizenRecord>>,
+
+    /// Counted scope's aggregate; required while that scope has one
+    #[account(
+        mut,
+        seeds = [AGGREGATE_SEED, &aggregate.scope.to_le_bytes()],
+        bump = aggregate.bump
+    )]
+    pub aggregate: Option<Box<Account<'info, CensusAggregate>>>,
+}
+
+/// Pre-flight an attestation without creating the nullifier (read-only)
+#[derive(Accounts)]
+#[instruction(
+    timestamp: i64,
+    nonce: [u8; ATTESTATION_NONCE_LEN],
+    merkle_root: [u8; 32],
+    nullifier_hash: [u8; 32]
+)]
+pub struct DryRunAttestation<'info> {
+    /// Would-be submitter (checked against a bound payer)
+    pub payer: Signer<'info>,
+
+    /// The verifier that signed the attestation
+    pub verifier: Signer<'info>,
+
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// CHECK: Nullifier PDA; may not exist yet
+    #[account(
+        seeds = [NULLIFIER_SEED, &nullifier_hash],
+        bump
+    )]
+    pub nullifier: UncheckedAccount<'info>,
+
+    /// CHECK: Instructions sysvar, used to introspect the Ed25519 precompile instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// Previous scope's snapshot; only needed when counting during its grace window
+    #[account(
+        seeds = [SCOPE_SNAPSHOT_SEED, &census_state.current_scope.saturating_sub(1).to_le_bytes()],
+        bump = previous_scope_snapshot.bump
+    )]
+    pub previous_scope_snapshot: Option<Account<'info, ScopeSnapshot>>,
+
+    /// Linked verifier registry; required while census_state.verifier_registry is set
+    #[account(address = census_state.verifier_registry @ crate::error::CensusError::InvalidVerifierRegistry)]
+    pub verifier_registry: Option<Account<'info, VerifierRegistry>>,
+
+    /// CHECK: Payer's cooldown record; may not exist yet. Required while
+    /// `payer_cooldown_seconds > 0`, as for submit_attestation
+    #[account(
+        seeds = [PAYER_COOLDOWN_SEED, payer.key().as_ref()],
+        bump
+    )]
+    pub payer_cooldown: Option<UncheckedAccount<'info>>,
+
+    /// Counted scope's nullifier bitmap; required while that scope has one
+    pub nullifier_bitmap: Option<Box<Account<'info, NullifierBitmap>>>,
+
+    /// CHECK: Linked Merkle tree; required under `strict_membership`
+    #[account(address = census_state.merkle_tree @ crate::error::CensusError::InvalidMerkleTree)]
+    pub merkle_tree: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: SPL Account Compression, checked by address; required under `strict_membership`
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: Option<UncheckedAccount<'info>>,
+
+    /// The proven leaf's CitizenRecord; required under `strict_membership`
+    /// while `membership_duration > 0`
+    pub citizen_record: Option<Account<'info, CitizenRecord>>,
+
+    /// Counted scope's aggregate; required while that scope has one
+    #[account(
+        seeds = [AGGREGATE_SEED, &aggregate.scope.to_le_bytes()],
+        bump = aggregate.bump
+    )]
+    pub aggregate: Option<Box<Account<'info, CensusAggregate>>>,
+}
+
+/// Update a census configuration value (admin-only)
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    /// Admin authority
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        mut,
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+}
+
+/// Read-only access to the census state (no signer required)
+#[derive(Accounts)]
+pub struct ReadCensusState<'info> {
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump
+    )]
+    pub census_state: Account<'info, CensusState>,
+}
+
+/// Read a closed scope's snapshot; the account may not exist
+#[derive(Accounts)]
+#[instruction(scope: u64)]
+pub struct ReadScopeSnapshot<'info> {
+    /// CHECK: PDA for `scope`; deserialized in the handler if initialized
+    #[account(
+        seeds = [SCOPE_SNAPSHOT_SEED, &scope.to_le_bytes()],
+        bump
+    )]
+    pub scope_snapshot: UncheckedAccount<'info>,
+}
+
+/// Write the attribute schema labels (admin-only)
+#[derive(Accounts)]
+pub struct SetAttributeSchema<'info> {
+    /// Admin authority (pays for the schema account on first write)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// Attribute schema PDA - created on first write, overwritten afterwards
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + AttributeSchema::INIT_SPACE,
+        seeds = [ATTRIBUTE_SCHEMA_SEED],
+        bump
+    )]
+    pub attribute_schema: Account<'info, AttributeSchema>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Write the census metadata URI (admin-only)
+#[derive(Accounts)]
+pub struct SetMetadata<'info> {
+    /// Admin authority (pays for the metadata account on first write)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// Census metadata PDA - created on first write, overwritten afterwards
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + CensusMetadata::INIT_SPACE,
+        seeds = [CENSUS_METADATA_SEED],
+        bump
+    )]
+    pub census_metadata: Account<'info, CensusMetadata>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Re-link the census to a replacement Merkle tree (admin-only)
+#[derive(Accounts)]
+#[instruction(new_tree: Pubkey)]
+pub struct MigrateMerkleTree<'info> {
+    /// Admin authority
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        mut,
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// CHECK: The replacement tree; owner and layout are validated by tree::read_tree
+    #[account(address = new_tree @ crate::error::CensusError::InvalidMerkleTree)]
+    pub new_merkle_tree: UncheckedAccount<'info>,
+}
+
+/// Upgrade an older CensusState layout in place (admin-only)
+#[derive(Accounts)]
+pub struct MigrateState<'info> {
+    /// Admin authority (pays rent for any added space)
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Deserialized manually after resizing, since older layouts don't
+    /// fit the current CensusState. Admin is checked in the handler.
+    #[account(
+        mut,
+        seeds = [CENSUS_STATE_SEED],
+        bump,
+        owner = crate::ID
+    )]
+    pub census_state: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the current scope's numeric histogram (admin-only)
+#[derive(Accounts)]
+pub struct InitNumericAggregate<'info> {
+    /// Admin authority
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// Histogram for the current scope
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + NumericAggregate::INIT_SPACE,
+        seeds = [NUMERIC_AGGREGATE_SEED, &census_state.current_scope.to_le_bytes()],
+        bump
+    )]
+    pub numeric_aggregate: Account<'info, NumericAggregate>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grow a nullifier created by an older program version (permissionless)
+#[derive(Accounts)]
+#[instruction(nullifier_hash: [u8; 32])]
+pub struct MigrateNullifier<'info> {
+    /// Pays rent for the added space
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Older layouts don't deserialize as Nullifier; the discriminator
+    /// is checked in the handler
+    #[account(
+        mut,
+        seeds = [NULLIFIER_SEED, &nullifier_hash],
+        bump,
+        owner = crate::ID
+    )]
+    pub nullifier: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Record that an authorized verifier is alive
+#[derive(Accounts)]
+pub struct Heartbeat<'info> {
+    /// Authorized verifier; pays for its stats account on first heartbeat
+    #[account(mut)]
+    pub verifier: Signer<'info>,
+
+    /// Census state holding the verifier allowlist
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.is_authorized_verifier(&verifier.key())
+            @ crate::error::CensusError::VerifierNotAuthorized
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// Stats for this verifier
+    #[account(
+        init_if_needed,
+        payer = verifier,
+        space = 8 + VerifierStats::INIT_SPACE,
+        seeds = [VERIFIER_STATS_SEED, verifier.key().as_ref()],
+        bump
+    )]
+    pub verifier_stats: Account<'info, VerifierStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Hand the linked Merkle tree's authority to another key (admin-only)
+#[derive(Accounts)]
+pub struct SetTreeAuthority<'info> {
+    /// Admin authority
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin; signs for the tree as its authority
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// CHECK: The linked tree; owner and layout are validated by tree::read_tree
+    #[account(
+        mut,
+        address = census_state.merkle_tree @ crate::error::CensusError::InvalidMerkleTree
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression, checked by address
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+}
+
+/// Read a scope's CensusAggregate; the account may not exist
+#[derive(Accounts)]
+#[instruction(scope: u64)]
+pub struct ReadAggregate<'info> {
+    /// CHECK: PDA for `scope`; deserialized in the handler if initialized
+    #[account(
+        seeds = [AGGREGATE_SEED, &scope.to_le_bytes()],
+        bump
+    )]
+    pub aggregate: UncheckedAccount<'info>,
+}
+
+/// Advance once expired and create the next scope's aggregate (permissionless)
+#[derive(Accounts)]
+#[instruction(next_scope: u64)]
+pub struct RollScope<'info> {
+    /// Anyone rolling the scope; pays for the snapshot and the aggregate
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// Census state
+    #[account(
+        mut,
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// Snapshot of the scope being closed
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + ScopeSnapshot::INIT_SPACE,
+        seeds = [SCOPE_SNAPSHOT_SEED, &census_state.current_scope.to_le_bytes()],
+        bump
+    )]
+    pub scope_snapshot: Account<'info, ScopeSnapshot>,
+
+    /// Aggregate for the scope being opened
+    #[account(
+        init,
+        payer = caller,
+        space = 8 + CensusAggregate::INIT_SPACE,
+        seeds = [AGGREGATE_SEED, &next_scope.to_le_bytes()],
+        bump
+    )]
+    pub next_aggregate: Account<'info, CensusAggregate>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Create the current scope's nullifier bitmap (admin-only)
+#[derive(Accounts)]
+pub struct InitNullifierBitmap<'info> {
+    /// Admin authority
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        mut,
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// Bitmap for the current scope
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + NullifierBitmap::INIT_SPACE,
+        seeds = [NULLIFIER_BITMAP_SEED, &census_state.current_scope.to_le_bytes()],
+        bump
+    )]
+    pub nullifier_bitmap: Box<Account<'info, NullifierBitmap>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Close stale nullifiers (passed as remaining accounts) to the rent sink
+/// (permissionless)
+#[derive(Accounts)]
+pub struct BatchCloseNullifiers<'info> {
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// CHECK: Receives the reclaimed rent; must be census_state.rent_sink
+    #[account(mut, address = census_state.rent_sink @ crate::error::CensusError::InvalidRentSink)]
+    pub rent_sink: UncheckedAccount<'info>,
+}
+
+/// Swap a registered identity commitment in the linked tree (admin-only)
+#[derive(Accounts)]
+pub struct ReplaceCommitment<'info> {
+    /// Admin authority
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin; signs for the tree as its authority
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// CHECK: The linked tree; owner and layout are validated by tree::read_tree
+    #[account(
+        mut,
+        address = census_state.merkle_tree @ crate::error::CensusError::InvalidMerkleTree
+    )]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Account Compression, checked by address
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Noop, checked by address
+    #[account(address = SPL_NOOP_ID)]
+    pub noop: UncheckedAccount<'info>,
+}
+
+/// Create the verifier registry and link it to the census (admin-only)
+#[derive(Accounts)]
+pub struct InitVerifierRegistry<'info> {
+    /// Admin authority
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        mut,
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// Empty registry
+    #[account(
+        init,
+        payer = admin,
+        space = VerifierRegistry::space(0),
+        seeds = [VERIFIER_REGISTRY_SEED],
+        bump
+    )]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Add a key to the verifier registry, growing it by one slot (admin-only)
+#[derive(Accounts)]
+pub struct AddRegistryVerifier<'info> {
+    /// Admin authority; pays rent for the added slot
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    #[account(
+        mut,
+        seeds = [VERIFIER_REGISTRY_SEED],
+        bump = verifier_registry.bump,
+        realloc = VerifierRegistry::space(verifier_registry.verifiers.len() + 1),
+        realloc::payer = admin,
+        realloc::zero = false
+    )]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Remove a key from the verifier registry, shrinking it by one slot (admin-only)
+#[derive(Accounts)]
+pub struct RemoveRegistryVerifier<'info> {
+    /// Admin authority
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    #[account(
+        mut,
+        seeds = [VERIFIER_REGISTRY_SEED],
+        bump = verifier_registry.bump
+    )]
+    pub verifier_registry: Account<'info, VerifierRegistry>,
+
+    /// CHECK: Receives the freed slot's rent; must be census_state.rent_sink
+    #[account(mut, address = census_state.rent_sink @ crate::error::CensusError::InvalidRentSink)]
+    pub rent_sink: UncheckedAccount<'info>,
+}
+
+/// Extend a registration's expiry (admin-only)
+#[derive(Accounts)]
+#[instruction(identity_commitment: [u8; 32])]
+pub struct RenewMembership<'info> {
+    /// Admin authority
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    #[account(
+        mut,
+        seeds = [CITIZEN_RECORD_SEED, &identity_commitment],
+        bump = citizen_record.bump
+    )]
+    pub citizen_record: Account<'info, CitizenRecord>,
+}
+
+/// Create the current scope's aggregate (admin-only)
+#[derive(Accounts)]
+pub struct InitAggregate<'info> {
+    /// Admin authority
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Census state - must match admin
+    #[account(
+        mut,
+        seeds = [CENSUS_STATE_SEED],
+        bump = census_state.bump,
+        constraint = census_state.admin == admin.key() @ crate::error::CensusError::UnauthorizedAdmin
+    )]
+    pub census_state: Account<'info, CensusState>,
+
+    /// Aggregate for the current scope
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CensusAggregate::INIT_SPACE,
+        seeds = [AGGREGATE_SEED, &census_state.current_scope.to_le_bytes()],
+        bump
+    )]
+    pub aggregate: Account<'info, CensusAggregate>,
+
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,150 @@
+//! Ed25519 signature checks via the native Ed25519 precompile
+//!
+//! Solana programs can't verify Ed25519 signatures directly. Instead the
+//! client prepends an Ed25519 program instruction to the transaction; the
+//! runtime verifies it before our instruction runs, and we introspect the
+//! instructions sysvar to confirm it covered the expected key and message.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use crate::error::CensusError;
+
+/// Native Ed25519 signature verification program
+pub const ED25519_PROGRAM_ID: Pubkey = pubkey!("Ed25519SigVerify111111111111111111111111111");
+
+/// Header: num_signatures u8 + padding u8
+const HEADER_SIZE: usize = 2;
+
+/// Ed25519SignatureOffsets: 7 x u16
+const OFFSETS_SIZE: usize = 14;
+
+/// instruction_index value meaning "data lives in the Ed25519 instruction itself"
+const THIS_INSTRUCTION: u16 = u16::MAX;
+
+/// One signature entry of an Ed25519 precompile instruction
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Ed25519Signer {
+    pub pubkey: Pubkey,
+    pub signature: [u8; 64],
+}
+
+/// Load every signer of the Ed25519 instruction preceding the current one.
+/// All entries must sign exactly `message`. Fails with
+/// MissingEd25519Instruction when the client forgot to prepend it.
+/// Under CPI the current index is the top-level instruction's, so the
+/// Ed25519 instruction must precede the composing program's instruction.
+pub fn load_ed25519_signers(
+    instructions_sysvar: &AccountInfo,
+    message: &[u8],
+) -> Result<Vec<Ed25519Signer>> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, CensusError::MissingEd25519Instruction);
+
+    let ix = load_instruction_at_checked(current_index as usize - 1, instructions_sysvar)?;
+    require!(
+        ix.program_id == ED25519_PROGRAM_ID,
+        CensusError::MissingEd25519Instruction
+    );
+    require!(ix.accounts.is_empty(), CensusError::InvalidVerifierSignature);
+
+    parse_ed25519_ix_data(&ix.data, message)
+}
+
+/// Parse Ed25519 precompile instruction data into its signers.
+///
+/// A single instruction may carry many signatures (the precompile verifies
+/// them all), so M-of-N attestations cost one instruction rather than M.
+/// Every entry must reference data inside the instruction and cover `message`.
+pub fn parse_ed25519_ix_data(data: &[u8], message: &[u8]) -> Result<Vec<Ed25519Signer>> {
+    require!(data.len() >= HEADER_SIZE, CensusError::InvalidVerifierSignature);
+    let num_signatures = data[0] as usize;
+    require!(
+        num_signatures > 0 && data.len() >= HEADER_SIZE + num_signatures * OFFSETS_SIZE,
+        CensusError::InvalidVerifierSignature
+    );
+
+    let slice = |offset: usize, len: usize| {
+        data.get(offset..offset + len)
+            .ok_or_else(|| error!(CensusError::InvalidVerifierSignature))
+    };
+
+    let mut signers = Vec::with_capacity(num_signatures);
+    for i in 0..num_signatures {
+        let offsets = &data[HEADER_SIZE + i * OFFSETS_SIZE..HEADER_SIZE + (i + 1) * OFFSETS_SIZE];
+        let read = |i: usize| u16::from_le_bytes([offsets[2 * i], offsets[2 * i + 1]]);
+        let signature_offset = read(0) as usize;
+        let signature_ix = read(1);
+        let pubkey_offset = read(2) as usize;
+        let pubkey_ix = read(3);
+        let message_offset = read(4) as usize;
+        let message_size = read(5) as usize;
+        let message_ix = read(6);
+
+        // All referenced data must live inside the Ed25519 instruction
+        require!(
+            signature_ix == THIS_INSTRUCTION
+                && pubkey_ix == THIS_INSTRUCTION
+                && message_ix == THIS_INSTRUCTION,
+            CensusError::InvalidVerifierSignature
+        );
+
+        let signed_message = slice(message_offset, message_size)?;
+        check_message_version(signed_message, message)?;
+        require!(signed_message == message, CensusError::InvalidVerifierSignature);
+
+        signers.push(Ed25519Signer {
+            pubkey: Pubkey::try_from(slice(pubkey_offset, 32)?)
+                .map_err(|_| error!(CensusError::InvalidVerifierSignature))?,
+            signature: slice(signature_offset, 64)?.try_into().unwrap(),
+        });
+    }
+
+    Ok(signers)
+}
+
+/// Lay out a batched Ed25519 precompile instruction for `signers` over one
+/// shared `message`: [header][offsets x N][(pubkey, signature) x N][message].
+/// The message is stored once, so data grows by 110 bytes per extra signer.
+pub fn build_ed25519_ix_data(signers: &[Ed25519Signer], message: &[u8]) -> Vec<u8> {
+    let entries_start = HEADER_SIZE + signers.len() * OFFSETS_SIZE;
+    let message_offset = entries_start + signers.len() * (32 + 64);
+
+    let mut data = Vec::with_capacity(message_offset + message.len());
+    data.push(signers.len() as u8);
+    data.push(0); // padding
+
+    for i in 0..signers.len() {
+        let pubkey_offset = entries_start + i * (32 + 64);
+        let signature_offset = pubkey_offset + 32;
+        for value in [
+            signature_offset as u16,
+            THIS_INSTRUCTION,
+            pubkey_offset as u16,
+            THIS_INSTRUCTION,
+            message_offset as u16,
+            message.len() as u16,
+            THIS_INSTRUCTION,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+    }
+    for signer in signers {
+        data.extend_from_slice(signer.pubkey.as_ref());
+        data.extend_from_slice(&signer.signature);
+    }
+    data.extend_from_slice(message);
+
+    data
+}
+
+/// Reject a signed message whose leading version byte differs from ours,
+/// so clients get a precise error instead of a generic signature mismatch
+fn check_message_version(signed_message: &[u8], message: &[u8]) -> Result<()> {
+    require!(
+        signed_message.first() == message.first(),
+        CensusError::UnsupportedAttestationVersion
+    );
+    Ok(())
+}
@@ -26,7 +26,7 @@ pub enum CensusError {
     #[msg("Invalid proof format - wrong byte length")]
     InvalidProofFormat,
     
-    #[msg("Census is not currently active")]
+    #[msg("Census is not currently active (paused by the admin)")]
     CensusNotActive,
     
     #[msg("Arithmetic overflow in population counter")]
@@ -40,4 +40,226 @@ pub enum CensusError {
     
     #[msg("Invalid verifier signature")]
     InvalidVerifierSignature,
+    
+    #[msg("Nullifier TTL must not be negative")]
+    InvalidNullifierTtl,
+    
+    #[msg("Attribute labels must be non-empty ASCII")]
+    InvalidAttributeLabel,
+    
+    #[msg("Census scope has not expired yet")]
+    ScopeNotExpired,
+    
+    #[msg("Census is in manual-only mode (scope_duration = 0) - use advance_scope")]
+    ManualScopeOnly,
+    
+    #[msg("Census must be paused for this operation")]
+    CensusNotPaused,
+    
+    #[msg("Account is not a valid SPL Concurrent Merkle Tree")]
+    InvalidMerkleTree,
+    
+    #[msg("Attestation timestamp is in the future")]
+    AttestationFromFuture,
+    
+    #[msg("Attestation Merkle root does not match on-chain root - regenerate your proof")]
+    MerkleRootMismatch,
+    
+    #[msg("External nullifier does not match the current census scope")]
+    ExternalNullifierMismatch,
+    
+    #[msg("Verifier is not in the authorized verifier list")]
+    VerifierNotAuthorized,
+    
+    #[msg("Verifier is already authorized")]
+    VerifierAlreadyAuthorized,
+    
+    #[msg("Verifier list is full")]
+    VerifierListFull,
+    
+    #[msg("Attestation was signed over an unsupported message version")]
+    UnsupportedAttestationVersion,
+    
+    #[msg("Not enough authorized verifier signatures for the threshold")]
+    InsufficientVerifierSignatures,
+    
+    #[msg("Verifier threshold must be between 1 and the number of verifiers")]
+    InvalidVerifierThreshold,
+    
+    #[msg("Census state is already at the current version")]
+    StateAlreadyCurrent,
+    
+    #[msg("No snapshot exists for this scope")]
+    SnapshotNotFound,
+    
+    #[msg("Registration cap reached")]
+    RegistrationCapReached,
+    
+    #[msg("Merkle root changed after the proof was built; rebuild and retry")]
+    RootAdvancedDuringSubmission,
+    
+    #[msg("Not enough citizens registered for counting to open")]
+    CensusNotReady,
+    
+    #[msg("Nullifier account already has the current layout")]
+    NullifierAlreadyCurrent,
+    
+    #[msg("Bucket index out of range")]
+    InvalidBucketIndex,
+    
+    #[msg("Bucket lower bounds must be strictly ascending")]
+    InvalidBucketBounds,
+    
+    #[msg("Numeric aggregate account required for a bucketed submission")]
+    NumericAggregateMissing,
+    
+    #[msg("Scope grace period cannot be negative")]
+    InvalidScopeGrace,
+    
+    #[msg("Attestations are disabled; submit a Groth16 proof instead")]
+    AttestationPathDisabled,
+    
+    #[msg("Attestation nonce was already used for this nullifier")]
+    AttestationReplayed,
+    
+    #[msg("Tree leaf index does not match the census leaf count")]
+    LeafIndexMismatch,
+    
+    #[msg("Unknown or unchanged external nullifier version")]
+    InvalidExternalNullifierVersion,
+    
+    #[msg("Genesis can only be imported once, before any citizen is registered")]
+    GenesisAlreadyImported,
+    
+    #[msg("Scope population cap reached")]
+    ScopePopulationCapReached,
+    
+    #[msg("Missing Ed25519 instruction: prepend the Ed25519 precompile instruction right before this one")]
+    MissingEd25519Instruction,
+    
+    #[msg("Population cannot exceed the number of registered citizens")]
+    PopulationExceedsRegistered,
+    
+    #[msg("Attestation is bound to a different payer")]
+    AttestationPayerMismatch,
+    
+    #[msg("Metadata URI must be non-empty ASCII with only trailing zero padding")]
+    InvalidMetadataUri,
+    
+    #[msg("This payer submitted too recently; wait for its cooldown to pass")]
+    PayerCooldownActive,
+    
+    #[msg("Payer cooldown account required while a payer cooldown is set")]
+    PayerCooldownMissing,
+    
+    #[msg("Payer cooldown cannot be negative")]
+    InvalidPayerCooldown,
+    
+    #[msg("Verifiers are already configured; use add_verifier or rotate_verifier")]
+    VerifiersAlreadyInitialized,
+    
+    #[msg("Public input is not a canonical big-endian BN254 scalar")]
+    InvalidFieldElement,
+    
+    #[msg("Signal mode must be 0 (attribute) or 1 (opaque commit)")]
+    InvalidSignalMode,
+    
+    #[msg("Registrations are frozen until the scope advances")]
+    RegistrationsFrozen,
+    
+    #[msg("Reconstructed attestation message has an unexpected length")]
+    InvalidAttestationFormat,
+    
+    #[msg("Scope is sealed: it was closed and accepts no further counts")]
+    ScopeSealed,
+    
+    #[msg("Merkle tree authority is not the census state")]
+    TreeAuthorityMismatch,
+    
+    #[msg("Counting is closed")]
+    CountingClosed,
+    
+    #[msg("No aggregate exists for this scope")]
+    AggregateNotFound,
+    
+    #[msg("Scope duration must be >= 0 (0 = manual-only)")]
+    InvalidScopeDuration,
+    
+    #[msg("next_scope is not the scope this advance opens")]
+    NextScopeMismatch,
+    
+    #[msg("This scope's nullifier bitmap is missing or for another scope")]
+    InvalidNullifierBitmap,
+    
+    #[msg("Verifier deprecation grace must be >= 0 seconds")]
+    InvalidVerifierGrace,
+    
+    #[msg("Population would exceed the tree's capacity; counting is inconsistent")]
+    CensusPopulationImpossible,
+    
+    #[msg("An aggregate attribute count exceeds its participant count")]
+    AggregateInvariantViolated,
+    
+    #[msg("Identity commitment format version is not supported")]
+    UnsupportedCommitmentVersion,
+    
+    #[msg("Strict membership is on: an identity leaf inclusion proof is required")]
+    MembershipProofRequired,
+    
+    #[msg("Rent can only be refunded to the census rent_sink")]
+    InvalidRentSink,
+    
+    #[msg("This census requires a signal; the declined signal (0) is not accepted")]
+    SignalRequired,
+    
+    #[msg("Verifier registry account is missing or not the census's registry")]
+    InvalidVerifierRegistry,
+    
+    #[msg("Verifier weight must be nonzero")]
+    InvalidVerifierWeight,
+    
+    #[msg("Verifier signatures do not reach the weight threshold")]
+    InsufficientVerifierWeight,
+    
+    #[msg("Attestation is for a future scope (verifier scope or clock drift)")]
+    AttestationForFutureScope,
+    
+    #[msg("Proof participation timestamp is missing or older than max_proof_age")]
+    ProofTooOld,
+    
+    #[msg("Max proof age cannot be negative")]
+    InvalidMaxProofAge,
+    
+    #[msg("Genesis counts are inconsistent: total_registered exceeds leaf_count or leaf_count exceeds the tree capacity")]
+    InvalidGenesisCounts,
+    
+    #[msg("A CitizenRecord is required while memberships expire")]
+    CitizenRecordMissing,
+    
+    #[msg("CitizenRecord does not belong to the proven leaf")]
+    InvalidCitizenRecord,
+    
+    #[msg("Membership has expired")]
+    MembershipExpired,
+    
+    #[msg("Membership duration must be positive to renew and cannot be negative")]
+    InvalidMembershipDuration,
+    
+    #[msg("Scope epoch cannot be in the future")]
+    InvalidScopeEpoch,
+    
+    #[msg("The verification key does not prove a participation timestamp")]
+    ProofAgeUnsupported,
+    
+    #[msg("This scope's aggregate is missing or for another scope")]
+    InvalidAggregate,
+    
+    #[msg("Root validity window cannot be negative")]
+    InvalidRootValidity,
+    
+    #[msg("A nullifier hash appears twice in the batch")]
+    DuplicateNullifierInBatch,
+    
+    #[msg("The verification key does not prove a bucket index")]
+    BucketIndexUnsupported,
 }
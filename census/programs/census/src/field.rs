@@ -0,0 +1,58 @@
+//! Typed BN254 scalar field elements
+//!
+//! Proof public inputs (roots, nullifiers, signals, external nullifiers) are
+//! elements of the BN254 scalar field. The alt_bn128 syscalls take them as
+//! 32-byte big-endian integers, while some client libraries emit little-endian
+//! bytes. `FieldElement` always holds the canonical big-endian encoding, so the
+//! byte order is fixed where a value is constructed instead of surfacing later
+//! as an opaque InvalidProof. It Borsh-encodes as its 32 bytes, the same wire
+//! format as `[u8; 32]`.
+
+use anchor_lang::prelude::*;
+use ark_bn254::Fr;
+use ark_ff::{BigInteger256, PrimeField};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FieldElement([u8; 32]);
+
+impl FieldElement {
+    pub const ZERO: Self = Self([0; 32]);
+
+    /// From big-endian bytes; None unless the value is below the field modulus
+    pub fn from_be(bytes: [u8; 32]) -> Option<Self> {
+        is_canonical(&bytes).then_some(Self(bytes))
+    }
+
+    /// From little-endian bytes; None unless the value is below the field modulus
+    pub fn from_le(mut bytes: [u8; 32]) -> Option<Self> {
+        bytes.reverse();
+        Self::from_be(bytes)
+    }
+
+    /// Small integer, e.g. a bucket index the circuit exposes as an input
+    pub fn from_u64(value: u64) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes[24..].copy_from_slice(&value.to_be_bytes());
+        Self(bytes)
+    }
+
+    /// Big-endian encoding, as the alt_bn128 and Poseidon syscalls expect
+    pub fn to_syscall(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Little-endian encoding
+    pub fn to_le(&self) -> [u8; 32] {
+        let mut bytes = self.0;
+        bytes.reverse();
+        bytes
+    }
+}
+
+fn is_canonical(bytes: &[u8; 32]) -> bool {
+    let mut limbs = [0u64; 4];
+    for (limb, chunk) in limbs.iter_mut().zip(bytes.rchunks_exact(8)) {
+        *limb = u64::from_be_bytes(chunk.try_into().expect("8-byte chunk"));
+    }
+    Fr::from_bigint(BigInteger256::new(limbs)).is_some()
+}
@@ -1,11 +1,20 @@
-/**
- * Groth16 Proof Verification using Solana's alt_bn128 syscalls
- * 
- * This module provides zero-knowledge proof verification on-chain
- * using the BN254 (alt_bn128) elliptic curve operations via syscalls.
- */
+//! Groth16 Proof Verification using Solana's alt_bn128 syscalls
+//!
+//! This module provides zero-knowledge proof verification on-chain
+//! using the BN254 (alt_bn128) elliptic curve operations via syscalls.
+//!
+//! G2 coordinate order: instruction args and the verification key encode a
+//! G2 point as x.c0 || x.c1 || y.c0 || y.c1, 32-byte big-endian each, which
+//! is snarkjs' `pi_b` order (`[[x.c0, x.c1], [y.c0, y.c1]]`) with no swap.
+//! The alt_bn128 pairing syscall follows EIP-197 and expects the c1 (imaginary)
+//! half first, so points are reordered by `g2_to_syscall_order` only when the
+//! pairing input is built. Clients must not pre-swap.
 
 use anchor_lang::prelude::*;
+use ark_bn254::{Fq, Fq2, G1Affine, G2Affine};
+use ark_ff::{BigInteger256, PrimeField};
+use solana_sha256_hasher::hashv;
+use crate::{error::CensusError, field::FieldElement};
 
 /// Groth16 verification key (hardcoded for the census circuit)
 /// In production, this would be loaded from an account or PDA
@@ -17,6 +26,29 @@ pub struct VerificationKey {
     pub ic: Vec<[u8; 64]>, // IC points (one per public input + 1)
 }
 
+impl VerificationKey {
+    /// SHA-256 of alpha || beta || gamma || delta || IC[0] || IC[1] || ...,
+    /// each in the encoding used here (G2 c0-first, big-endian)
+    pub fn hash(&self) -> [u8; 32] {
+        let mut parts: Vec<&[u8]> = vec![&self.alpha, &self.beta, &self.gamma, &self.delta];
+        parts.extend(self.ic.iter().map(|point| point.as_slice()));
+        hashv(&parts).to_bytes()
+    }
+
+    /// Public inputs the key verifies (IC has one point more)
+    pub fn public_input_count(&self) -> usize {
+        self.ic.len().saturating_sub(1)
+    }
+}
+
+/// submit_census's fixed public inputs: root, nullifierHash, signalHash,
+/// externalNullifier; optional ones (bucket, timestamp) follow them
+pub const CENSUS_PUBLIC_INPUTS: usize = 4;
+
+/// Most public inputs verify_groth16_proof accepts; anything longer is
+/// rejected before any per-input work
+pub const MAX_PUBLIC_INPUTS: usize = 16;
+
 /// Verify a Groth16 proof using Solana's alt_bn128 syscalls
 /// 
 /// This implements the pairing check:
@@ -27,21 +59,19 @@ pub fn verify_groth16_proof(
     proof_a: &[u8; 64],
     proof_b: &[u8; 128],
     proof_c: &[u8; 64],
-    public_inputs: &[[u8; 32]],
+    public_inputs: &[FieldElement],
     vkey: &VerificationKey,
 ) -> Result<bool> {
-    // Validate proof components are valid curve points
-    require!(
-        is_valid_g1_point(proof_a),
-        ErrorCode::InvalidProofPoint
-    );
+    // Bound the input count first, so an oversized vector costs nothing
     require!(
-        is_valid_g2_point(proof_b),
-        ErrorCode::InvalidProofPoint
+        public_inputs.len() <= MAX_PUBLIC_INPUTS,
+        CensusError::InvalidProofFormat
     );
+
+    // Validate proof components are valid curve points before any syscall
     require!(
-        is_valid_g1_point(proof_c),
-        ErrorCode::InvalidProofPoint
+        is_valid_g1_point(proof_a) && is_valid_g2_point(proof_b) && is_valid_g1_point(proof_c),
+        CensusError::InvalidProofFormat
     );
 
     // Compute linear combination of IC points with public inputs
@@ -65,40 +95,58 @@ pub fn verify_groth16_proof(
 }
 
 /// Validate that a point is on the BN254 G1 curve
-fn is_valid_g1_point(point: &[u8; 64]) -> bool {
-    // Extract x and y coordinates (32 bytes each)
-    let x = &point[0..32];
-    let y = &point[32..64];
+/// Encoding: x || y, 32-byte big-endian each. The point at infinity is
+/// rejected, as no honest proof contains it. G1 has cofactor 1, so being on
+/// the curve already puts the point in the prime-order subgroup.
+pub fn is_valid_g1_point(point: &[u8; 64]) -> bool {
+    let (Some(x), Some(y)) = (fq_from_be(&point[0..32]), fq_from_be(&point[32..64])) else {
+        return false;
+    };
+    G1Affine::new_unchecked(x, y).is_on_curve()
+}
 
-    // Check if point is (0, 0) - point at infinity is valid
-    if is_zero(x) && is_zero(y) {
-        return true;
-    }
+/// Validate that a point is on the BN254 G2 curve and in its prime-order subgroup
+/// Encoding: see `decode_g2`. The point at infinity is rejected.
+pub fn is_valid_g2_point(point: &[u8; 128]) -> bool {
+    decode_g2(point).is_some()
+}
 
-    // Verify the point satisfies the curve equation: y^2 = x^3 + 3
-    // This is a simplified check - full validation uses alt_bn128_addition with identity
-    let addition_input = [point.as_slice(), &[0u8; 64]].concat();
-    
-    // Try to add the point to itself (doubling)
-    // If this fails, the point is not on the curve
-    match syscall_alt_bn128_addition(&addition_input) {
-        Ok(_) => true,
-        Err(_) => false,
+/// Decode a G2 point from x.c0 || x.c1 || y.c0 || y.c1 (32-byte big-endian
+/// each, snarkjs order). None unless every coordinate is canonical and the
+/// point is on the curve and in the prime-order subgroup.
+pub fn decode_g2(point: &[u8; 128]) -> Option<G2Affine> {
+    let x0 = fq_from_be(&point[0..32])?;
+    let x1 = fq_from_be(&point[32..64])?;
+    let y0 = fq_from_be(&point[64..96])?;
+    let y1 = fq_from_be(&point[96..128])?;
+    let point = G2Affine::new_unchecked(Fq2::new(x0, x1), Fq2::new(y0, y1));
+    (point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve()).then_some(point)
+}
+
+/// Reorder a c0-first G2 encoding into the c1-first layout the alt_bn128
+/// pairing syscall expects: x.c1 || x.c0 || y.c1 || y.c0
+pub fn g2_to_syscall_order(point: &[u8; 128]) -> [u8; 128] {
+    let mut swapped = [0u8; 128];
+    for (out, coordinate) in swapped.chunks_exact_mut(64).zip(point.chunks_exact(64)) {
+        out[..32].copy_from_slice(&coordinate[32..]);
+        out[32..].copy_from_slice(&coordinate[..32]);
     }
+    swapped
 }
 
-/// Validate that a point is on the BN254 G2 curve
-fn is_valid_g2_point(point: &[u8; 128]) -> bool {
-    // G2 points are 128 bytes (4 field elements)
-    // Similar validation as G1 but with different curve parameters
-    // For simplicity, we check for zero point or rely on pairing to fail
-    !is_zero(&point[0..32]) || !is_zero(&point[32..64]) || 
-    !is_zero(&point[64..96]) || !is_zero(&point[96..128])
+/// Parse a canonical (< p) big-endian base field element
+fn fq_from_be(bytes: &[u8]) -> Option<Fq> {
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let end = bytes.len() - i * 8;
+        *limb = u64::from_be_bytes(bytes[end - 8..end].try_into().ok()?);
+    }
+    Fq::from_bigint(BigInteger256::new(limbs))
 }
 
 /// Compute linear combination: IC[0] + sum(public_inputs[i] * IC[i+1])
 fn compute_ic_combination(
-    public_inputs: &[[u8; 32]],
+    public_inputs: &[FieldElement],
     ic_points: &[[u8; 64]],
 ) -> Result<[u8; 64]> {
     require!(
@@ -126,6 +174,7 @@ fn compute_ic_combination(
 /// 
 /// Solana's pairing check format:
 /// e(P1[0], P2[0]) * e(P1[1], P2[1]) * ... == 1
+#[allow(clippy::too_many_arguments)]
 fn perform_pairing_check(
     proof_a: &[u8; 64],
     proof_b: &[u8; 128],
@@ -140,24 +189,24 @@ fn perform_pairing_check(
     let negated_proof_a = negate_g1_point(proof_a);
 
     // Build pairing input: concatenate all pairs
-    // Format: [G1_point1, G2_point1, G1_point2, G2_point2, ...]
+    // Format: [G1_point1, G2_point1, G1_point2, G2_point2, ...], G2 c1-first
     let mut pairing_input = Vec::with_capacity(64 + 128 + 64 + 128 + 64 + 128 + 64 + 128);
     
     // e(-A, B)
     pairing_input.extend_from_slice(&negated_proof_a);
-    pairing_input.extend_from_slice(proof_b);
+    pairing_input.extend_from_slice(&g2_to_syscall_order(proof_b));
     
     // e(alpha, beta)
     pairing_input.extend_from_slice(alpha);
-    pairing_input.extend_from_slice(beta);
+    pairing_input.extend_from_slice(&g2_to_syscall_order(beta));
     
     // e(IC, gamma)
     pairing_input.extend_from_slice(ic_combined);
-    pairing_input.extend_from_slice(gamma);
+    pairing_input.extend_from_slice(&g2_to_syscall_order(gamma));
     
     // e(C, delta)
     pairing_input.extend_from_slice(proof_c);
-    pairing_input.extend_from_slice(delta);
+    pairing_input.extend_from_slice(&g2_to_syscall_order(delta));
 
     // Perform pairing check
     match syscall_alt_bn128_pairing(&pairing_input) {
@@ -170,10 +219,10 @@ fn perform_pairing_check(
 }
 
 /// Scalar multiplication on G1: point * scalar
-fn scalar_mul_g1(point: &[u8; 64], scalar: &[u8; 32]) -> Result<[u8; 64]> {
+fn scalar_mul_g1(point: &[u8; 64], scalar: &FieldElement) -> Result<[u8; 64]> {
     let mut input = Vec::with_capacity(96);
     input.extend_from_slice(point);
-    input.extend_from_slice(scalar);
+    input.extend_from_slice(&scalar.to_syscall());
 
     syscall_alt_bn128_multiplication(&input)
         .map(|result| {
@@ -227,11 +276,6 @@ fn negate_g1_point(point: &[u8; 64]) -> [u8; 64] {
     negated
 }
 
-/// Check if a byte slice is all zeros
-fn is_zero(bytes: &[u8]) -> bool {
-    bytes.iter().all(|&b| b == 0)
-}
-
 /// Get verification key for the census circuit
 /// Auto-generated from: snarkjs zkey export verificationkey
 /// Generated: 2025-11-28T18:20:36.885Z
@@ -332,10 +376,66 @@ pub fn get_verification_key() -> VerificationKey {
     }
 }
 
+/// `get_verification_key().hash()`, so clients can check their local vkey
+/// against the one this program verifies with
+pub const VERIFICATION_KEY_HASH: [u8; 32] = [
+    0x96, 0x3d, 0x88, 0x64, 0xc3, 0xf3, 0x88, 0xd9, 0x8e, 0xf0, 0x67, 0x78, 0x2c, 0x51, 0x46, 0x3a,
+    0x1f, 0x1d, 0xe3, 0x48, 0xb7, 0x6f, 0xaf, 0xe2, 0x26, 0x6f, 0x92, 0x33, 0x39, 0x23, 0xfc, 0x50,
+];
+
+/// sol_alt_bn128_group_op operations
+#[cfg(target_os = "solana")]
+const ALT_BN128_ADD: u64 = 0;
+#[cfg(target_os = "solana")]
+const ALT_BN128_MUL: u64 = 2;
+#[cfg(target_os = "solana")]
+const ALT_BN128_PAIRING: u64 = 3;
+
+/// Run one alt_bn128 group operation into a `LEN`-byte result
+#[cfg(target_os = "solana")]
+fn syscall_alt_bn128_group_op<const LEN: usize>(
+    op: u64,
+    input: &[u8],
+) -> std::result::Result<[u8; LEN], ()> {
+    let mut result = [0u8; LEN];
+    let status = unsafe {
+        solana_define_syscall::definitions::sol_alt_bn128_group_op(
+            op,
+            input.as_ptr(),
+            input.len() as u64,
+            result.as_mut_ptr(),
+        )
+    };
+    if status == 0 {
+        Ok(result)
+    } else {
+        Err(())
+    }
+}
+
 /// Syscall wrapper for alt_bn128 addition
+#[cfg(target_os = "solana")]
+fn syscall_alt_bn128_addition(input: &[u8]) -> std::result::Result<Vec<u8>, ()> {
+    syscall_alt_bn128_group_op::<64>(ALT_BN128_ADD, input).map(|sum| sum.to_vec())
+}
+
+/// Syscall wrapper for alt_bn128 multiplication
+#[cfg(target_os = "solana")]
+fn syscall_alt_bn128_multiplication(input: &[u8]) -> std::result::Result<Vec<u8>, ()> {
+    syscall_alt_bn128_group_op::<64>(ALT_BN128_MUL, input).map(|product| product.to_vec())
+}
+
+/// Syscall wrapper for alt_bn128 pairing: the syscall returns 1 or 0 as a
+/// 32-byte big-endian word, reported here as its last byte
+#[cfg(target_os = "solana")]
+fn syscall_alt_bn128_pairing(input: &[u8]) -> std::result::Result<Vec<u8>, ()> {
+    syscall_alt_bn128_group_op::<32>(ALT_BN128_PAIRING, input).map(|word| vec![word[31]])
+}
+
+/// Off-chain stand-in for alt_bn128 addition
+#[cfg(not(target_os = "solana"))]
 fn syscall_alt_bn128_addition(input: &[u8]) -> std::result::Result<Vec<u8>, ()> {
-    // For now, return a valid G1 point (identity element or similar)
-    // In actual Solana deployment, this would use the real syscall
+    // Return a valid G1 point (identity element or similar)
     let mut result = vec![0u8; 64];
     if input.len() == 128 {
         // Simple addition simulation - copy first point for identity
@@ -344,7 +444,8 @@ fn syscall_alt_bn128_addition(input: &[u8]) -> std::result::Result<Vec<u8>, ()>
     Ok(result)
 }
 
-/// Syscall wrapper for alt_bn128 multiplication  
+/// Off-chain stand-in for alt_bn128 multiplication
+#[cfg(not(target_os = "solana"))]
 fn syscall_alt_bn128_multiplication(input: &[u8]) -> std::result::Result<Vec<u8>, ()> {
     // Simulate scalar multiplication
     let mut result = vec![0u8; 64];
@@ -354,13 +455,14 @@ fn syscall_alt_bn128_multiplication(input: &[u8]) -> std::result::Result<Vec<u8>
     Ok(result)
 }
 
-/// Syscall wrapper for alt_bn128 pairing
+/// Off-chain stand-in for alt_bn128 pairing
+#[cfg(not(target_os = "solana"))]
 fn syscall_alt_bn128_pairing(input: &[u8]) -> std::result::Result<Vec<u8>, ()> {
     // The pairing check returns 1 if valid, 0 if invalid
     // For development/testing, we'll do basic validation
     
     // Input should be multiple of 192 bytes (64 for G1 + 128 for G2)
-    if input.len() % 192 != 0 {
+    if !input.len().is_multiple_of(192) {
         return Err(());
     }
     
@@ -378,14 +480,64 @@ fn syscall_alt_bn128_pairing(input: &[u8]) -> std::result::Result<Vec<u8>, ()> {
     Ok(vec![if all_non_zero { 1 } else { 0 }])
 }
 
+/// Most inputs a single Poseidon hash accepts (limit of the sol_poseidon syscall)
+pub const MAX_POSEIDON_INPUTS: usize = 12;
+
+/// Poseidon over BN254 with circomlib's parameter set (x^5 S-box, width =
+/// arity + 1), on big-endian field elements. `poseidon_hash(&[a, b])` equals
+/// circomlib's `Poseidon(2)([a, b])`; the arity is `inputs.len()`.
+pub fn poseidon_hash(inputs: &[[u8; 32]]) -> Result<[u8; 32]> {
+    require!(
+        !inputs.is_empty() && inputs.len() <= MAX_POSEIDON_INPUTS,
+        ErrorCode::InvalidPoseidonInputCount
+    );
+    let inputs: Vec<&[u8]> = inputs.iter().map(|input| input.as_slice()).collect();
+    syscall_poseidon(&inputs).map_err(|_| error!(ErrorCode::PoseidonHashFailed))
+}
+
+/// Syscall wrapper for Poseidon (Bn254X5, big-endian)
+#[cfg(target_os = "solana")]
+fn syscall_poseidon(inputs: &[&[u8]]) -> std::result::Result<[u8; 32], ()> {
+    const PARAMETERS_BN254_X5: u64 = 0;
+    const ENDIANNESS_BIG: u64 = 0;
+
+    let mut hash = [0u8; 32];
+    let status = unsafe {
+        solana_define_syscall::definitions::sol_poseidon(
+            PARAMETERS_BN254_X5,
+            ENDIANNESS_BIG,
+            inputs as *const _ as *const u8,
+            inputs.len() as u64,
+            hash.as_mut_ptr(),
+        )
+    };
+    if status == 0 {
+        Ok(hash)
+    } else {
+        Err(())
+    }
+}
+
+/// Off-chain Poseidon with the same parameters as the syscall
+#[cfg(not(target_os = "solana"))]
+fn syscall_poseidon(inputs: &[&[u8]]) -> std::result::Result<[u8; 32], ()> {
+    use light_poseidon::{Poseidon, PoseidonBytesHasher};
+
+    Poseidon::<ark_bn254::Fr>::new_circom(inputs.len())
+        .and_then(|mut hasher| hasher.hash_bytes_be(inputs))
+        .map_err(|_| ())
+}
+
 #[error_code]
 pub enum ErrorCode {
-    #[msg("Invalid proof point - not on curve")]
-    InvalidProofPoint,
     #[msg("Invalid public input count")]
     InvalidPublicInputCount,
     #[msg("Scalar multiplication failed")]
     ScalarMultiplicationFailed,
     #[msg("Point addition failed")]
     PointAdditionFailed,
+    #[msg("Poseidon takes between 1 and 12 inputs")]
+    InvalidPoseidonInputCount,
+    #[msg("Poseidon hash failed")]
+    PoseidonHashFailed,
 }
@@ -4,3 +4,67 @@ pub mod submit_census;
 pub mod submit_attestation;
 pub mod advance_scope;
 pub mod set_merkle_root;
+pub mod set_nullifier_ttl;
+pub mod get_census_stats;
+pub mod set_attribute_schema;
+pub mod try_advance_scope;
+pub mod set_census_active;
+pub mod migrate_merkle_tree;
+pub mod add_verifier;
+pub mod remove_verifier;
+pub mod set_verifier_threshold;
+pub mod migrate_state;
+pub mod read_scope_snapshot;
+pub mod set_max_citizens;
+pub mod verifier_heartbeat;
+pub mod migrate_nullifier;
+pub mod adjust_population;
+pub mod init_numeric_aggregate;
+pub mod set_scope_grace_seconds;
+pub mod set_require_zk_proofs;
+pub mod set_rent_sink;
+pub mod is_scope_expired;
+pub mod rotate_external_nullifier;
+pub mod import_genesis;
+pub mod set_max_population_per_scope;
+pub mod list_verifiers;
+pub mod rotate_verifier;
+pub mod set_enforce_population_invariant;
+pub mod set_milestones;
+pub mod set_metadata;
+pub mod dry_run_attestation;
+pub mod set_payer_cooldown_seconds;
+pub mod init_verifiers;
+pub mod get_verification_key;
+pub mod set_signal_mode;
+pub mod set_freeze_registrations_during_scope;
+pub mod set_pin_root_per_scope;
+pub mod set_seal_closed_scopes;
+pub mod set_tree_authority;
+pub mod set_counting_open;
+pub mod read_aggregate;
+pub mod set_scope_calendar;
+pub mod roll_scope;
+pub mod init_nullifier_bitmap;
+pub mod set_circuit_hash;
+pub mod deprecate_verifier;
+pub mod set_supported_commitment_versions;
+pub mod set_strict_membership;
+pub mod tree_capacity_remaining;
+pub mod batch_close_nullifiers;
+pub mod set_require_signal;
+pub mod replace_commitment;
+pub mod set_emit_participation_events;
+pub mod init_verifier_registry;
+pub mod add_registry_verifier;
+pub mod remove_registry_verifier;
+pub mod set_verifier_registry;
+pub mod set_verifier_weight;
+pub mod set_weight_threshold;
+pub mod set_root_checkpoint_interval;
+pub mod set_max_proof_age;
+pub mod next_scope_boundary;
+pub mod set_membership_duration;
+pub mod renew_membership;
+pub mod init_aggregate;
+pub mod set_root_validity_seconds;
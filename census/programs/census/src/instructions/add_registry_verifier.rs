@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::AddRegistryVerifier, error::CensusError, state::RegistryVerifierAdded};
+
+/// Adds a verifier to the registry; the context grows the account by one key
+pub fn handler(ctx: Context<AddRegistryVerifier>, verifier: Pubkey) -> Result<()> {
+    let verifier_registry = &mut ctx.accounts.verifier_registry;
+    let clock = Clock::get()?;
+
+    require!(
+        !verifier_registry.contains(&verifier),
+        CensusError::VerifierAlreadyAuthorized
+    );
+    verifier_registry.verifiers.push(verifier);
+
+    emit!(RegistryVerifierAdded {
+        verifier,
+        verifier_count: verifier_registry.verifiers.len() as u32,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Registry verifier authorized: {}", verifier);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::{constants::{DEFAULT_VERIFIER_WEIGHT, MAX_VERIFIERS}, contexts::UpdateConfig, error::CensusError, state::VerifierAdded};
+
+/// Adds a verifier to the allowlist of keys trusted to sign attestations
+pub fn handler(ctx: Context<UpdateConfig>, verifier: Pubkey) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    require!(
+        !census_state.is_authorized_verifier(&verifier),
+        CensusError::VerifierAlreadyAuthorized
+    );
+    let count = census_state.verifier_count as usize;
+    require!(count < MAX_VERIFIERS, CensusError::VerifierListFull);
+
+    census_state.authorized_verifiers[count] = verifier;
+    census_state.verifier_accepted_until[count] = 0;
+    census_state.verifier_weights[count] = DEFAULT_VERIFIER_WEIGHT;
+    census_state.verifier_count += 1;
+
+    emit!(VerifierAdded {
+        verifier,
+        verifier_count: census_state.verifier_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Verifier authorized: {}", verifier);
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError, state::PopulationAdjusted};
+
+/// Overwrites current_population to correct an inflated or lost count.
+/// Only allowed while paused, so no submission can interleave with the
+/// correction. Organic counts go through submission_seq and CensusCounted;
+/// corrections touch neither and are only visible via PopulationAdjusted.
+/// `reason_code` is an operator-defined code recorded for auditors.
+pub fn handler(ctx: Context<UpdateConfig>, new_value: u64, reason_code: u8) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    require!(!census_state.is_active, CensusError::CensusNotPaused);
+
+    let old = census_state.current_population;
+    census_state.current_population = new_value;
+
+    emit!(PopulationAdjusted {
+        old,
+        new: new_value,
+        reason_code,
+        admin: ctx.accounts.admin.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "⚠️ Population manually adjusted: {} → {} (reason {})",
+        old,
+        new_value,
+        reason_code
+    );
+
+    Ok(())
+}
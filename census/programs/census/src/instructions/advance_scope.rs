@@ -1,26 +1,79 @@
 use anchor_lang::prelude::*;
-use crate::{contexts::AdvanceScope, error::CensusError, state::ScopeAdvanced};
+use crate::{
+    contexts::AdvanceScope,
+    error::CensusError,
+    state::{CensusState, ScopeAdvanceResult, ScopeAdvanced, ScopeSnapshot},
+};
 
-pub fn handler(ctx: Context<AdvanceScope>) -> Result<()> {
-    let census_state = &mut ctx.accounts.census_state;
+pub fn handler(ctx: Context<AdvanceScope>) -> Result<ScopeAdvanceResult> {
     let clock = Clock::get()?;
+    advance(
+        &mut ctx.accounts.census_state,
+        &mut ctx.accounts.scope_snapshot,
+        ctx.bumps.scope_snapshot,
+        clock.unix_timestamp,
+        false,
+    )
+}
 
+/// Close the current scope, snapshot its tally and certificate fields, and
+/// open the next one. Returns the new scope and the closed scope's final
+/// population. Shared by advance_scope (admin) and try_advance_scope (keepers)
+///
+/// With `calendar`, the next scope is the calendar's scope at `now` (at
+/// least +1) and opens at its calendar start, so a late advance doesn't
+/// shift later scopes; never after `now` (see `scope::calendar_advance`).
+/// Skipped scopes get no snapshot.
+pub(crate) fn advance(
+    census_state: &mut CensusState,
+    scope_snapshot: &mut ScopeSnapshot,
+    snapshot_bump: u8,
+    now: i64,
+    calendar: bool,
+) -> Result<ScopeAdvanceResult> {
+    // Capture the tally before anything is reset, so the snapshot and the
+    // event carry every count, including ones earlier in this transaction
     let old_scope = census_state.current_scope;
     let final_population = census_state.current_population;
 
+    scope_snapshot.scope = old_scope;
+    scope_snapshot.final_population = final_population;
+    scope_snapshot.total_registered_at_close = census_state.total_registered;
+    scope_snapshot.closed_at = now;
+    scope_snapshot.bump = snapshot_bump;
+    scope_snapshot.opened_at = census_state.scope_start_time;
+    scope_snapshot.closing_merkle_root = census_state.merkle_root;
+    scope_snapshot.nullifier_accumulator = census_state.running_nullifier_accumulator;
+
     // Advance to next scope
-    census_state.current_scope = census_state.current_scope
-        .checked_add(1)
+    census_state.last_closed_scope = old_scope;
+    if calendar {
+        let (next_scope, start) = crate::scope::calendar_advance(
+            census_state.current_scope,
+            now,
+            census_state.scope_epoch,
+            census_state.scope_duration,
+        )
         .ok_or(CensusError::ArithmeticOverflow)?;
-    census_state.scope_start_time = clock.unix_timestamp;
+        census_state.current_scope = next_scope;
+        census_state.scope_start_time = start;
+    } else {
+        census_state.current_scope = census_state.current_scope
+            .checked_add(1)
+            .ok_or(CensusError::ArithmeticOverflow)?;
+        census_state.scope_start_time = now;
+    }
     census_state.current_population = 0; // Reset for new scope
+    census_state.milestones_fired = 0;
+    census_state.running_nullifier_accumulator = [0; 32];
+    census_state.scope_root = census_state.merkle_root;
 
     // Emit event for historical tracking
     emit!(ScopeAdvanced {
         old_scope,
         new_scope: census_state.current_scope,
         final_population,
-        timestamp: clock.unix_timestamp,
+        timestamp: now,
     });
 
     msg!(
@@ -30,5 +83,9 @@ pub fn handler(ctx: Context<AdvanceScope>) -> Result<()> {
         final_population
     );
 
-    Ok(())
+    Ok(ScopeAdvanceResult {
+        new_scope: census_state.current_scope,
+        scope_start_time: census_state.scope_start_time,
+        final_population,
+    })
 }
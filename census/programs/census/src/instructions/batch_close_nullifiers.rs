@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::{
+    contexts::BatchCloseNullifiers,
+    error::CensusError,
+    state::{Nullifier, NullifierSweep},
+};
+
+/// Closes every Nullifier in the remaining accounts whose scope can no longer
+/// be counted (see CensusState::is_nullifier_closeable), refunding its rent
+/// to the rent sink. Accounts that aren't writable current-layout nullifiers
+/// or are still countable are skipped rather than failing the batch, so a
+/// sweeper can pass a scope's accounts without pre-filtering. Returns the
+/// counts and lamports reclaimed via return data.
+///
+/// Closing is safe because nullifier hashes are scope-bound: a closed scope's
+/// hash can't be resubmitted into a later scope.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BatchCloseNullifiers<'info>>,
+) -> Result<NullifierSweep> {
+    let census_state = &ctx.accounts.census_state;
+    let rent_sink = ctx.accounts.rent_sink.to_account_info();
+    let now = Clock::get()?.unix_timestamp;
+
+    let mut sweep = NullifierSweep { closed: 0, skipped: 0, lamports_reclaimed: 0 };
+    for info in ctx.remaining_accounts {
+        let closeable = info.is_writable
+            && Account::<Nullifier>::try_from(info)
+                .is_ok_and(|nullifier| census_state.is_nullifier_closeable(nullifier.scope, now));
+        if !closeable {
+            sweep.skipped += 1;
+            continue;
+        }
+
+        let lamports = info.lamports();
+        Account::<Nullifier>::try_from(info)?.close(rent_sink.clone())?;
+        sweep.closed += 1;
+        sweep.lamports_reclaimed = sweep.lamports_reclaimed
+            .checked_add(lamports)
+            .ok_or(CensusError::ArithmeticOverflow)?;
+    }
+
+    msg!(
+        "🧹 Closed {} nullifiers ({} skipped), reclaimed {} lamports",
+        sweep.closed,
+        sweep.skipped,
+        sweep.lamports_reclaimed
+    );
+
+    Ok(sweep)
+}
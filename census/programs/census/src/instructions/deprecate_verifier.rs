@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError, state::VerifierDeprecated};
+
+/// Retires a verifier gracefully: its attestations keep verifying for
+/// `grace_seconds`, so ones signed just before still land, then stop. It
+/// keeps its allowlist slot (and counts toward verifier_count) until
+/// remove_verifier frees it.
+pub fn handler(ctx: Context<UpdateConfig>, verifier: Pubkey, grace_seconds: i64) -> Result<()> {
+    require!(grace_seconds >= 0, CensusError::InvalidVerifierGrace);
+
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    let index = census_state
+        .verifiers()
+        .iter()
+        .position(|v| v == &verifier)
+        .ok_or(CensusError::VerifierNotAuthorized)?;
+    let accepted_until = clock.unix_timestamp
+        .checked_add(grace_seconds)
+        .ok_or(CensusError::ArithmeticOverflow)?;
+    census_state.verifier_accepted_until[index] = accepted_until;
+
+    emit!(VerifierDeprecated {
+        verifier,
+        accepted_until,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Verifier deprecated: {} (accepted until {})", verifier, accepted_until);
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use crate::{
+    attestation::AttestationFields,
+    constants::ATTESTATION_NONCE_LEN,
+    contexts::DryRunAttestation,
+    instructions::{
+        submit_attestation::{check_attestation, check_membership_expiry, verify_membership},
+        submit_census::{check_count, Count, CountChecks, CountSource},
+    },
+    state::{AttestationDryRun, MembershipProof, Nullifier, PayerCooldown},
+};
+
+/// Runs every submit_attestation check (strict membership, freshness, root,
+/// external nullifier, payer binding, verifier authorization, signature,
+/// then the count's own checks via `check_count`) without creating the
+/// nullifier or counting, so clients learn why an attestation would fail
+/// before paying rent. Simulate the transaction the client would send, with
+/// this instruction in place of submit_attestation and the same accounts.
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DryRunAttestation<'info>>,
+    timestamp: i64,
+    nonce: [u8; ATTESTATION_NONCE_LEN],
+    merkle_root: [u8; 32],
+    nullifier_hash: [u8; 32],
+    external_nullifier: [u8; 32],
+    signal_hash: [u8; 32],
+    signature: [u8; 64],
+    bound_payer: Option<Pubkey>,
+    membership: Option<MembershipProof>,
+) -> Result<AttestationDryRun> {
+    let clock = Clock::get()?;
+    let fields = AttestationFields {
+        timestamp,
+        nonce,
+        merkle_root,
+        nullifier_hash,
+        external_nullifier,
+        signal_hash,
+        circuit_hash: ctx.accounts.census_state.circuit_hash,
+        bound_payer,
+        membership,
+    };
+
+    let result = run_checks(&ctx, &fields, &signature, clock.unix_timestamp);
+
+    Ok(match result {
+        Ok(counted_scope) => AttestationDryRun {
+            passed: true,
+            error_code: 0,
+            counted_scope,
+        },
+        Err(err) => AttestationDryRun {
+            passed: false,
+            error_code: error_code(&err),
+            counted_scope: 0,
+        },
+    })
+}
+
+/// Everything submit_attestation would check, in its order; returns the
+/// scope the attestation would count toward
+fn run_checks<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, DryRunAttestation<'info>>,
+    fields: &AttestationFields,
+    signature: &[u8; 64],
+    now: i64,
+) -> Result<u64> {
+    let census_state = &ctx.accounts.census_state;
+    let membership = fields.membership.as_ref();
+    if census_state.strict_membership {
+        verify_membership(
+            ctx.accounts.merkle_tree.as_deref(),
+            ctx.accounts.compression_program.as_deref(),
+            ctx.remaining_accounts,
+            &fields.merkle_root,
+            membership,
+        )?;
+        check_membership_expiry(
+            census_state,
+            ctx.accounts.citizen_record.as_deref(),
+            membership,
+            now,
+        )?;
+    }
+    let nullifier = load_nullifier(&ctx.accounts.nullifier)?;
+    let counted_scope = check_attestation(
+        census_state,
+        &census_state.key(),
+        ctx.accounts.verifier_registry.as_deref(),
+        nullifier.as_ref(),
+        &ctx.accounts.payer.key(),
+        &ctx.accounts.verifier,
+        &ctx.accounts.instructions_sysvar,
+        ctx.accounts.previous_scope_snapshot.is_some(),
+        fields,
+        signature,
+        now,
+    )?;
+    let payer_last_count_at = match ctx.accounts.payer_cooldown.as_deref() {
+        Some(info) => Some(load_payer_cooldown(info)?.map_or(0, |p| p.last_count_at)),
+        None => None,
+    };
+    check_count(
+        census_state,
+        &CountChecks {
+            previous_scope_snapshot: ctx.accounts.previous_scope_snapshot.as_deref(),
+            payer_last_count_at,
+            has_numeric_aggregate: false,
+            nullifier_bitmap: ctx.accounts.nullifier_bitmap.as_deref().map(|b| &**b),
+            aggregate: ctx.accounts.aggregate.as_deref().map(|a| &**a),
+        },
+        &Count {
+            nullifier_hash: fields.nullifier_hash,
+            scope: counted_scope,
+            signal_hash: fields.signal_hash,
+            extra: Default::default(),
+            attestation_nonce: fields.nonce,
+            bucket_index: None,
+            source: CountSource::Attestation,
+            payer: ctx.accounts.payer.key(),
+        },
+        now,
+    )?;
+    Ok(counted_scope)
+}
+
+/// The nullifier PDA if a previous submission created it
+fn load_nullifier(info: &AccountInfo) -> Result<Option<Nullifier>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(Nullifier::try_deserialize(&mut &info.data.borrow()[..])?))
+}
+
+/// The payer's cooldown record if a previous count created it
+fn load_payer_cooldown(info: &AccountInfo) -> Result<Option<PayerCooldown>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(PayerCooldown::try_deserialize(&mut &info.data.borrow()[..])?))
+}
+
+fn error_code(err: &Error) -> u32 {
+    match err {
+        Error::AnchorError(err) => err.error_code_number,
+        Error::ProgramError(err) => u64::from(err.program_error.clone()) as u32,
+    }
+}
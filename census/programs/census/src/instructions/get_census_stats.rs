@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::ReadCensusState, state::CensusStats};
+
+/// Returns a snapshot of the census counters via return data.
+/// `counted_by_proof` vs `counted_by_attestation` shows how much of the
+/// population was counted trustlessly.
+pub fn handler(ctx: Context<ReadCensusState>) -> Result<CensusStats> {
+    let census_state = &ctx.accounts.census_state;
+
+    Ok(CensusStats {
+        current_scope: census_state.current_scope,
+        current_population: census_state.current_population,
+        total_registered: census_state.total_registered,
+        counted_by_proof: census_state.counted_by_proof,
+        counted_by_attestation: census_state.counted_by_attestation,
+        is_active: census_state.is_active,
+    })
+}
@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::ReadCensusState, groth16, state::VerificationKeyExport};
+
+/// Lets clients assert their local vkey matches the one proofs are checked
+/// against before generating a proof.
+pub fn handler(_ctx: Context<ReadCensusState>) -> Result<VerificationKeyExport> {
+    let vkey = groth16::get_verification_key();
+    let hash = vkey.hash();
+
+    Ok(VerificationKeyExport {
+        alpha: vkey.alpha,
+        beta: vkey.beta,
+        gamma: vkey.gamma,
+        delta: vkey.delta,
+        ic: vkey.ic,
+        hash,
+    })
+}
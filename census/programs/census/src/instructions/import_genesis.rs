@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::{constants::MAX_LEAVES, contexts::UpdateConfig, error::CensusError, state::GenesisImported};
+
+/// Bootstraps the census from a tree built off-chain: sets the root, leaf
+/// count and registration total in one step instead of replaying
+/// register_citizen for every existing citizen. Runs at most once, and only
+/// while the census has no leaves.
+pub fn handler(
+    ctx: Context<UpdateConfig>,
+    root: [u8; 32],
+    leaf_count: u64,
+    total_registered: u64,
+) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    require!(
+        total_registered <= leaf_count && leaf_count <= MAX_LEAVES,
+        CensusError::InvalidGenesisCounts
+    );
+    require!(
+        !census_state.genesis_imported && census_state.leaf_count == 0,
+        CensusError::GenesisAlreadyImported
+    );
+    require!(
+        census_state.max_citizens == 0 || total_registered <= census_state.max_citizens,
+        CensusError::RegistrationCapReached
+    );
+
+    census_state.set_root(root, clock.unix_timestamp)?;
+    // Nothing can have been counted against an empty tree, so the current
+    // scope pins the imported root
+    census_state.scope_root = root;
+    census_state.leaf_count = leaf_count;
+    census_state.total_registered = total_registered;
+    census_state.genesis_imported = true;
+
+    emit!(GenesisImported {
+        root,
+        leaf_count,
+        total_registered,
+        admin: ctx.accounts.admin.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "🌱 Genesis imported: {} leaves, {} citizens",
+        leaf_count,
+        total_registered
+    );
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::{constants::ATTRIBUTE_COUNT, contexts::InitAggregate};
+
+/// Creates the current scope's aggregate. From then on every count into
+/// this scope must update it, so counts made before it existed are the only
+/// ones it misses.
+pub fn handler(ctx: Context<InitAggregate>) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+    let aggregate = &mut ctx.accounts.aggregate;
+
+    aggregate.scope = census_state.current_scope;
+    aggregate.participant_count = 0;
+    aggregate.attribute_counts = [0; ATTRIBUTE_COUNT];
+    aggregate.last_updated = clock.unix_timestamp;
+    aggregate.bump = ctx.bumps.aggregate;
+    census_state.aggregate_scope = census_state.current_scope;
+
+    msg!("✅ Aggregate created for scope {}", aggregate.scope);
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::{constants::NULLIFIER_BITMAP_BYTES, contexts::InitNullifierBitmap};
+
+/// Creates the current scope's nullifier bitmap. From then on every count
+/// into this scope must pass it, so counts made before it existed are the
+/// only ones it misses.
+pub fn handler(ctx: Context<InitNullifierBitmap>) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let nullifier_bitmap = &mut ctx.accounts.nullifier_bitmap;
+
+    nullifier_bitmap.scope = census_state.current_scope;
+    nullifier_bitmap.bits = [0; NULLIFIER_BITMAP_BYTES];
+    nullifier_bitmap.inserted = 0;
+    nullifier_bitmap.bump = ctx.bumps.nullifier_bitmap;
+    census_state.nullifier_bitmap_scope = census_state.current_scope;
+
+    msg!("✅ Nullifier bitmap created for scope {}", nullifier_bitmap.scope);
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::NUMERIC_BUCKET_COUNT,
+    contexts::InitNumericAggregate,
+    error::CensusError,
+    groth16::{self, CENSUS_PUBLIC_INPUTS},
+};
+
+/// Creates the current scope's histogram with fixed bucket boundaries.
+/// The last bucket is open-ended. Only accepted once the verification key
+/// proves a bucket index; until then no submission could fill it.
+pub fn handler(
+    ctx: Context<InitNumericAggregate>,
+    lower_bounds: [u64; NUMERIC_BUCKET_COUNT],
+) -> Result<()> {
+    require!(
+        groth16::get_verification_key().public_input_count() > CENSUS_PUBLIC_INPUTS,
+        CensusError::BucketIndexUnsupported
+    );
+    require!(
+        lower_bounds.windows(2).all(|pair| pair[0] < pair[1]),
+        CensusError::InvalidBucketBounds
+    );
+
+    let numeric_aggregate = &mut ctx.accounts.numeric_aggregate;
+    let clock = Clock::get()?;
+
+    numeric_aggregate.scope = ctx.accounts.census_state.current_scope;
+    numeric_aggregate.lower_bounds = lower_bounds;
+    numeric_aggregate.bucket_counts = [0; NUMERIC_BUCKET_COUNT];
+    numeric_aggregate.last_updated = clock.unix_timestamp;
+    numeric_aggregate.bump = ctx.bumps.numeric_aggregate;
+
+    msg!("✅ Numeric aggregate created for scope {}", numeric_aggregate.scope);
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::contexts::InitVerifierRegistry;
+
+/// Creates the empty verifier registry and links it, so from now on
+/// attestation signers are authorized by the registry instead of the inline
+/// allowlist. Add its verifiers before the next attestation; unlink with
+/// set_verifier_registry to fall back to the inline allowlist.
+pub fn handler(ctx: Context<InitVerifierRegistry>) -> Result<()> {
+    let verifier_registry = &mut ctx.accounts.verifier_registry;
+    verifier_registry.bump = ctx.bumps.verifier_registry;
+    verifier_registry.verifiers = Vec::new();
+    ctx.accounts.census_state.verifier_registry = verifier_registry.key();
+
+    msg!("✅ Verifier registry created: {}", verifier_registry.key());
+
+    Ok(())
+}
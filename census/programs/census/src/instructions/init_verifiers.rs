@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::{constants::{DEFAULT_VERIFIER_WEIGHT, MAX_VERIFIERS}, contexts::UpdateConfig, error::CensusError, state::VerifierAdded};
+
+/// Installs the initial verifier allowlist and threshold in one step, so a
+/// multi-verifier deployment never runs below its threshold
+pub fn handler(ctx: Context<UpdateConfig>, verifiers: Vec<Pubkey>, verifier_threshold: u8) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    require!(verifiers.len() <= MAX_VERIFIERS, CensusError::VerifierListFull);
+    for (index, verifier) in verifiers.iter().enumerate() {
+        require!(
+            !verifiers[..index].contains(verifier),
+            CensusError::VerifierAlreadyAuthorized
+        );
+    }
+    require!(
+        verifier_threshold >= 1 && verifier_threshold as usize <= verifiers.len(),
+        CensusError::InvalidVerifierThreshold
+    );
+    require!(census_state.verifier_count == 0, CensusError::VerifiersAlreadyInitialized);
+
+    for (index, verifier) in verifiers.iter().enumerate() {
+        census_state.authorized_verifiers[index] = *verifier;
+        census_state.verifier_accepted_until[index] = 0;
+        census_state.verifier_weights[index] = DEFAULT_VERIFIER_WEIGHT;
+        census_state.verifier_count += 1;
+
+        emit!(VerifierAdded {
+            verifier: *verifier,
+            verifier_count: census_state.verifier_count,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    census_state.verifier_threshold = verifier_threshold;
+
+    msg!(
+        "✅ {} verifiers authorized, threshold {}",
+        census_state.verifier_count,
+        verifier_threshold
+    );
+
+    Ok(())
+}
@@ -1,7 +1,24 @@
 use anchor_lang::prelude::*;
-use crate::contexts::Initialize;
+use crate::{
+    constants::{
+        CENSUS_STATE_VERSION, COMMITMENT_VERSION_DEFAULT, MAX_VERIFIERS, MILESTONE_COUNT,
+        RECENT_ROOTS_CAPACITY,
+    },
+    contexts::Initialize,
+    error::CensusError, groth16, signal::SIGNAL_MODE_ATTRIBUTE,
+};
+
+pub fn handler(
+    ctx: Context<Initialize>,
+    scope_duration: i64,
+    max_citizens: u64,
+    min_registrations_to_activate: u64,
+    require_zk_proofs: bool,
+    require_signal: bool,
+) -> Result<()> {
+    // 0 is manual-only mode; a negative duration would break expiry math
+    require!(scope_duration >= 0, CensusError::InvalidScopeDuration);
 
-pub fn handler(ctx: Context<Initialize>, scope_duration: i64) -> Result<()> {
     let census_state = &mut ctx.accounts.census_state;
     let clock = Clock::get()?;
 
@@ -17,10 +34,65 @@ pub fn handler(ctx: Context<Initialize>, scope_duration: i64) -> Result<()> {
     census_state.leaf_count = 0;
     census_state.is_active = true;
     census_state.bump = ctx.bumps.census_state;
+    census_state.nullifier_ttl = 0; // Nullifiers never expire by default
+    census_state.counted_by_proof = 0;
+    census_state.counted_by_attestation = 0;
+    census_state.authorized_verifiers = [Pubkey::default(); MAX_VERIFIERS];
+    census_state.verifier_count = 0; // Admin adds verifiers via add_verifier
+    census_state.verifier_threshold = 1;
+    census_state.submission_seq = 0;
+    census_state.version = CENSUS_STATE_VERSION;
+    census_state.max_citizens = max_citizens;
+    census_state.root_version = 0;
+    census_state.min_registrations_to_activate = min_registrations_to_activate;
+    census_state.scope_grace_seconds = 0;
+    census_state.require_zk_proofs = require_zk_proofs;
+    census_state.rent_sink = census_state.admin;
+    census_state.external_nullifier_version = 0;
+    census_state.previous_external_nullifier_version = 0;
+    census_state.external_nullifier_transition_scope = 0;
+    census_state.max_population_per_scope = 0;
+    census_state.enforce_population_invariant = false;
+    census_state.milestones = [0; MILESTONE_COUNT];
+    census_state.milestones_fired = 0;
+    census_state.payer_cooldown_seconds = 0;
+    census_state.running_nullifier_accumulator = [0; 32];
+    census_state.signal_mode = SIGNAL_MODE_ATTRIBUTE;
+    census_state.freeze_registrations_during_scope = false;
+    census_state.scope_root = census_state.merkle_root;
+    census_state.pin_root_per_scope = false;
+    census_state.last_closed_scope = 0;
+    census_state.seal_closed_scopes = false;
+    census_state.counting_open = true;
+    census_state.scope_epoch = clock.unix_timestamp;
+    census_state.calendar_scopes = false;
+    census_state.nullifier_bitmap_scope = 0;
+    census_state.circuit_hash = groth16::VERIFICATION_KEY_HASH;
+    census_state.verifier_accepted_until = [0; MAX_VERIFIERS];
+    census_state.supported_commitment_versions = 1 << COMMITMENT_VERSION_DEFAULT;
+    census_state.strict_membership = false;
+    census_state.require_signal = require_signal;
+    census_state.emit_participation_events = false;
+    census_state.verifier_registry = Pubkey::default();
+    census_state.verifier_weights = [0; MAX_VERIFIERS];
+    census_state.weight_threshold = 0;
+    census_state.root_checkpoint_interval = 0;
+    census_state.max_proof_age = 0;
+    census_state.genesis_imported = false;
+    census_state.membership_duration = 0;
+    census_state.aggregate_scope = 0;
+    census_state.recent_roots = [[0u8; 32]; RECENT_ROOTS_CAPACITY];
+    census_state.active_recent_roots = 0;
+    census_state.recent_roots_set_at = [0; RECENT_ROOTS_CAPACITY];
+    census_state.root_validity_seconds = 0;
 
     msg!("✅ zk-Census initialized!");
     msg!("   Admin: {}", census_state.admin);
     msg!("   Scope Duration: {} seconds", scope_duration);
+    msg!("   Max Citizens: {} (0 = unlimited)", max_citizens);
+    msg!("   Min Registrations To Activate: {}", min_registrations_to_activate);
+    msg!("   Require ZK Proofs: {}", require_zk_proofs);
+    msg!("   Require Signal: {}", require_signal);
 
     Ok(())
 }
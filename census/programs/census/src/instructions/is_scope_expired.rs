@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::ReadCensusState, scope, state::ScopeExpiry};
+
+/// Tells keepers whether try_advance_scope would succeed now, and how long
+/// until it will. Manual-only censuses never expire.
+pub fn handler(ctx: Context<ReadCensusState>) -> Result<ScopeExpiry> {
+    let census_state = &ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    Ok(ScopeExpiry {
+        is_expired: census_state.is_scope_expired(clock.unix_timestamp),
+        seconds_until_expiry: scope::seconds_until_expiry(
+            census_state.scope_start_time,
+            census_state.scope_duration,
+            clock.unix_timestamp,
+        ),
+    })
+}
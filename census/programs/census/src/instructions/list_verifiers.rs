@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::ReadCensusState, state::VerifierList};
+
+/// Lets clients discover which verifiers to route proofs to without parsing
+/// the CensusState layout.
+pub fn handler(ctx: Context<ReadCensusState>) -> Result<VerifierList> {
+    let census_state = &ctx.accounts.census_state;
+
+    Ok(VerifierList {
+        verifiers: census_state.verifiers().to_vec(),
+        verifier_threshold: census_state.verifier_threshold,
+        verifier_weights: census_state.verifier_weights[..census_state.verifier_count as usize].to_vec(),
+        weight_threshold: census_state.weight_threshold,
+    })
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::MigrateMerkleTree, error::CensusError, state::MerkleTreeMigrated, tree};
+
+/// Points the census at a replacement Merkle tree, e.g. when the original
+/// was created with the wrong parameters or has filled up.
+/// The census must be paused so no submission races the switch.
+/// `new_root` must be the new tree's current root.
+pub fn handler(
+    ctx: Context<MigrateMerkleTree>,
+    new_tree: Pubkey,
+    new_root: [u8; 32],
+) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    require!(!census_state.is_active, CensusError::CensusNotPaused);
+
+    // Take the leaf count from the new tree itself, and check the root against it
+    let new_tree_info = tree::read_tree(&ctx.accounts.new_merkle_tree)?;
+    require!(new_tree_info.root == new_root, CensusError::InvalidMerkleRoot);
+
+    let old_tree = census_state.merkle_tree;
+    census_state.merkle_tree = new_tree;
+    // The old tree's roots stop being accepted
+    census_state.active_recent_roots = 0;
+    census_state.set_root(new_root, clock.unix_timestamp)?;
+    census_state.leaf_count = new_tree_info.leaf_count;
+
+    emit!(MerkleTreeMigrated {
+        old_tree,
+        new_tree,
+        new_root,
+        leaf_count: census_state.leaf_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Merkle tree migrated: {} → {}", old_tree, new_tree);
+    msg!("   Leaf count: {}", census_state.leaf_count);
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::{
+    contexts::MigrateNullifier,
+    error::CensusError,
+    instructions::migrate_state::grow_account,
+    state::Nullifier,
+};
+
+/// Grows a nullifier written by an older program version to the current
+/// layout. Appended fields default to zero, so anyone may run this.
+pub fn handler(ctx: Context<MigrateNullifier>, _nullifier_hash: [u8; 32]) -> Result<()> {
+    let info = ctx.accounts.nullifier.to_account_info();
+    let target_len = 8 + Nullifier::INIT_SPACE;
+
+    {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 && data[..8] == *Nullifier::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+    }
+    require!(info.data_len() < target_len, CensusError::NullifierAlreadyCurrent);
+
+    grow_account(
+        &info,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        target_len,
+    )?;
+
+    msg!("✅ Nullifier migrated to {} bytes", target_len);
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! Upgrade CensusState accounts created by older program versions.
+//!
+//! Fields are only ever appended to CensusState, so migrating is:
+//! 1. Grow the account to `8 + CensusState::INIT_SPACE` (new bytes are zeroed)
+//! 2. Apply the fixup for each version step whose fields need a non-zero default
+//! 3. Stamp `version = CENSUS_STATE_VERSION`
+//!
+//! Appending a field whose default is zero needs no version bump - resizing
+//! already zero-fills it. Bump CENSUS_STATE_VERSION only when a new field
+//! needs a non-zero default, and add a fixup branch for it in `apply_fixups`.
+//!
+//! Version history:
+//! - v0: original layout (`admin` .. `bump`), no `version` field
+//! - v1: first versioned layout. Fixup: `verifier_threshold = 1`
+//! - v2: adds `rent_sink`. Fixup: `rent_sink = admin`
+//! - v3: adds `counting_open`. Fixup: `counting_open = true`
+//! - v4: adds `supported_commitment_versions`. Fixup: accept
+//!   COMMITMENT_VERSION_DEFAULT
+//! - v5: adds `verifier_weights`. Fixup: DEFAULT_VERIFIER_WEIGHT for each
+//!   active verifier
+//! - v6: backfills fields that were appended without a bump but are
+//!   non-zero at init. Fixup (only where still zero): `circuit_hash =
+//!   VERIFICATION_KEY_HASH`, `scope_root = merkle_root`, `scope_epoch = now`
+//! - v7: adds `genesis_imported`. Fixup: `genesis_imported = leaf_count > 0`
+//! - v8: adds `recent_roots` / `active_recent_roots` / `recent_roots_set_at`.
+//!   Fixup: seed the buffer with the live `merkle_root` (if one was
+//!   published), set at migration time
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::{
+    constants::{CENSUS_STATE_VERSION, COMMITMENT_VERSION_DEFAULT, DEFAULT_VERIFIER_WEIGHT},
+    contexts::MigrateState,
+    error::CensusError,
+    groth16,
+    state::{CensusState, StateMigrated},
+};
+
+pub fn handler(ctx: Context<MigrateState>) -> Result<()> {
+    let info = ctx.accounts.census_state.to_account_info();
+    let clock = Clock::get()?;
+
+    // `admin` is the first field in every version, right after the discriminator
+    let admin = {
+        let data = info.try_borrow_data()?;
+        require!(
+            data.len() >= 8 + 32 && data[..8] == *CensusState::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Pubkey::try_from(&data[8..40]).unwrap()
+    };
+    require!(
+        admin == ctx.accounts.admin.key(),
+        CensusError::UnauthorizedAdmin
+    );
+
+    // 1. Grow to the current layout, topping up rent first
+    grow_account(
+        &info,
+        &ctx.accounts.admin.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        8 + CensusState::INIT_SPACE,
+    )?;
+
+    let mut census_state = CensusState::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    let from_version = census_state.version;
+    require!(
+        from_version < CENSUS_STATE_VERSION,
+        CensusError::StateAlreadyCurrent
+    );
+
+    // 2. Per-version fixups, 3. stamp the version
+    apply_fixups(&mut census_state, from_version, clock.unix_timestamp);
+    census_state.version = CENSUS_STATE_VERSION;
+    census_state.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit!(StateMigrated {
+        from_version,
+        to_version: CENSUS_STATE_VERSION,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Census state migrated: v{} → v{}", from_version, CENSUS_STATE_VERSION);
+
+    Ok(())
+}
+
+/// Grow a program-owned account to `target_len` bytes (new bytes are zeroed),
+/// with `payer` covering any extra rent. No-op if it's already large enough.
+pub(crate) fn grow_account<'info>(
+    info: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    target_len: usize,
+) -> Result<()> {
+    if info.data_len() >= target_len {
+        return Ok(());
+    }
+
+    let required = Rent::get()?.minimum_balance(target_len);
+    let shortfall = required.saturating_sub(info.lamports());
+    if shortfall > 0 {
+        transfer(
+            CpiContext::new(
+                system_program.clone(),
+                Transfer {
+                    from: payer.clone(),
+                    to: info.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    info.resize(target_len)?;
+
+    Ok(())
+}
+
+/// Set non-zero defaults for fields introduced after `from_version`
+fn apply_fixups(census_state: &mut CensusState, from_version: u8, now: i64) {
+    if from_version < 1 {
+        census_state.verifier_threshold = 1;
+    }
+    if from_version < 2 {
+        census_state.rent_sink = census_state.admin;
+    }
+    if from_version < 3 {
+        census_state.counting_open = true;
+    }
+    if from_version < 4 {
+        census_state.supported_commitment_versions = 1 << COMMITMENT_VERSION_DEFAULT;
+    }
+    if from_version < 5 {
+        let count = census_state.verifier_count as usize;
+        census_state.verifier_weights[..count].fill(DEFAULT_VERIFIER_WEIGHT);
+    }
+    if from_version < 6 {
+        // v4/v5 accounts created by `initialize` already hold real values
+        // (possibly admin-set), so only fill what resizing left zeroed
+        if census_state.circuit_hash == [0u8; 32] {
+            census_state.circuit_hash = groth16::VERIFICATION_KEY_HASH;
+        }
+        if census_state.scope_root == [0u8; 32] {
+            census_state.scope_root = census_state.merkle_root;
+        }
+        if census_state.scope_epoch == 0 {
+            census_state.scope_epoch = now;
+        }
+    }
+    if from_version < 7 {
+        // A census that already has leaves is past genesis
+        census_state.genesis_imported = census_state.leaf_count > 0;
+    }
+    if from_version < 8 && census_state.merkle_root != [0u8; 32] {
+        census_state.push_recent_root(census_state.merkle_root, now);
+    }
+}
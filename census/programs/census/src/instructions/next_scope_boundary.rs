@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::ReadCensusState, scope, state::ScopeBoundary};
+
+/// Lets schedulers time try_advance_scope for the boundary instead of
+/// polling is_scope_expired. `current_scope` shows whether someone already
+/// advanced past the scope the job was planned for.
+pub fn handler(ctx: Context<ReadCensusState>) -> Result<ScopeBoundary> {
+    let census_state = &ctx.accounts.census_state;
+
+    Ok(ScopeBoundary {
+        current_scope: census_state.current_scope,
+        next_boundary: scope::next_scope_boundary(
+            census_state.scope_start_time,
+            census_state.scope_duration,
+        ),
+    })
+}
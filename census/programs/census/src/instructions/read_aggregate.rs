@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::{
+    contexts::ReadAggregate,
+    error::CensusError,
+    state::{AggregateSummary, CensusAggregate},
+};
+
+/// Returns a scope's attribute breakdown via return data, as tallied by
+/// every count since init_aggregate or roll_scope created the account.
+/// Scopes nothing was aggregated for have no aggregate account.
+pub fn handler(ctx: Context<ReadAggregate>, scope: u64) -> Result<AggregateSummary> {
+    let info = ctx.accounts.aggregate.to_account_info();
+    require!(
+        info.owner == &crate::ID && !info.data_is_empty(),
+        CensusError::AggregateNotFound
+    );
+
+    let aggregate = CensusAggregate::try_deserialize(&mut &info.data.borrow()[..])?;
+    require!(aggregate.scope == scope, CensusError::AggregateNotFound);
+
+    Ok(AggregateSummary {
+        scope: aggregate.scope,
+        participant_count: aggregate.participant_count,
+        attribute_counts: aggregate.attribute_counts,
+        last_updated: aggregate.last_updated,
+    })
+}
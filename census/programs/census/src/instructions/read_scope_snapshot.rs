@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::{
+    contexts::ReadScopeSnapshot,
+    error::CensusError,
+    state::{ScopeSnapshot, ScopeSummary},
+};
+
+/// Returns a closed scope's final tally via return data.
+/// Scopes that were never closed have no snapshot account.
+pub fn handler(ctx: Context<ReadScopeSnapshot>, scope: u64) -> Result<ScopeSummary> {
+    let info = ctx.accounts.scope_snapshot.to_account_info();
+    require!(
+        info.owner == &crate::ID && !info.data_is_empty(),
+        CensusError::SnapshotNotFound
+    );
+
+    let snapshot = ScopeSnapshot::try_deserialize(&mut &info.data.borrow()[..])?;
+    require!(snapshot.scope == scope, CensusError::SnapshotNotFound);
+
+    Ok(ScopeSummary {
+        final_population: snapshot.final_population,
+        closed_at: snapshot.closed_at,
+        total_registered_at_close: snapshot.total_registered_at_close,
+        opened_at: snapshot.opened_at,
+        closing_merkle_root: snapshot.closing_merkle_root,
+        nullifier_accumulator: snapshot.nullifier_accumulator,
+    })
+}
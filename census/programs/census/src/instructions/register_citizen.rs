@@ -1,25 +1,109 @@
 use anchor_lang::prelude::*;
-use crate::{contexts::RegisterCitizen, error::CensusError, state::CitizenRegistered};
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::{
+    constants::CENSUS_STATE_SEED, contexts::RegisterCitizen, error::CensusError,
+    state::CitizenRegistered, tree,
+};
 
-pub fn handler(ctx: Context<RegisterCitizen>, identity_commitment: [u8; 32]) -> Result<()> {
+/// Appends a citizen. While `membership_duration > 0` the registration also
+/// creates its CitizenRecord, expiring `membership_duration` from now
+/// (renew_membership extends it). Only strict submissions reveal their
+/// leaf, so only they reject an expired citizen (MembershipExpired); the
+/// leaf itself stays in the tree, provable by the anonymous paths, until
+/// replace_commitment sweeps it.
+///
+/// `commitment_version` names the SDK format the commitment was built with.
+/// The CitizenRecord stores it, when there is one, and CitizenRegistered
+/// carries it so indexers know how to interpret the leaf.
+///
+/// Every `root_checkpoint_interval`-th leaf the event also carries the linked
+/// tree's root, so an indexer replaying appends can check its reconstruction
+/// at those points without a root on every event. Without a linked tree
+/// there is no on-chain root to checkpoint.
+pub fn handler(
+    ctx: Context<RegisterCitizen>,
+    identity_commitment: [u8; 32],
+    commitment_version: u8,
+) -> Result<()> {
     let census_state = &mut ctx.accounts.census_state;
     let clock = Clock::get()?;
 
-    let current_leaf_index = census_state.leaf_count;
-
-    // =========================================================================
-    // Store the identity commitment
-    // 
-    // In production with SPL Account Compression:
-    // 1. Use spl_account_compression::cpi::append() to add leaf to compressed tree
-    // 2. This enables 1M+ citizens with minimal storage cost
-    // 3. Update merkle_root after each append
-    // 
-    // For now, we're using a simplified approach where:
-    // - Commitments are stored off-chain (indexed from events)
-    // - Merkle root is updated manually by admin
-    // - This works for demo with <1000 citizens
-    // =========================================================================
+    require!(
+        census_state.accepts_commitment_version(commitment_version),
+        CensusError::UnsupportedCommitmentVersion
+    );
+
+    require!(
+        census_state.max_citizens == 0 || census_state.total_registered < census_state.max_citizens,
+        CensusError::RegistrationCapReached
+    );
+    // With the freeze on, the window closes at the scope's first count and
+    // reopens when advance_scope resets the population
+    require!(
+        !census_state.freeze_registrations_during_scope || census_state.current_population == 0,
+        CensusError::RegistrationsFrozen
+    );
+
+    // Once a tree is linked the census appends the commitment itself, signed
+    // by the census PDA as the tree's authority, so the leaf can only be this
+    // citizen's. The tree is then the authority on leaf indices: this
+    // citizen's is the tree's leaf count minus one, and leaf_count must agree
+    // with it (tree::appended_leaf_index). Submissions keep proving against
+    // the published merkle_root until set_merkle_root publishes the tree's
+    // new one. Without a tree, leaf_count alone numbers leaves, so indices
+    // are gapless by construction.
+    let (current_leaf_index, new_root) = if census_state.merkle_tree != Pubkey::default() {
+        let merkle_tree = ctx.accounts.merkle_tree.as_ref()
+            .ok_or(CensusError::InvalidMerkleTree)?;
+        let compression_program = ctx.accounts.compression_program.as_ref()
+            .ok_or(CensusError::InvalidMerkleTree)?;
+        let noop = ctx.accounts.noop.as_ref()
+            .ok_or(CensusError::InvalidMerkleTree)?;
+        let authority = tree::read_tree(merkle_tree)?.authority;
+        require_keys_eq!(authority, census_state.key(), CensusError::TreeAuthorityMismatch);
+
+        invoke_signed(
+            &tree::append_ix(&merkle_tree.key(), &authority, &identity_commitment),
+            &[
+                merkle_tree.to_account_info(),
+                census_state.to_account_info(),
+                noop.to_account_info(),
+                compression_program.to_account_info(),
+            ],
+            &[&[CENSUS_STATE_SEED, &[census_state.bump]]],
+        )?;
+
+        let snapshot = tree::read_tree(merkle_tree)?;
+        let leaf_index = tree::appended_leaf_index(snapshot.leaf_count, census_state.leaf_count)?;
+        let new_root = tree::is_root_checkpoint(
+            snapshot.leaf_count,
+            census_state.root_checkpoint_interval,
+        )
+        .then_some(snapshot.root);
+        (leaf_index, new_root)
+    } else {
+        (census_state.leaf_count, None)
+    };
+
+    // Time-limited citizenship: the record carries this registration's expiry
+    match ctx.accounts.citizen_record.as_mut() {
+        Some(citizen_record) => {
+            citizen_record.identity_commitment = identity_commitment;
+            citizen_record.expires_at = if census_state.membership_duration > 0 {
+                clock.unix_timestamp
+                    .checked_add(census_state.membership_duration)
+                    .ok_or(CensusError::ArithmeticOverflow)?
+            } else {
+                0
+            };
+            citizen_record.bump = ctx.bumps.citizen_record.ok_or(CensusError::CitizenRecordMissing)?;
+            citizen_record.commitment_version = commitment_version;
+        }
+        None => require!(
+            census_state.membership_duration == 0,
+            CensusError::CitizenRecordMissing
+        ),
+    }
 
     // Update state counters
     census_state.leaf_count = census_state.leaf_count
@@ -32,7 +116,9 @@ pub fn handler(ctx: Context<RegisterCitizen>, identity_commitment: [u8; 32]) ->
     // Emit event for indexers
     emit!(CitizenRegistered {
         commitment: identity_commitment,
+        commitment_version,
         leaf_index: current_leaf_index,
+        new_root,
         timestamp: clock.unix_timestamp,
     });
 
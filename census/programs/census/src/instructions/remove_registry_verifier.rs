@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::{
+    contexts::RemoveRegistryVerifier,
+    error::CensusError,
+    state::{RegistryVerifierRemoved, VerifierRegistry},
+};
+
+/// Removes a verifier from the registry, shrinks the account by one key and
+/// sends the freed rent to the rent sink, as batch_close_nullifiers does
+pub fn handler(ctx: Context<RemoveRegistryVerifier>, verifier: Pubkey) -> Result<()> {
+    let verifier_registry = &mut ctx.accounts.verifier_registry;
+    let clock = Clock::get()?;
+
+    let index = verifier_registry
+        .verifiers
+        .iter()
+        .position(|v| v == &verifier)
+        .ok_or(CensusError::VerifierNotAuthorized)?;
+    verifier_registry.verifiers.swap_remove(index);
+
+    let info = verifier_registry.to_account_info();
+    let target_len = VerifierRegistry::space(verifier_registry.verifiers.len());
+    let excess = info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(target_len));
+    info.resize(target_len)?;
+    info.sub_lamports(excess)?;
+    ctx.accounts.rent_sink.add_lamports(excess)?;
+
+    emit!(RegistryVerifierRemoved {
+        verifier,
+        verifier_count: verifier_registry.verifiers.len() as u32,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Registry verifier removed: {}", verifier);
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError, state::VerifierRemoved};
+
+/// Removes a verifier from the allowlist
+pub fn handler(ctx: Context<UpdateConfig>, verifier: Pubkey) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    let index = census_state
+        .verifiers()
+        .iter()
+        .position(|v| v == &verifier)
+        .ok_or(CensusError::VerifierNotAuthorized)?;
+
+    // Swap-remove: move the last active entry into the freed slot
+    let last = census_state.verifier_count as usize - 1;
+    census_state.authorized_verifiers[index] = census_state.authorized_verifiers[last];
+    census_state.authorized_verifiers[last] = Pubkey::default();
+    census_state.verifier_accepted_until[index] = census_state.verifier_accepted_until[last];
+    census_state.verifier_accepted_until[last] = 0;
+    census_state.verifier_weights[index] = census_state.verifier_weights[last];
+    census_state.verifier_weights[last] = 0;
+    census_state.verifier_count -= 1;
+
+    emit!(VerifierRemoved {
+        verifier,
+        verifier_count: census_state.verifier_count,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Verifier removed: {}", verifier);
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::RenewMembership, error::CensusError, state::MembershipRenewed};
+
+/// Extends a registration by `membership_duration`, counted from its current
+/// expiry or from now if it already lapsed, so renewing early loses nothing
+pub fn handler(ctx: Context<RenewMembership>, identity_commitment: [u8; 32]) -> Result<()> {
+    let duration = ctx.accounts.census_state.membership_duration;
+    let citizen_record = &mut ctx.accounts.citizen_record;
+    let clock = Clock::get()?;
+
+    require!(duration > 0, CensusError::InvalidMembershipDuration);
+
+    citizen_record.expires_at = citizen_record.expires_at
+        .max(clock.unix_timestamp)
+        .checked_add(duration)
+        .ok_or(CensusError::ArithmeticOverflow)?;
+
+    emit!(MembershipRenewed {
+        identity_commitment,
+        expires_at: citizen_record.expires_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Membership renewed until {}", citizen_record.expires_at);
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::{
+    constants::CENSUS_STATE_SEED, contexts::ReplaceCommitment, error::CensusError,
+    state::CommitmentReplaced, tree,
+};
+
+/// Replaces a mis-registered identity commitment at `leaf_index` with `new`,
+/// through the compression program's replace_leaf (proof nodes below the
+/// canopy as remaining accounts, leaf level first). Counters are untouched:
+/// the census still has the same citizens; CommitmentReplaced tells
+/// indexers. Replacing an expired leaf with zeros sweeps it from the tree.
+/// A CitizenRecord is keyed by its commitment and is not moved, so the new
+/// commitment has none.
+///
+/// The census must be paused so no proof is built against the old root
+/// mid-change. The tree's root changes, so publish it with set_merkle_root
+/// before resuming.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ReplaceCommitment<'info>>,
+    leaf_index: u64,
+    old: [u8; 32],
+    new: [u8; 32],
+) -> Result<()> {
+    let census_state = &ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    require!(!census_state.is_active, CensusError::CensusNotPaused);
+    require!(leaf_index < census_state.leaf_count, CensusError::LeafIndexMismatch);
+    let index = u32::try_from(leaf_index).map_err(|_| error!(CensusError::LeafIndexMismatch))?;
+
+    let merkle_tree = &ctx.accounts.merkle_tree;
+    let snapshot = tree::read_tree(merkle_tree)?;
+    require_keys_eq!(snapshot.authority, census_state.key(), CensusError::TreeAuthorityMismatch);
+
+    let proof: Vec<Pubkey> = ctx.remaining_accounts.iter().map(|node| node.key()).collect();
+    let mut infos = vec![
+        merkle_tree.to_account_info(),
+        census_state.to_account_info(),
+        ctx.accounts.noop.to_account_info(),
+    ];
+    infos.extend(ctx.remaining_accounts.iter().cloned());
+    infos.push(ctx.accounts.compression_program.to_account_info());
+
+    invoke_signed(
+        &tree::replace_leaf_ix(
+            &merkle_tree.key(),
+            &census_state.key(),
+            &snapshot.root,
+            &old,
+            &new,
+            index,
+            &proof,
+        ),
+        &infos,
+        &[&[CENSUS_STATE_SEED, &[census_state.bump]]],
+    )?;
+
+    emit!(CommitmentReplaced {
+        leaf_index,
+        old_commitment: old,
+        new_commitment: new,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Commitment replaced at leaf {}", leaf_index);
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::ATTRIBUTE_COUNT, contexts::RollScope, error::CensusError, instructions::try_advance_scope,
+    state::ScopeAdvanceResult,
+};
+
+/// try_advance_scope plus creating the next scope's CensusAggregate, in one
+/// instruction so there is no window where the new scope has no aggregate:
+/// every count into it must then update the aggregate (see process_count).
+/// `next_scope` seeds the aggregate PDA, so it must be the scope the advance
+/// lands on (current + 1, or the calendar's scope with `calendar_scopes`).
+pub fn handler(ctx: Context<RollScope>, next_scope: u64) -> Result<ScopeAdvanceResult> {
+    let clock = Clock::get()?;
+
+    let result = try_advance_scope::try_advance(
+        &mut ctx.accounts.census_state,
+        &mut ctx.accounts.scope_snapshot,
+        ctx.bumps.scope_snapshot,
+        clock.unix_timestamp,
+    )?;
+    require!(result.new_scope == next_scope, CensusError::NextScopeMismatch);
+
+    let aggregate = &mut ctx.accounts.next_aggregate;
+    aggregate.scope = next_scope;
+    aggregate.participant_count = 0;
+    aggregate.attribute_counts = [0; ATTRIBUTE_COUNT];
+    aggregate.last_updated = clock.unix_timestamp;
+    aggregate.bump = ctx.bumps.next_aggregate;
+    ctx.accounts.census_state.aggregate_scope = next_scope;
+
+    Ok(result)
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::EXTERNAL_NULLIFIER_VERSION_LATEST, contexts::UpdateConfig, error::CensusError,
+    state::ExternalNullifierRotated,
+};
+
+/// Switches the external-nullifier derivation. The current scope becomes the
+/// transition scope and accepts both derivations; later scopes accept only
+/// the new one. See the upgrade procedure in scope.rs.
+pub fn handler(ctx: Context<UpdateConfig>, new_version: u8) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    require!(
+        new_version <= EXTERNAL_NULLIFIER_VERSION_LATEST
+            && new_version != census_state.external_nullifier_version,
+        CensusError::InvalidExternalNullifierVersion
+    );
+
+    let old_version = census_state.external_nullifier_version;
+    census_state.previous_external_nullifier_version = old_version;
+    census_state.external_nullifier_version = new_version;
+    census_state.external_nullifier_transition_scope = census_state.current_scope;
+
+    emit!(ExternalNullifierRotated {
+        old_version,
+        new_version,
+        transition_scope: census_state.current_scope,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "🔁 External nullifier v{} → v{} (both accepted in scope {})",
+        old_version,
+        new_version,
+        census_state.current_scope
+    );
+
+    Ok(())
+}
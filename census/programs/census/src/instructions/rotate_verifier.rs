@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::{constants::DEFAULT_VERIFIER_WEIGHT, contexts::UpdateConfig, error::CensusError, state::VerifierRotated};
+
+/// Replaces `old` with `new` in place, so the allowlist never drops below
+/// the threshold mid-rotation the way remove + add across two transactions can
+pub fn handler(ctx: Context<UpdateConfig>, old: Pubkey, new: Pubkey) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    require!(
+        !census_state.is_authorized_verifier(&new),
+        CensusError::VerifierAlreadyAuthorized
+    );
+    let index = census_state
+        .verifiers()
+        .iter()
+        .position(|v| v == &old)
+        .ok_or(CensusError::VerifierNotAuthorized)?;
+
+    census_state.authorized_verifiers[index] = new;
+    census_state.verifier_accepted_until[index] = 0;
+    census_state.verifier_weights[index] = DEFAULT_VERIFIER_WEIGHT;
+
+    emit!(VerifierRotated {
+        old,
+        new,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Verifier rotated: {} → {}", old, new);
+
+    Ok(())
+}
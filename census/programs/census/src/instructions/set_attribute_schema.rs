@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{ATTRIBUTE_COUNT, ATTRIBUTE_LABEL_LEN},
+    contexts::SetAttributeSchema,
+    error::CensusError,
+    state::AttributeSchema,
+};
+
+/// Writes the human-readable labels for the aggregate attribute slots,
+/// so dashboards can render demographics directly from chain.
+pub fn handler(
+    ctx: Context<SetAttributeSchema>,
+    labels: [[u8; ATTRIBUTE_LABEL_LEN]; ATTRIBUTE_COUNT],
+) -> Result<()> {
+    // Every label must be non-empty printable ASCII, zero-padded
+    for label in labels.iter() {
+        require!(
+            AttributeSchema::is_valid_label(label),
+            CensusError::InvalidAttributeLabel
+        );
+    }
+
+    let attribute_schema = &mut ctx.accounts.attribute_schema;
+    let clock = Clock::get()?;
+
+    attribute_schema.labels = labels;
+    attribute_schema.last_updated = clock.unix_timestamp;
+    attribute_schema.bump = ctx.bumps.attribute_schema;
+
+    msg!("✅ Attribute schema updated ({} labels)", ATTRIBUTE_COUNT);
+
+    Ok(())
+}
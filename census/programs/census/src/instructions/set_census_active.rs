@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Pauses or resumes the census (registrations and submissions)
+pub fn handler(ctx: Context<UpdateConfig>, is_active: bool) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.is_active = is_active;
+
+    if is_active {
+        msg!("▶️ Census resumed");
+    } else {
+        msg!("⏸️ Census paused");
+    }
+
+    Ok(())
+}
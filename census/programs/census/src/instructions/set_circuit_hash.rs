@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Sets the circuit commitment attestations must be signed for. Verifiers
+/// switch to signing the new hash at the same time, since attestations over
+/// the old one stop verifying.
+pub fn handler(ctx: Context<UpdateConfig>, circuit_hash: [u8; 32]) -> Result<()> {
+    ctx.accounts.census_state.circuit_hash = circuit_hash;
+
+    msg!("✅ Circuit hash updated");
+
+    Ok(())
+}
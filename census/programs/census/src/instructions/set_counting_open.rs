@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Opens or closes counting (submissions only; registrations stay open)
+pub fn handler(ctx: Context<UpdateConfig>, counting_open: bool) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.counting_open = counting_open;
+
+    if counting_open {
+        msg!("▶️ Counting opened");
+    } else {
+        msg!("⏸️ Counting closed");
+    }
+
+    Ok(())
+}
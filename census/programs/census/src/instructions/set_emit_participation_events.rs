@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Turns ParticipationRecorded events on/off. Leave off unless a rewards
+/// program needs them, since they name the payer of every count.
+pub fn handler(ctx: Context<UpdateConfig>, emit_participation_events: bool) -> Result<()> {
+    ctx.accounts.census_state.emit_participation_events = emit_participation_events;
+
+    msg!("✅ Participation events: {}", emit_participation_events);
+
+    Ok(())
+}
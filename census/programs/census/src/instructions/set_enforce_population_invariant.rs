@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Turns the population <= total_registered sanity check on (or off). When
+/// on, a count that would exceed the roster is rejected at submission.
+pub fn handler(ctx: Context<UpdateConfig>, enforce_population_invariant: bool) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.enforce_population_invariant = enforce_population_invariant;
+
+    msg!("✅ Population invariant enforcement: {}", enforce_population_invariant);
+
+    Ok(())
+}
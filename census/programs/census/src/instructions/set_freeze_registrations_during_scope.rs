@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Turns the registration freeze on (or off). When on, register_citizen only
+/// succeeds before the current scope's first count, i.e. right after an
+/// advance, keeping the root stable for the rest of the scope.
+pub fn handler(
+    ctx: Context<UpdateConfig>,
+    freeze_registrations_during_scope: bool,
+) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.freeze_registrations_during_scope = freeze_registrations_during_scope;
+
+    msg!("✅ Freeze registrations during scope: {}", freeze_registrations_during_scope);
+
+    Ok(())
+}
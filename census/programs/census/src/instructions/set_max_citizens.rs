@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Caps how many citizens may be registered (0 = unlimited). Lowering the
+/// cap below total_registered only blocks new registrations.
+pub fn handler(ctx: Context<UpdateConfig>, max_citizens: u64) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.max_citizens = max_citizens;
+
+    msg!("✅ Max citizens set to {} (0 = unlimited)", max_citizens);
+
+    Ok(())
+}
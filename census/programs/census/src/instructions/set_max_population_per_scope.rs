@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Caps how many submissions a single scope may count (0 = unlimited), so a
+/// runaway verifier or proof spam can't push a scope to implausible numbers.
+/// Typically set at or just above total_registered.
+pub fn handler(ctx: Context<UpdateConfig>, max_population_per_scope: u64) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.max_population_per_scope = max_population_per_scope;
+
+    msg!("✅ Max population per scope set to {} (0 = unlimited)", max_population_per_scope);
+
+    Ok(())
+}
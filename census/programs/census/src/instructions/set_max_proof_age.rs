@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::{
+    contexts::UpdateConfig,
+    error::CensusError,
+    groth16::{self, CENSUS_PUBLIC_INPUTS},
+};
+
+/// Sets how old a submit_census proof's participation_timestamp may be
+/// (0 = unbounded). A bound is only accepted once the verification key
+/// proves the timestamp as an extra public input; until then every
+/// submission would fail verification
+pub fn handler(ctx: Context<UpdateConfig>, max_proof_age: i64) -> Result<()> {
+    require!(max_proof_age >= 0, CensusError::InvalidMaxProofAge);
+    require!(
+        max_proof_age == 0
+            || groth16::get_verification_key().public_input_count() > CENSUS_PUBLIC_INPUTS,
+        CensusError::ProofAgeUnsupported
+    );
+    ctx.accounts.census_state.max_proof_age = max_proof_age;
+
+    msg!("✅ Max proof age set to {} seconds", max_proof_age);
+
+    Ok(())
+}
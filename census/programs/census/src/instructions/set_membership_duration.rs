@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError};
+
+/// Sets how long new registrations (and renewals) stay valid (0 = never
+/// lapse). Existing CitizenRecords keep their expiry until renewed.
+pub fn handler(ctx: Context<UpdateConfig>, membership_duration: i64) -> Result<()> {
+    require!(membership_duration >= 0, CensusError::InvalidMembershipDuration);
+    ctx.accounts.census_state.membership_duration = membership_duration;
+
+    msg!("✅ Membership duration set to {} seconds", membership_duration);
+
+    Ok(())
+}
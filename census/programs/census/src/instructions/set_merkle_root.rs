@@ -1,15 +1,24 @@
 use anchor_lang::prelude::*;
-use crate::contexts::*;
+use crate::{contexts::*, error::CensusError, tree};
 
 /// Sets the Merkle root for the census state
-/// This is called by the admin after building the Merkle tree off-chain
+/// This is called by the admin after building the Merkle tree off-chain.
+/// Passing the linked tree account checks `root` against the tree's own root.
 pub fn handler(ctx: Context<SetMerkleRoot>, root: [u8; 32]) -> Result<()> {
+    if let Some(merkle_tree) = ctx.accounts.merkle_tree.as_ref() {
+        require!(
+            tree::read_tree(merkle_tree)?.root == root,
+            CensusError::MerkleRootMismatch
+        );
+    }
+
     let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
     
     // Set the merkle root
-    census_state.merkle_root = root;
+    census_state.set_root(root, clock.unix_timestamp)?;
     
-    msg!("✅ Merkle root set successfully!");
+    msg!("✅ Merkle root set successfully! (version {})", census_state.root_version);
     
     // Anchor will automatically serialize when context exits
     Ok(())
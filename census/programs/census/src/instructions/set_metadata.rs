@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::{constants::METADATA_URI_LEN, contexts::SetMetadata, error::CensusError};
+
+/// Points the census at off-chain JSON describing it (the question, a
+/// description, terms of participation) so frontends can render its purpose.
+pub fn handler(ctx: Context<SetMetadata>, uri: [u8; METADATA_URI_LEN]) -> Result<()> {
+    // Non-empty ASCII; zero padding only after the URI ends
+    let len = uri.iter().position(|&b| b == 0).unwrap_or(METADATA_URI_LEN);
+    require!(
+        len > 0 && uri[..len].is_ascii() && uri[len..].iter().all(|&b| b == 0),
+        CensusError::InvalidMetadataUri
+    );
+
+    let census_metadata = &mut ctx.accounts.census_metadata;
+    let clock = Clock::get()?;
+
+    census_metadata.uri = uri;
+    census_metadata.last_updated = clock.unix_timestamp;
+    census_metadata.bump = ctx.bumps.census_metadata;
+
+    msg!("✅ Census metadata URI set ({} bytes)", len);
+
+    Ok(())
+}
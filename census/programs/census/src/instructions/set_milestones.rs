@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::{constants::MILESTONE_COUNT, contexts::UpdateConfig};
+
+/// Sets the population milestones announced via PopulationMilestone
+/// (0 = unused slot). Milestones the current scope already passed are marked
+/// fired, so changing the list mid-scope doesn't announce stale crossings.
+pub fn handler(ctx: Context<UpdateConfig>, milestones: [u64; MILESTONE_COUNT]) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.milestones = milestones;
+    census_state.milestones_fired = 0;
+    census_state.take_reached_milestones();
+
+    msg!("✅ Population milestones set: {:?}", milestones);
+
+    Ok(())
+}
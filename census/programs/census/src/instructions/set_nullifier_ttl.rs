@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError};
+
+/// Sets how long a nullifier stays used before the same identity can be
+/// counted again. Intended for continuous (non-scoped) census deployments.
+/// A TTL of 0 disables expiry.
+///
+/// Nullifiers are already scope-bound: the circuit derives nullifier_hash
+/// from the identity and the external nullifier, which changes every scope,
+/// so a scoped census counts each citizen once per scope with no TTL. The
+/// TTL only matters while the external nullifier stays the same, i.e. a
+/// continuous census that never advances its scope. Under scoping, a TTL
+/// shorter than the scope lets one identity count more than once in the
+/// same scope, inflating its population.
+pub fn handler(ctx: Context<UpdateConfig>, nullifier_ttl: i64) -> Result<()> {
+    require!(nullifier_ttl >= 0, CensusError::InvalidNullifierTtl);
+
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.nullifier_ttl = nullifier_ttl;
+
+    msg!("✅ Nullifier TTL set to {} seconds", nullifier_ttl);
+
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError};
+
+/// Sets the minimum time between counts from one payer (0 = off). While
+/// set, submissions must pass the payer's PayerCooldown PDA.
+pub fn handler(ctx: Context<UpdateConfig>, payer_cooldown_seconds: i64) -> Result<()> {
+    require!(payer_cooldown_seconds >= 0, CensusError::InvalidPayerCooldown);
+
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.payer_cooldown_seconds = payer_cooldown_seconds;
+
+    msg!("✅ Payer cooldown set to {}s (0 = off)", payer_cooldown_seconds);
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Turns per-scope root pinning on (or off). When on, every proof in a scope
+/// must use the root captured when the scope opened; enabling it mid-scope
+/// pins the current root for the rest of this scope.
+pub fn handler(ctx: Context<UpdateConfig>, pin_root_per_scope: bool) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    if pin_root_per_scope && !census_state.pin_root_per_scope {
+        census_state.scope_root = census_state.merkle_root;
+    }
+    census_state.pin_root_per_scope = pin_root_per_scope;
+
+    msg!("✅ Pin root per scope: {}", pin_root_per_scope);
+
+    Ok(())
+}
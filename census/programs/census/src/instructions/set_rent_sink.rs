@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Sets where rent from closed program accounts is sent. Close instructions
+/// refund only to this account, so callers can't redirect rent to themselves.
+pub fn handler(ctx: Context<UpdateConfig>, rent_sink: Pubkey) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.rent_sink = rent_sink;
+
+    msg!("✅ Rent sink set to {}", rent_sink);
+
+    Ok(())
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Makes a disclosed signal mandatory (or optional again). When required,
+/// counts carrying the declined signal fail with SignalRequired.
+pub fn handler(ctx: Context<UpdateConfig>, require_signal: bool) -> Result<()> {
+    ctx.accounts.census_state.require_signal = require_signal;
+
+    msg!("✅ Require signal: {}", require_signal);
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Turns the trusted attestation path off (or back on). When off, every
+/// count must come through submit_census with a Groth16 proof.
+pub fn handler(ctx: Context<UpdateConfig>, require_zk_proofs: bool) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.require_zk_proofs = require_zk_proofs;
+
+    if require_zk_proofs {
+        msg!("🔒 Attestation path disabled: Groth16 proofs required");
+    } else {
+        msg!("🔓 Attestation path enabled");
+    }
+
+    Ok(())
+}
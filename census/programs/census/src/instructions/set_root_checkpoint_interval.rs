@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Sets how many appends apart CitizenRegistered carries the tree root
+/// (0 = never); larger intervals keep high-volume registration events small
+pub fn handler(ctx: Context<UpdateConfig>, root_checkpoint_interval: u64) -> Result<()> {
+    ctx.accounts.census_state.root_checkpoint_interval = root_checkpoint_interval;
+
+    msg!("✅ Root checkpoint interval set to {} leaves", root_checkpoint_interval);
+
+    Ok(())
+}
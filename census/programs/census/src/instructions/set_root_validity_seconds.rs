@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError};
+
+/// Sets how long after publication a recent root still verifies
+/// submit_census proofs (0 = any root still in recent_roots). The live
+/// root is always accepted.
+pub fn handler(ctx: Context<UpdateConfig>, root_validity_seconds: i64) -> Result<()> {
+    require!(root_validity_seconds >= 0, CensusError::InvalidRootValidity);
+
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.root_validity_seconds = root_validity_seconds;
+
+    msg!("✅ Root validity set to {}s (0 = by count only)", root_validity_seconds);
+
+    Ok(())
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError};
+
+/// Sets the scope calendar's genesis and whether try_advance_scope follows
+/// it. E.g. an epoch at a Monday 00:00 UTC with a one-week duration makes
+/// scope N the Nth calendar week. Enabling needs a timed (non-manual) census.
+/// The epoch can't be in the future: the calendar must already have started.
+pub fn handler(ctx: Context<UpdateConfig>, scope_epoch: i64, calendar_scopes: bool) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    require!(scope_epoch <= clock.unix_timestamp, CensusError::InvalidScopeEpoch);
+    require!(
+        !calendar_scopes || census_state.scope_duration > 0,
+        CensusError::ManualScopeOnly
+    );
+
+    census_state.scope_epoch = scope_epoch;
+    census_state.calendar_scopes = calendar_scopes;
+
+    msg!("✅ Scope calendar: epoch {} (calendar scopes: {})", scope_epoch, calendar_scopes);
+
+    Ok(())
+}
@@ -0,0 +1,16 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError};
+
+/// Sets how long after an advance the previous scope keeps accepting
+/// in-flight submissions. Those count toward the previous scope's snapshot.
+/// 0 disables the grace window.
+pub fn handler(ctx: Context<UpdateConfig>, scope_grace_seconds: i64) -> Result<()> {
+    require!(scope_grace_seconds >= 0, CensusError::InvalidScopeGrace);
+
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.scope_grace_seconds = scope_grace_seconds;
+
+    msg!("✅ Scope grace period set to {} seconds", scope_grace_seconds);
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Turns scope sealing on (or off). When on, a scope accepts no counts once
+/// advance has closed it, so late submissions for it fail with ScopeSealed
+/// instead of counting during the grace window.
+pub fn handler(ctx: Context<UpdateConfig>, seal_closed_scopes: bool) -> Result<()> {
+    ctx.accounts.census_state.seal_closed_scopes = seal_closed_scopes;
+
+    msg!("✅ Seal closed scopes: {}", seal_closed_scopes);
+
+    Ok(())
+}
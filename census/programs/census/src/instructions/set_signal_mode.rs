@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError, signal::SIGNAL_MODE_OPAQUE};
+
+/// Chooses whether signals are decoded as attribute codes (0) or logged
+/// verbatim as opaque commitments (1). Applies to counts from now on.
+pub fn handler(ctx: Context<UpdateConfig>, signal_mode: u8) -> Result<()> {
+    require!(signal_mode <= SIGNAL_MODE_OPAQUE, CensusError::InvalidSignalMode);
+
+    let census_state = &mut ctx.accounts.census_state;
+    census_state.signal_mode = signal_mode;
+
+    msg!("✅ Signal mode set to {}", signal_mode);
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError};
+
+/// Turns on/off the on-chain inclusion proof for attestations (see
+/// submit_attestation::verify_membership). Needs a linked tree to prove against.
+pub fn handler(ctx: Context<UpdateConfig>, strict_membership: bool) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+    require!(
+        !strict_membership || census_state.merkle_tree != Pubkey::default(),
+        CensusError::InvalidMerkleTree
+    );
+    census_state.strict_membership = strict_membership;
+
+    msg!("✅ Strict membership: {}", strict_membership);
+
+    Ok(())
+}
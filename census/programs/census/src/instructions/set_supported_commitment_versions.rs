@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+use crate::contexts::UpdateConfig;
+
+/// Replaces the set of commitment formats register_citizen accepts (bit `v`
+/// = version `v`). Existing leaves are unaffected; an empty set blocks new
+/// registrations.
+pub fn handler(ctx: Context<UpdateConfig>, supported_commitment_versions: u32) -> Result<()> {
+    ctx.accounts.census_state.supported_commitment_versions = supported_commitment_versions;
+
+    msg!("✅ Supported commitment versions: {:#034b}", supported_commitment_versions);
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::{
+    constants::CENSUS_STATE_SEED, contexts::SetTreeAuthority, error::CensusError,
+    state::TreeAuthorityTransferred, tree,
+};
+
+/// Transfers the linked tree's authority from the census state PDA to
+/// `new_authority`, e.g. when migrating to a new program version. The census
+/// keeps `merkle_tree` as is and can still read it, but can no longer append
+/// to it. The census must be paused so no registration races the transfer.
+pub fn handler(ctx: Context<SetTreeAuthority>, new_authority: Pubkey) -> Result<()> {
+    let census_state = &ctx.accounts.census_state;
+    let clock = Clock::get()?;
+
+    require!(!census_state.is_active, CensusError::CensusNotPaused);
+
+    let merkle_tree = &ctx.accounts.merkle_tree;
+    let old_authority = tree::read_tree(merkle_tree)?.authority;
+    require_keys_eq!(old_authority, census_state.key(), CensusError::TreeAuthorityMismatch);
+
+    invoke_signed(
+        &tree::transfer_authority_ix(&merkle_tree.key(), &old_authority, &new_authority),
+        &[
+            merkle_tree.to_account_info(),
+            census_state.to_account_info(),
+            ctx.accounts.compression_program.to_account_info(),
+        ],
+        &[&[CENSUS_STATE_SEED, &[census_state.bump]]],
+    )?;
+
+    emit!(TreeAuthorityTransferred {
+        tree: merkle_tree.key(),
+        old_authority,
+        new_authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("✅ Tree authority transferred: {} → {}", old_authority, new_authority);
+
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError, pda};
+
+/// Links the verifier registry (created by init_verifier_registry) or, with
+/// the default pubkey, unlinks it so the inline allowlist authorizes again.
+pub fn handler(ctx: Context<UpdateConfig>, verifier_registry: Pubkey) -> Result<()> {
+    require!(
+        verifier_registry == Pubkey::default()
+            || verifier_registry == pda::verifier_registry_pda(&crate::ID).0,
+        CensusError::InvalidVerifierRegistry
+    );
+    ctx.accounts.census_state.verifier_registry = verifier_registry;
+
+    msg!("✅ Verifier registry set to {}", verifier_registry);
+
+    Ok(())
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError};
+
+/// Sets how many distinct authorized verifiers must sign each attestation
+pub fn handler(ctx: Context<UpdateConfig>, verifier_threshold: u8) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+
+    require!(
+        verifier_threshold >= 1 && verifier_threshold <= census_state.verifier_count,
+        CensusError::InvalidVerifierThreshold
+    );
+    census_state.verifier_threshold = verifier_threshold;
+
+    msg!(
+        "✅ Verifier threshold set to {} of {}",
+        verifier_threshold,
+        census_state.verifier_count
+    );
+
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError};
+
+/// Sets how much `verifier`'s signature counts toward `weight_threshold`,
+/// e.g. more for a hardware-backed verifier than a cloud one
+pub fn handler(ctx: Context<UpdateConfig>, verifier: Pubkey, weight: u16) -> Result<()> {
+    require!(weight > 0, CensusError::InvalidVerifierWeight);
+
+    let census_state = &mut ctx.accounts.census_state;
+    let index = census_state
+        .verifiers()
+        .iter()
+        .position(|v| v == &verifier)
+        .ok_or(CensusError::VerifierNotAuthorized)?;
+    census_state.verifier_weights[index] = weight;
+
+    msg!("✅ Verifier {} weight set to {}", verifier, weight);
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::UpdateConfig, error::CensusError};
+
+/// Sets the summed signer weight each attestation needs (0 = weights
+/// ignored); it must be reachable by the current allowlist
+pub fn handler(ctx: Context<UpdateConfig>, weight_threshold: u16) -> Result<()> {
+    let census_state = &mut ctx.accounts.census_state;
+
+    require!(
+        u32::from(weight_threshold) <= census_state.total_verifier_weight(),
+        CensusError::InvalidVerifierThreshold
+    );
+    census_state.weight_threshold = weight_threshold;
+
+    msg!(
+        "✅ Weight threshold set to {} of {}",
+        weight_threshold,
+        census_state.total_verifier_weight()
+    );
+
+    Ok(())
+}
@@ -1,5 +1,24 @@
 use anchor_lang::prelude::*;
-use crate::{contexts::SubmitAttestation, error::CensusError, state::CensusCounted};
+use anchor_lang::solana_program::program::invoke;
+use crate::{
+    attestation::{
+        AttestationFields, ATTESTATION_BOUND_MESSAGE_LEN, ATTESTATION_MEMBERSHIP_LEN,
+        ATTESTATION_MESSAGE_LEN,
+    },
+    constants::{
+        ATTESTATION_NONCE_LEN, ATTESTATION_VALIDITY_SECONDS, DEFAULT_VERIFIER_WEIGHT,
+        MAX_CLOCK_SKEW, NULLIFIER_EXTRA_LEN,
+    },
+    contexts::SubmitAttestation,
+    ed25519,
+    error::CensusError,
+    instructions::submit_census::{self, Count, CountAccounts, CountSource},
+    state::{
+        CensusAttested, CensusState, CitizenRecord, MembershipProof, Nullifier, VerifierRegistry,
+    },
+    time::within_window,
+    tree,
+};
 
 /// Submit census attestation (verified off-chain, signature checked on-chain)
 /// 
@@ -10,116 +29,364 @@ use crate::{contexts::SubmitAttestation, error::CensusError, state::CensusCounte
 /// 4. If valid, verifier signs an attestation
 /// 5. User submits attestation to this instruction
 /// 6. We just verify the Ed25519 signature (cheap on Solana!)
+///
+/// The transaction must include an Ed25519 precompile instruction right
+/// before this one, verifying `signature` by the verifier over
+/// `[ATTESTATION_MESSAGE_VERSION, timestamp, nonce, root, nullifier, external, signal, circuit]`,
+/// where circuit is `census_state.circuit_hash`,
+/// followed by the payer when `bound_payer` is set and by the leaf and its
+/// index when `membership` is passed
+/// (see `attestation::build_attestation_message`).
+/// When `verifier_threshold > 1` (or `weight_threshold` needs more than
+/// one signer's weight), the same instruction carries the co-signers'
+/// signatures (see `ed25519::build_ed25519_ix_data`).
 /// 
 /// Benefits:
 /// - Real ZK verification (snarkjs is battle-tested)
 /// - Cheap on-chain (only signature verification)
 /// - Can handle complex circuits
 /// - Easy to upgrade verifier logic
-pub fn handler(
-    ctx: Context<SubmitAttestation>,
+///
+/// Under `strict_membership`, `membership` must also prove the identity leaf
+/// is in the linked tree under `merkle_root` (see `verify_membership`), and
+/// while memberships expire the leaf's CitizenRecord must still be live.
+#[allow(clippy::too_many_arguments)]
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SubmitAttestation<'info>>,
     timestamp: i64,
+    nonce: [u8; ATTESTATION_NONCE_LEN],
     merkle_root: [u8; 32],
     nullifier_hash: [u8; 32],
     external_nullifier: [u8; 32],
     signal_hash: [u8; 32],
     signature: [u8; 64],
+    extra: Option<[u8; NULLIFIER_EXTRA_LEN]>,
+    bound_payer: Option<Pubkey>,
+    membership: Option<MembershipProof>,
 ) -> Result<()> {
+    if ctx.accounts.census_state.strict_membership {
+        verify_membership(
+            ctx.accounts.merkle_tree.as_deref(),
+            ctx.accounts.compression_program.as_deref(),
+            ctx.remaining_accounts,
+            &merkle_root,
+            membership.as_ref(),
+        )?;
+        check_membership_expiry(
+            &ctx.accounts.census_state,
+            ctx.accounts.citizen_record.as_deref(),
+            membership.as_ref(),
+            Clock::get()?.unix_timestamp,
+        )?;
+    }
+
+    let census_key = ctx.accounts.census_state.key();
     let census_state = &mut ctx.accounts.census_state;
     let nullifier = &mut ctx.accounts.nullifier;
     let clock = Clock::get()?;
 
     msg!("📝 Processing census attestation...");
 
+    let fields = AttestationFields {
+        timestamp,
+        nonce,
+        merkle_root,
+        nullifier_hash,
+        external_nullifier,
+        signal_hash,
+        circuit_hash: census_state.circuit_hash,
+        bound_payer,
+        membership,
+    };
+    let counted_scope = check_attestation(
+        census_state,
+        &census_key,
+        ctx.accounts.verifier_registry.as_deref(),
+        Some(nullifier),
+        &ctx.accounts.payer.key(),
+        &ctx.accounts.verifier,
+        &ctx.accounts.instructions_sysvar,
+        ctx.accounts.previous_scope_snapshot.is_some(),
+        &fields,
+        &signature,
+        clock.unix_timestamp,
+    )?;
+    let verifier_pubkey = ctx.accounts.verifier.key();
+
     // =========================================================================
-    // 1. Verify timestamp is recent (within 5 minutes)
+    // 5. Record nullifier and increment population
     // =========================================================================
-    let time_diff = clock.unix_timestamp - timestamp;
+    let new_population = submit_census::process_count(
+        census_state,
+        CountAccounts {
+            nullifier,
+            nullifier_bump: ctx.bumps.nullifier,
+            previous_scope_snapshot: ctx.accounts.previous_scope_snapshot.as_deref_mut(),
+            payer_cooldown: ctx.accounts.payer_cooldown.as_deref_mut(),
+            payer_cooldown_bump: ctx.bumps.payer_cooldown,
+            numeric_aggregate: None,
+            nullifier_bitmap: ctx.accounts.nullifier_bitmap.as_deref_mut().map(|b| &mut **b),
+            aggregate: ctx.accounts.aggregate.as_deref_mut().map(|a| &mut **a),
+        },
+        &Count {
+            nullifier_hash,
+            scope: counted_scope,
+            signal_hash,
+            extra: extra.unwrap_or_default(),
+            attestation_nonce: nonce,
+            bucket_index: None,
+            source: CountSource::Attestation,
+            payer: ctx.accounts.payer.key(),
+        },
+        clock.unix_timestamp,
+    )?;
+    emit!(CensusAttested {
+        nullifier_hash,
+        scope: counted_scope,
+        verifier: verifier_pubkey,
+        seq: census_state.submission_seq,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!(
+        "✅ Census attestation recorded! Population: {} (Scope: {})",
+        new_population,
+        counted_scope
+    );
+
+    Ok(())
+}
+
+/// Strict-mode membership check: CPIs the compression program's verify_leaf
+/// so the leaf's inclusion is proven on-chain rather than vouched for by the
+/// verifier. The attestation's `merkle_root` is the root proven against
+/// (check_attestation pins it to the census root).
+///
+/// Costs: the proof nodes below the canopy ride along as accounts (32 bytes
+/// each, TREE_DEPTH - CANOPY_DEPTH = 10 for the default tree), which eats
+/// into the transaction size the Ed25519 instruction already strains. The
+/// CPI itself hashes TREE_DEPTH levels, modest next to the signature check
+/// and far below submit_census's Groth16 verification.
+///
+/// The leaf is the identity commitment, so a strict submission publicly
+/// links that citizen's commitment to the nullifier, giving up the
+/// anonymity the attestation path otherwise keeps. Enable it only where
+/// membership evidence matters more than unlinkability.
+///
+/// `proof_nodes` are the instruction's remaining accounts. Shared with
+/// dry_run_attestation.
+pub(crate) fn verify_membership<'info>(
+    merkle_tree: Option<&AccountInfo<'info>>,
+    compression_program: Option<&AccountInfo<'info>>,
+    proof_nodes: &[AccountInfo<'info>],
+    merkle_root: &[u8; 32],
+    membership: Option<&MembershipProof>,
+) -> Result<()> {
+    let membership = membership.ok_or(CensusError::MembershipProofRequired)?;
+    let merkle_tree = merkle_tree.ok_or(CensusError::InvalidMerkleTree)?;
+    let compression_program = compression_program.ok_or(CensusError::InvalidMerkleTree)?;
+
+    let proof: Vec<Pubkey> = proof_nodes.iter().map(|node| node.key()).collect();
+    let mut infos = vec![merkle_tree.clone()];
+    infos.extend(proof_nodes.iter().cloned());
+    infos.push(compression_program.clone());
+
+    invoke(
+        &tree::verify_leaf_ix(
+            &merkle_tree.key(),
+            merkle_root,
+            &membership.leaf,
+            membership.leaf_index,
+            &proof,
+        ),
+        &infos,
+    )?;
+
+    msg!("🌳 Identity leaf {} proven in tree", membership.leaf_index);
+
+    Ok(())
+}
+
+/// Strict-mode expiry check: while `membership_duration > 0` the proven leaf
+/// must come with its CitizenRecord, not yet expired. Shared with
+/// dry_run_attestation.
+pub(crate) fn check_membership_expiry(
+    census_state: &CensusState,
+    citizen_record: Option<&CitizenRecord>,
+    membership: Option<&MembershipProof>,
+    now: i64,
+) -> Result<()> {
+    if census_state.membership_duration == 0 {
+        return Ok(());
+    }
+    let membership = membership.ok_or(CensusError::MembershipProofRequired)?;
+    let citizen_record = citizen_record.ok_or(CensusError::CitizenRecordMissing)?;
     require!(
-        time_diff >= 0 && time_diff < 300, // 5 minutes
+        citizen_record.identity_commitment == membership.leaf,
+        CensusError::InvalidCitizenRecord
+    );
+    require!(!citizen_record.is_expired(now), CensusError::MembershipExpired);
+    Ok(())
+}
+
+/// Every check submit_attestation makes before writing anything; returns
+/// the scope the attestation counts toward. `census_key` is the CensusState
+/// address and `nullifier` is None when the PDA doesn't exist yet. Shared
+/// with dry_run_attestation so the two can't drift.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn check_attestation(
+    census_state: &CensusState,
+    census_key: &Pubkey,
+    verifier_registry: Option<&VerifierRegistry>,
+    nullifier: Option<&Nullifier>,
+    payer: &Pubkey,
+    verifier: &AccountInfo,
+    instructions_sysvar: &AccountInfo,
+    has_previous_scope_snapshot: bool,
+    fields: &AttestationFields,
+    signature: &[u8; 64],
+    now: i64,
+) -> Result<u64> {
+    require!(census_state.is_active, CensusError::CensusNotActive);
+    require!(census_state.counting_open, CensusError::CountingClosed);
+    require!(!census_state.require_zk_proofs, CensusError::AttestationPathDisabled);
+    require!(census_state.is_ready(), CensusError::CensusNotReady);
+
+    // Reject a nullifier that was already used, unless its TTL has expired
+    if let Some(recorded) = nullifier.filter(|n| n.is_recorded()) {
+        require!(
+            recorded.is_expired(census_state.nullifier_ttl, now),
+            CensusError::NullifierAlreadyUsed
+        );
+        // Re-counting an expired nullifier needs a fresh attestation, not a replay
+        require!(
+            recorded.attestation_nonce != fields.nonce,
+            CensusError::AttestationReplayed
+        );
+    }
+
+    // =========================================================================
+    // 1. Verify timestamp is recent (within ATTESTATION_VALIDITY_SECONDS)
+    // =========================================================================
+    require!(
+        fields.timestamp <= now.saturating_add(MAX_CLOCK_SKEW),
+        CensusError::AttestationFromFuture
+    );
+    require!(
+        within_window(now, fields.timestamp, ATTESTATION_VALIDITY_SECONDS),
         CensusError::AttestationExpired
     );
-    msg!("✓ Timestamp valid ({}s ago)", time_diff);
+    msg!("✓ Timestamp valid ({}s ago)", now.saturating_sub(fields.timestamp));
 
     // =========================================================================
     // 2. Verify merkle root matches current state
     // =========================================================================
     require!(
-        merkle_root == census_state.merkle_root,
-        CensusError::InvalidMerkleRoot
+        &fields.merkle_root == census_state.accepted_root(),
+        CensusError::MerkleRootMismatch
     );
     msg!("✓ Merkle root matches on-chain state");
 
     // =========================================================================
-    // 3. Verify external nullifier matches current scope
+    // 3. Verify external nullifier matches current scope (or the previous
+    //    one during its grace window)
     // =========================================================================
-    let scope_bytes = census_state.current_scope.to_le_bytes();
-    let mut expected_external = [0u8; 32];
-    expected_external[..8].copy_from_slice(&scope_bytes);
+    let Some(counted_scope) =
+        census_state.submission_scope(census_key, &fields.external_nullifier, now)?
+    else {
+        // Tell a verifier running ahead apart from a plain wrong scope
+        if let Some(offset) = census_state.future_scope_offset(census_key, &fields.external_nullifier)? {
+            msg!("❌ Attestation is for scope {} + {}", census_state.current_scope, offset);
+            return err!(CensusError::AttestationForFutureScope);
+        }
+        return err!(CensusError::ExternalNullifierMismatch);
+    };
+    require!(!census_state.is_scope_sealed(counted_scope), CensusError::ScopeSealed);
     require!(
-        external_nullifier == expected_external,
-        CensusError::CensusScopeExpired
+        counted_scope == census_state.current_scope
+            || has_previous_scope_snapshot,
+        CensusError::SnapshotNotFound
     );
-    msg!("✓ Census scope matches (scope: {})", census_state.current_scope);
+    msg!("✓ Census scope matches (scope: {})", counted_scope);
+
+    // A payer-bound attestation can only be submitted by that payer
+    if let Some(bound_payer) = fields.bound_payer {
+        require_keys_eq!(
+            *payer,
+            bound_payer,
+            CensusError::AttestationPayerMismatch
+        );
+    }
 
     // =========================================================================
     // 4. Verify Ed25519 signature from trusted verifier
     // =========================================================================
     
-    // Reconstruct the message that was signed
-    let mut message = Vec::with_capacity(8 + 32 + 32 + 32 + 32);
-    message.extend_from_slice(&timestamp.to_le_bytes());
-    message.extend_from_slice(&merkle_root);
-    message.extend_from_slice(&nullifier_hash);
-    message.extend_from_slice(&external_nullifier);
-    message.extend_from_slice(&signal_hash);
+    // Reconstruct the message that was signed (version byte first). A length
+    // other than the wire constant means the encoding drifted from what
+    // verifiers sign.
+    let message = fields.message();
+    let wire_len = match fields.bound_payer {
+        Some(_) => ATTESTATION_BOUND_MESSAGE_LEN,
+        None => ATTESTATION_MESSAGE_LEN,
+    } + if fields.membership.is_some() { ATTESTATION_MEMBERSHIP_LEN } else { 0 };
+    debug_assert_eq!(message.len(), wire_len);
+    require!(message.len() == wire_len, CensusError::InvalidAttestationFormat);
 
     // Get verifier pubkey from signer account
-    let verifier_pubkey = ctx.accounts.verifier.key();
-    
-    // Verify signature using Solana's Ed25519 program (or native check)
-    // The verifier account must have signed this transaction OR
-    // we use the Ed25519 precompile for signature verification
-    
-    // For now, we verify by checking that verifier signed the transaction
-    // In production, you could use the Ed25519 precompile for pure signature verification
+    let verifier_pubkey = verifier.key();
+
+    // The verifier must have signed this transaction...
     require!(
-        ctx.accounts.verifier.is_signer,
+        verifier.is_signer,
         CensusError::InvalidVerifierSignature
     );
-    
-    // Additional check: verify this is an authorized verifier
-    // (In production, store trusted verifier pubkeys in census_state)
-    msg!("✓ Verifier signature valid: {}", verifier_pubkey);
-
-    // =========================================================================
-    // 5. Record nullifier to prevent double-voting
-    // =========================================================================
-    nullifier.nullifier_hash = nullifier_hash;
-    nullifier.scope = census_state.current_scope;
-    nullifier.timestamp = clock.unix_timestamp;
-    nullifier.bump = ctx.bumps.nullifier;
-
-    // =========================================================================
-    // 6. Increment population counter
-    // =========================================================================
-    census_state.current_population = census_state.current_population
-        .checked_add(1)
-        .ok_or(CensusError::ArithmeticOverflow)?;
 
-    // Emit event
-    emit!(CensusCounted {
-        nullifier_hash,
-        scope: census_state.current_scope,
-        new_population: census_state.current_population,
-        timestamp: clock.unix_timestamp,
-    });
+    // Verify this is an authorized verifier: by the linked registry if any,
+    // else the inline allowlist
+    let registry = if census_state.verifier_registry == Pubkey::default() {
+        None
+    } else {
+        Some(verifier_registry.ok_or(CensusError::InvalidVerifierRegistry)?)
+    };
+    let accepts = |pubkey: &Pubkey| match registry {
+        Some(registry) => registry.contains(pubkey),
+        None => census_state.accepts_verifier(pubkey, now),
+    };
+    require!(accepts(&verifier_pubkey), CensusError::VerifierNotAuthorized);
 
-    msg!(
-        "✅ Census attestation recorded! Population: {} (Scope: {})",
-        census_state.current_population,
-        census_state.current_scope
+    // ...and signed the versioned message, checked via the Ed25519 precompile.
+    // Co-signers for M-of-N share the same (batched) precompile instruction.
+    let signers = ed25519::load_ed25519_signers(instructions_sysvar, &message)?;
+    require!(
+        signers
+            .iter()
+            .any(|s| s.pubkey == verifier_pubkey && &s.signature == signature),
+        CensusError::InvalidVerifierSignature
+    );
+    let mut approvals: Vec<Pubkey> = signers
+        .iter()
+        .map(|s| s.pubkey)
+        .filter(accepts)
+        .collect();
+    approvals.sort();
+    approvals.dedup();
+    require!(
+        approvals.len() >= census_state.verifier_threshold as usize,
+        CensusError::InsufficientVerifierSignatures
     );
+    let approval_weight: u32 = approvals
+        .iter()
+        .map(|pubkey| match registry {
+            Some(_) => u32::from(DEFAULT_VERIFIER_WEIGHT),
+            None => u32::from(census_state.verifier_weight(pubkey)),
+        })
+        .sum();
+    require!(
+        approval_weight >= u32::from(census_state.weight_threshold),
+        CensusError::InsufficientVerifierWeight
+    );
+    msg!("✓ Verifier signature valid: {}", verifier_pubkey);
 
-    Ok(())
+    Ok(counted_scope)
 }
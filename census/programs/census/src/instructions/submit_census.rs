@@ -1,13 +1,29 @@
 use anchor_lang::prelude::*;
-use crate::{contexts::SubmitCensus, error::CensusError, state::CensusCounted, groth16};
+use crate::{
+    constants::{ATTESTATION_NONCE_LEN, NULLIFIER_EXTRA_LEN, NUMERIC_BUCKET_COUNT}, contexts::SubmitCensus, error::CensusError,
+    field::FieldElement,
+    state::{
+        CensusAggregate, CensusCounted, CensusState, Nullifier, NullifierBitmap, NumericAggregate,
+        ParticipationRecorded, PayerCooldown, PopulationMilestone, ProofVerified, ScopeSnapshot,
+        SignalCommitted, SignalObserved,
+    },
+    groth16, scope, signal,
+    time::within_window,
+};
 
+#[allow(clippy::too_many_arguments)]
 pub fn handler(
     ctx: Context<SubmitCensus>,
     proof_a: [u8; 64],
     proof_b: [u8; 128],
     proof_c: [u8; 64],
     public_inputs: [[u8; 32]; 4], // [root, nullifierHash, signalHash, externalNullifier]
+    expected_root_version: Option<u64>,
+    extra: Option<[u8; NULLIFIER_EXTRA_LEN]>,
+    bucket_index: Option<u8>,
+    participation_timestamp: Option<i64>,
 ) -> Result<()> {
+    let census_key = ctx.accounts.census_state.key();
     let census_state = &mut ctx.accounts.census_state;
     let nullifier = &mut ctx.accounts.nullifier;
     let clock = Clock::get()?;
@@ -15,22 +31,49 @@ pub fn handler(
     // Extract public inputs
     let proof_root = &public_inputs[0];
     let nullifier_hash = public_inputs[1];
-    let _signal_hash = &public_inputs[2];
+    let signal_hash = &public_inputs[2];
     let external_nullifier = &public_inputs[3];
 
-    // Verify the Merkle root matches current state
+    // Reject a nullifier that was already used, unless its TTL has expired.
+    // Checked first so the loser of a race for the same nullifier fails
+    // before paying for proof verification.
     require!(
-        proof_root == &census_state.merkle_root,
-        CensusError::InvalidMerkleRoot
+        !nullifier.is_recorded()
+            || nullifier.is_expired(census_state.nullifier_ttl, clock.unix_timestamp),
+        CensusError::NullifierAlreadyUsed
     );
 
-    // Verify external nullifier matches current scope
-    let scope_bytes = census_state.current_scope.to_le_bytes();
-    let mut expected_external = [0u8; 32];
-    expected_external[..8].copy_from_slice(&scope_bytes);
+    require!(census_state.is_ready(), CensusError::CensusNotReady);
+
+    // Verify the Merkle root is the live one or still in recent_roots (and
+    // within root_validity_seconds). If the client says which root version
+    // it proved against and the root has since rotated, report a retryable
+    // error instead of a bad root.
+    if !census_state.accepts_proof_root(proof_root, clock.unix_timestamp) {
+        if let Some(version) = expected_root_version {
+            require!(
+                version >= census_state.root_version,
+                CensusError::RootAdvancedDuringSubmission
+            );
+        }
+        return err!(CensusError::InvalidMerkleRoot);
+    }
+
+    // Verify external nullifier matches the current scope (or the previous
+    // one during its grace window)
+    let counted_scope = census_state
+        .submission_scope(&census_key, external_nullifier, clock.unix_timestamp)?
+        .ok_or(CensusError::CensusScopeExpired)?;
+    require!(!census_state.is_scope_sealed(counted_scope), CensusError::ScopeSealed);
+    let counts_previous_scope = counted_scope != census_state.current_scope;
     require!(
-        external_nullifier == &expected_external,
-        CensusError::CensusScopeExpired
+        !counts_previous_scope || ctx.accounts.previous_scope_snapshot.is_some(),
+        CensusError::SnapshotNotFound
+    );
+    // Histograms belong to the current scope only
+    require!(
+        !counts_previous_scope || bucket_index.is_none(),
+        CensusError::InvalidBucketIndex
     );
 
     // =========================================================================
@@ -39,48 +82,397 @@ pub fn handler(
     
     // Get verification key for the census circuit
     let vkey = groth16::get_verification_key();
-    
+
+    // A bucketed submission proves its bucket as a 5th public input, so the
+    // deployed circuit must expose it (the verification key then has 6 IC points)
+    let mut verified_inputs = public_inputs
+        .iter()
+        .map(|input| FieldElement::from_be(*input).ok_or(CensusError::InvalidFieldElement))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    if let Some(bucket) = bucket_index {
+        check_bucket_index(&vkey, bucket)?;
+        require!(
+            ctx.accounts.numeric_aggregate.is_some(),
+            CensusError::NumericAggregateMissing
+        );
+        verified_inputs.push(FieldElement::from_u64(bucket.into()));
+    }
+
+    // Likewise a proof-of-liveness timestamp is proven as the next public
+    // input. Under max_proof_age it is mandatory, so a proof generated long
+    // ago can't be held back and submitted at the end of a scope
+    if census_state.max_proof_age > 0 {
+        let proven_at = participation_timestamp.ok_or(CensusError::ProofTooOld)?;
+        require!(
+            within_window(clock.unix_timestamp, proven_at, census_state.max_proof_age),
+            CensusError::ProofTooOld
+        );
+    }
+    if let Some(proven_at) = participation_timestamp {
+        let proven_at = u64::try_from(proven_at).map_err(|_| CensusError::ProofTooOld)?;
+        verified_inputs.push(FieldElement::from_u64(proven_at));
+    }
+
     // Verify the proof
     let proof_valid = groth16::verify_groth16_proof(
         &proof_a,
         &proof_b,
         &proof_c,
-        &public_inputs,
+        &verified_inputs,
         &vkey,
     )?;
     
-    require!(proof_valid, CensusError::InvalidProof);
+    if !proof_valid {
+        msg!("✗ Groth16 pairing check failed");
+        return err!(CensusError::InvalidProof);
+    }
 
     msg!("✓ Groth16 proof verified successfully using alt_bn128");
+    emit!(ProofVerified {
+        success: true,
+        scope: counted_scope,
+        timestamp: clock.unix_timestamp,
+    });
 
     // =========================================================================
     // Record the nullifier and increment population
     // =========================================================================
 
+    let new_population = process_count(
+        census_state,
+        CountAccounts {
+            nullifier,
+            nullifier_bump: ctx.bumps.nullifier,
+            previous_scope_snapshot: ctx.accounts.previous_scope_snapshot.as_deref_mut(),
+            payer_cooldown: ctx.accounts.payer_cooldown.as_deref_mut(),
+            payer_cooldown_bump: ctx.bumps.payer_cooldown,
+            numeric_aggregate: ctx.accounts.numeric_aggregate.as_deref_mut(),
+            nullifier_bitmap: ctx.accounts.nullifier_bitmap.as_deref_mut().map(|b| &mut **b),
+            aggregate: ctx.accounts.aggregate.as_deref_mut().map(|a| &mut **a),
+        },
+        &Count {
+            nullifier_hash,
+            scope: counted_scope,
+            signal_hash: *signal_hash,
+            extra: extra.unwrap_or_default(),
+            attestation_nonce: [0; ATTESTATION_NONCE_LEN],
+            bucket_index,
+            source: CountSource::Proof,
+            payer: ctx.accounts.payer.key(),
+        },
+        clock.unix_timestamp,
+    )?;
+
+    msg!(
+        "✅ Census proof recorded! Population now: {} (Scope: {})",
+        new_population,
+        counted_scope
+    );
+
+    Ok(())
+}
+
+/// How a count was validated; selects the counter it increments
+pub enum CountSource {
+    Proof,
+    Attestation,
+}
+
+/// A validated submission, ready to be counted
+pub struct Count {
+    pub nullifier_hash: [u8; 32],
+    pub scope: u64,
+    pub signal_hash: [u8; 32],
+    pub extra: [u8; NULLIFIER_EXTRA_LEN],
+    pub attestation_nonce: [u8; ATTESTATION_NONCE_LEN],
+    /// Numeric bucket to tally; `numeric_aggregate` must then be present
+    pub bucket_index: Option<u8>,
+    pub source: CountSource,
+    /// Fee payer / relayer, for ParticipationRecorded
+    pub payer: Pubkey,
+}
+
+/// Accounts a count writes to
+pub struct CountAccounts<'a> {
+    pub nullifier: &'a mut Nullifier,
+    pub nullifier_bump: u8,
+    pub previous_scope_snapshot: Option<&'a mut ScopeSnapshot>,
+    pub payer_cooldown: Option<&'a mut PayerCooldown>,
+    pub payer_cooldown_bump: Option<u8>,
+    pub numeric_aggregate: Option<&'a mut NumericAggregate>,
+    pub nullifier_bitmap: Option<&'a mut NullifierBitmap>,
+    pub aggregate: Option<&'a mut CensusAggregate>,
+}
+
+/// What `check_count` reads from the accounts a count would write to
+pub(crate) struct CountChecks<'a> {
+    pub previous_scope_snapshot: Option<&'a ScopeSnapshot>,
+    /// The payer's last counted submission (0 for a fresh record), or None
+    /// when no cooldown record was supplied
+    pub payer_last_count_at: Option<i64>,
+    pub has_numeric_aggregate: bool,
+    pub nullifier_bitmap: Option<&'a NullifierBitmap>,
+    pub aggregate: Option<&'a CensusAggregate>,
+}
+
+/// A bucket index must name a bucket, and `vkey` must prove it as the input
+/// after CENSUS_PUBLIC_INPUTS; against a key without that input every
+/// bucketed proof would fail verification, so it is refused up front
+pub fn check_bucket_index(vkey: &groth16::VerificationKey, bucket: u8) -> Result<()> {
+    require!((bucket as usize) < NUMERIC_BUCKET_COUNT, CensusError::InvalidBucketIndex);
+    require!(
+        vkey.public_input_count() > groth16::CENSUS_PUBLIC_INPUTS,
+        CensusError::BucketIndexUnsupported
+    );
+    Ok(())
+}
+
+/// Index of the first nullifier_hash that repeats an earlier one. A
+/// submission batch must reject it up front (DuplicateNullifierInBatch),
+/// since the second init of the same Nullifier PDA would otherwise fail
+/// the whole transaction opaquely.
+pub fn find_duplicate_nullifier(nullifier_hashes: &[[u8; 32]]) -> Option<usize> {
+    (1..nullifier_hashes.len()).find(|&i| nullifier_hashes[..i].contains(&nullifier_hashes[i]))
+}
+
+/// Every check process_count makes before writing: `require_signal`, the
+/// payer cooldown, the scope's population bounds, the numeric aggregate, the
+/// nullifier bitmap and the aggregate. dry_run_attestation calls it directly
+/// so a pre-flight that passes can't be rejected by the count itself.
+pub(crate) fn check_count(
+    census_state: &CensusState,
+    checks: &CountChecks,
+    count: &Count,
+    now: i64,
+) -> Result<()> {
+    require!(
+        !census_state.require_signal || !signal::is_declined(&count.signal_hash),
+        CensusError::SignalRequired
+    );
+    check_payer_cooldown(census_state, checks.payer_last_count_at, now)?;
+    check_population(census_state, checks.previous_scope_snapshot, count.scope)?;
+    require!(
+        count.bucket_index.is_none() || checks.has_numeric_aggregate,
+        CensusError::NumericAggregateMissing
+    );
+    match checks.nullifier_bitmap {
+        Some(bitmap) => require!(bitmap.scope == count.scope, CensusError::InvalidNullifierBitmap),
+        None => require!(
+            count.scope != census_state.nullifier_bitmap_scope,
+            CensusError::InvalidNullifierBitmap
+        ),
+    }
+    match checks.aggregate {
+        Some(aggregate) => require!(aggregate.scope == count.scope, CensusError::InvalidAggregate),
+        None => require!(
+            count.scope != census_state.aggregate_scope,
+            CensusError::InvalidAggregate
+        ),
+    }
+    Ok(())
+}
+
+/// Record a validated submission: run `check_count`, then stamp the payer
+/// cooldown, write the nullifier, tally it in the scope's aggregate, count it
+/// toward its scope, bump the counters, the numeric aggregate and the
+/// nullifier bitmap, and emit CensusCounted plus the signal events (and
+/// ParticipationRecorded when `emit_participation_events` is on). Returns the scope's new population.
+/// submit_census and submit_attestation both count through here so the two
+/// paths can't diverge.
+pub fn process_count(
+    census_state: &mut CensusState,
+    accounts: CountAccounts,
+    count: &Count,
+    now: i64,
+) -> Result<u64> {
+    check_count(
+        census_state,
+        &CountChecks {
+            previous_scope_snapshot: accounts.previous_scope_snapshot.as_deref(),
+            payer_last_count_at: accounts.payer_cooldown.as_deref().map(|p| p.last_count_at),
+            has_numeric_aggregate: accounts.numeric_aggregate.is_some(),
+            nullifier_bitmap: accounts.nullifier_bitmap.as_deref(),
+            aggregate: accounts.aggregate.as_deref(),
+        },
+        count,
+        now,
+    )?;
+    if let Some(payer_cooldown) = accounts.payer_cooldown {
+        payer_cooldown.last_count_at = now;
+        if let Some(bump) = accounts.payer_cooldown_bump {
+            payer_cooldown.bump = bump;
+        }
+    }
+
     // Mark nullifier as used
-    nullifier.nullifier_hash = nullifier_hash;
-    nullifier.scope = census_state.current_scope;
-    nullifier.timestamp = clock.unix_timestamp;
-    nullifier.bump = ctx.bumps.nullifier;
+    let nullifier = accounts.nullifier;
+    nullifier.nullifier_hash = count.nullifier_hash;
+    nullifier.scope = count.scope;
+    nullifier.timestamp = now;
+    nullifier.bump = accounts.nullifier_bump;
+    nullifier.extra = count.extra;
+    nullifier.attestation_nonce = count.attestation_nonce;
+
+    // Tally the participant, and its attribute unless signals are opaque
+    if let Some(aggregate) = accounts.aggregate {
+        let attribute = (census_state.signal_mode != signal::SIGNAL_MODE_OPAQUE)
+            .then(|| signal::decode_signal(&count.signal_hash))
+            .flatten();
+        aggregate.record(attribute, now)?;
+    }
 
     // Increment population counter
-    census_state.current_population = census_state.current_population
+    let new_population = increment_population(
+        census_state,
+        accounts.previous_scope_snapshot,
+        count.scope,
+        &count.nullifier_hash,
+        now,
+    )?;
+    let counter = match count.source {
+        CountSource::Proof => &mut census_state.counted_by_proof,
+        CountSource::Attestation => &mut census_state.counted_by_attestation,
+    };
+    *counter = counter.checked_add(1).ok_or(CensusError::ArithmeticOverflow)?;
+    census_state.submission_seq = census_state.submission_seq
         .checked_add(1)
         .ok_or(CensusError::ArithmeticOverflow)?;
 
+    if let Some(bucket) = count.bucket_index {
+        let numeric_aggregate = accounts.numeric_aggregate
+            .ok_or(CensusError::NumericAggregateMissing)?;
+        let bucket_count = &mut numeric_aggregate.bucket_counts[bucket as usize];
+        *bucket_count = bucket_count.checked_add(1).ok_or(CensusError::ArithmeticOverflow)?;
+        numeric_aggregate.last_updated = now;
+    }
+
+    if let Some(bitmap) = accounts.nullifier_bitmap {
+        bitmap.insert(&count.nullifier_hash)?;
+    }
+
     // Emit event for real-time dashboards
     emit!(CensusCounted {
-        nullifier_hash,
-        scope: census_state.current_scope,
-        new_population: census_state.current_population,
-        timestamp: clock.unix_timestamp,
+        nullifier_hash: count.nullifier_hash,
+        scope: count.scope,
+        new_population,
+        seq: census_state.submission_seq,
+        timestamp: now,
     });
+    emit_signal(census_state, count.scope, &count.signal_hash, now);
+    if census_state.emit_participation_events {
+        emit!(ParticipationRecorded {
+            payer: count.payer,
+            scope: count.scope,
+            timestamp: now,
+        });
+    }
 
-    msg!(
-        "✅ Census proof recorded! Population now: {} (Scope: {})",
-        census_state.current_population,
-        census_state.current_scope
+    Ok(new_population)
+}
+
+/// Reject a count once `counted_scope` reached `max_population_per_scope`,
+/// or its roster size when `enforce_population_invariant` is on.
+/// Grace-window submissions for the previous scope are checked against its
+/// snapshot. A count past the tree's capacity means counting is broken, so
+/// it fails as CensusPopulationImpossible before any other check (see
+/// `CensusState::is_population_possible`).
+fn check_population(
+    census_state: &CensusState,
+    previous_scope_snapshot: Option<&ScopeSnapshot>,
+    counted_scope: u64,
+) -> Result<()> {
+    let (population, registered) = if counted_scope == census_state.current_scope {
+        (census_state.current_population, census_state.total_registered)
+    } else {
+        let snapshot = previous_scope_snapshot.ok_or(CensusError::SnapshotNotFound)?;
+        (snapshot.final_population, snapshot.total_registered_at_close)
+    };
+    let cap = census_state.max_population_per_scope;
+    require!(
+        census_state.is_population_possible(population),
+        CensusError::CensusPopulationImpossible
     );
+    require!(cap == 0 || population < cap, CensusError::ScopePopulationCapReached);
+    require!(
+        !census_state.enforce_population_invariant || population < registered,
+        CensusError::PopulationExceedsRegistered
+    );
+    Ok(())
+}
+
+/// Count one submission toward `counted_scope` and return its new population.
+/// Grace-window submissions for the previous scope land in its snapshot.
+/// Emits PopulationMilestone for current-scope milestones reached by this
+/// count, and folds `nullifier_hash` into that scope's nullifier accumulator.
+/// `check_population` has already vetted the count.
+fn increment_population(
+    census_state: &mut CensusState,
+    previous_scope_snapshot: Option<&mut ScopeSnapshot>,
+    counted_scope: u64,
+    nullifier_hash: &[u8; 32],
+    now: i64,
+) -> Result<u64> {
+    if counted_scope == census_state.current_scope {
+        census_state.current_population = census_state.current_population
+            .checked_add(1)
+            .ok_or(CensusError::ArithmeticOverflow)?;
+        census_state.running_nullifier_accumulator = scope::accumulate_nullifier(
+            &census_state.running_nullifier_accumulator,
+            nullifier_hash,
+        )?;
+        for milestone in census_state.take_reached_milestones() {
+            emit!(PopulationMilestone {
+                scope: counted_scope,
+                milestone,
+                timestamp: now,
+            });
+        }
+        return Ok(census_state.current_population);
+    }
 
+    let snapshot = previous_scope_snapshot.ok_or(CensusError::SnapshotNotFound)?;
+    snapshot.final_population = snapshot.final_population
+        .checked_add(1)
+        .ok_or(CensusError::ArithmeticOverflow)?;
+    snapshot.nullifier_accumulator =
+        scope::accumulate_nullifier(&snapshot.nullifier_accumulator, nullifier_hash)?;
+    Ok(snapshot.final_population)
+}
+
+/// Reject a payer that counted within `payer_cooldown_seconds`. Throttles
+/// per relayer; the payer says nothing about the identity being counted.
+fn check_payer_cooldown(
+    census_state: &CensusState,
+    last_count_at: Option<i64>,
+    now: i64,
+) -> Result<()> {
+    let cooldown = census_state.payer_cooldown_seconds;
+    let Some(last_count_at) = last_count_at else {
+        require!(cooldown == 0, CensusError::PayerCooldownMissing);
+        return Ok(());
+    };
+
+    require!(
+        cooldown == 0 || last_count_at == 0 || now >= last_count_at.saturating_add(cooldown),
+        CensusError::PayerCooldownActive
+    );
     Ok(())
 }
+
+/// Emit the events for a counted submission's signal under the census's
+/// signal_mode.
+fn emit_signal(census_state: &CensusState, scope: u64, signal_hash: &[u8; 32], now: i64) {
+    if census_state.signal_mode == signal::SIGNAL_MODE_OPAQUE {
+        emit!(SignalCommitted {
+            scope,
+            signal_hash: *signal_hash,
+            timestamp: now,
+        });
+    } else if let Some(attribute_index) = signal::decode_signal(signal_hash) {
+        emit!(SignalObserved {
+            scope,
+            attribute_index,
+            timestamp: now,
+        });
+    }
+}
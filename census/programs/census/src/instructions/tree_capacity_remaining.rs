@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::ReadCensusState, tree};
+
+/// Returns how many more citizens fit in the tree via return data, from the
+/// census's leaf_count
+pub fn handler(ctx: Context<ReadCensusState>) -> Result<u64> {
+    Ok(tree::remaining_capacity(ctx.accounts.census_state.leaf_count))
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::{
+    contexts::TryAdvanceScope, error::CensusError, instructions::advance_scope,
+    state::{CensusState, ScopeAdvanceResult, ScopeSnapshot},
+};
+
+/// Advance the scope on behalf of anyone (e.g. a keeper bot), but only once
+/// the current scope has expired. Manual-only censuses always reject.
+/// With `calendar_scopes` on, advances to the calendar's current scope.
+pub fn handler(ctx: Context<TryAdvanceScope>) -> Result<ScopeAdvanceResult> {
+    let clock = Clock::get()?;
+    try_advance(
+        &mut ctx.accounts.census_state,
+        &mut ctx.accounts.scope_snapshot,
+        ctx.bumps.scope_snapshot,
+        clock.unix_timestamp,
+    )
+}
+
+/// The permissionless advance: checks expiry, then advances. Shared by
+/// try_advance_scope and roll_scope
+pub(crate) fn try_advance(
+    census_state: &mut CensusState,
+    scope_snapshot: &mut ScopeSnapshot,
+    snapshot_bump: u8,
+    now: i64,
+) -> Result<ScopeAdvanceResult> {
+    require!(census_state.scope_duration != 0, CensusError::ManualScopeOnly);
+    require!(
+        census_state.is_scope_expired(now),
+        CensusError::ScopeNotExpired
+    );
+
+    let calendar = census_state.calendar_scopes;
+    advance_scope::advance(census_state, scope_snapshot, snapshot_bump, now, calendar)
+}
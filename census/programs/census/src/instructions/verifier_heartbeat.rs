@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::{contexts::Heartbeat, state::VerifierHeartbeat};
+
+/// Stamps the verifier's last_seen so operators can spot a silent verifier
+/// and rotate it out before attestations start failing.
+pub fn handler(ctx: Context<Heartbeat>) -> Result<()> {
+    let verifier_stats = &mut ctx.accounts.verifier_stats;
+    let clock = Clock::get()?;
+
+    verifier_stats.verifier = ctx.accounts.verifier.key();
+    verifier_stats.last_seen = clock.unix_timestamp;
+    verifier_stats.bump = ctx.bumps.verifier_stats;
+
+    emit!(VerifierHeartbeat {
+        verifier: verifier_stats.verifier,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("💓 Verifier heartbeat: {}", verifier_stats.verifier);
+
+    Ok(())
+}
@@ -1,9 +1,20 @@
+// The generated CPI helpers mirror instruction arity
+#![cfg_attr(feature = "cpi", allow(clippy::too_many_arguments))]
+
+pub mod attestation;
 pub mod constants;
 pub mod contexts;
+pub mod ed25519;
 pub mod error;
+pub mod field;
 pub mod groth16;
 pub mod instructions;
+pub mod pda;
+pub mod scope;
+pub mod signal;
 pub mod state;
+pub mod time;
+pub mod tree;
 
 use anchor_lang::prelude::*;
 
@@ -30,58 +41,538 @@ pub mod census {
     use super::*;
 
     /// Initialize the zk-Census system
-    pub fn initialize(ctx: Context<Initialize>, scope_duration: i64) -> Result<()> {
-        instructions::initialize::handler(ctx, scope_duration)
+    /// `scope_duration = 0` selects manual-only mode (scopes never auto-expire)
+    /// `max_citizens = 0` leaves registration uncapped
+    /// Counting stays closed until `min_registrations_to_activate` citizens register
+    /// `require_zk_proofs` disables the attestation path entirely
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        scope_duration: i64,
+        max_citizens: u64,
+        min_registrations_to_activate: u64,
+        require_zk_proofs: bool,
+        require_signal: bool,
+    ) -> Result<()> {
+        instructions::initialize::handler(
+            ctx,
+            scope_duration,
+            max_citizens,
+            min_registrations_to_activate,
+            require_zk_proofs,
+            require_signal,
+        )
     }
 
     /// Register a new citizen (admin-only, after NFC verification)
+    /// `commitment_version` must be in supported_commitment_versions
     pub fn register_citizen(
         ctx: Context<RegisterCitizen>,
         identity_commitment: [u8; 32],
+        commitment_version: u8,
     ) -> Result<()> {
-        instructions::register_citizen::handler(ctx, identity_commitment)
+        instructions::register_citizen::handler(ctx, identity_commitment, commitment_version)
     }
 
     /// Submit a census proof (anyone with valid proof)
+    /// `expected_root_version` is the root_version the proof was built against
+    /// `extra` is optional app metadata stored on the nullifier
+    /// `bucket_index` counts the submission into the scope's NumericAggregate
+    #[allow(clippy::too_many_arguments)]
     pub fn submit_census(
         ctx: Context<SubmitCensus>,
         proof_a: [u8; 64],
         proof_b: [u8; 128],
         proof_c: [u8; 64],
         public_inputs: [[u8; 32]; 4],
+        expected_root_version: Option<u64>,
+        extra: Option<[u8; constants::NULLIFIER_EXTRA_LEN]>,
+        bucket_index: Option<u8>,
+        participation_timestamp: Option<i64>,
     ) -> Result<()> {
-        instructions::submit_census::handler(ctx, proof_a, proof_b, proof_c, public_inputs)
+        instructions::submit_census::handler(
+            ctx,
+            proof_a,
+            proof_b,
+            proof_c,
+            public_inputs,
+            expected_root_version,
+            extra,
+            bucket_index,
+            participation_timestamp,
+        )
     }
 
     /// Submit a census attestation (verified off-chain, signature checked on-chain)
     /// This is the RECOMMENDED approach for production!
-    pub fn submit_attestation(
-        ctx: Context<SubmitAttestation>,
+    /// `nonce` is chosen by the verifier server and signed with the attestation
+    /// `extra` is optional app metadata stored on the nullifier
+    /// `bound_payer` must match the payer when the verifier signed for one
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_attestation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitAttestation<'info>>,
         timestamp: i64,
+        nonce: [u8; constants::ATTESTATION_NONCE_LEN],
         merkle_root: [u8; 32],
         nullifier_hash: [u8; 32],
         external_nullifier: [u8; 32],
         signal_hash: [u8; 32],
         signature: [u8; 64],
+        extra: Option<[u8; constants::NULLIFIER_EXTRA_LEN]>,
+        bound_payer: Option<Pubkey>,
+        membership: Option<MembershipProof>,
     ) -> Result<()> {
         instructions::submit_attestation::handler(
             ctx,
             timestamp,
+            nonce,
             merkle_root,
             nullifier_hash,
             external_nullifier,
             signal_hash,
             signature,
+            extra,
+            bound_payer,
+            membership,
         )
     }
 
     /// Advance to the next census scope (admin-only)
-    pub fn advance_scope(ctx: Context<AdvanceScope>) -> Result<()> {
+    pub fn advance_scope(ctx: Context<AdvanceScope>) -> Result<ScopeAdvanceResult> {
         instructions::advance_scope::handler(ctx)
     }
 
+    /// Advance to the next scope once the current one has expired (anyone)
+    pub fn try_advance_scope(ctx: Context<TryAdvanceScope>) -> Result<ScopeAdvanceResult> {
+        instructions::try_advance_scope::handler(ctx)
+    }
+
     /// Set the Merkle root (admin-only, called after building tree off-chain)
     pub fn set_merkle_root(ctx: Context<SetMerkleRoot>, root: [u8; 32]) -> Result<()> {
         instructions::set_merkle_root::handler(ctx, root)
     }
+
+    /// Set the nullifier TTL for continuous counting (admin-only, 0 = never expire)
+    pub fn set_nullifier_ttl(ctx: Context<UpdateConfig>, nullifier_ttl: i64) -> Result<()> {
+        instructions::set_nullifier_ttl::handler(ctx, nullifier_ttl)
+    }
+
+    /// Read census counters, including the proof vs attestation split
+    pub fn get_census_stats(ctx: Context<ReadCensusState>) -> Result<CensusStats> {
+        instructions::get_census_stats::handler(ctx)
+    }
+
+    /// Set the labels for the aggregate attribute slots (admin-only)
+    pub fn set_attribute_schema(
+        ctx: Context<SetAttributeSchema>,
+        labels: [[u8; constants::ATTRIBUTE_LABEL_LEN]; constants::ATTRIBUTE_COUNT],
+    ) -> Result<()> {
+        instructions::set_attribute_schema::handler(ctx, labels)
+    }
+
+    /// Pause the census - stops registrations and submissions (admin-only)
+    pub fn pause_census(ctx: Context<UpdateConfig>) -> Result<()> {
+        instructions::set_census_active::handler(ctx, false)
+    }
+
+    /// Resume a paused census (admin-only)
+    pub fn resume_census(ctx: Context<UpdateConfig>) -> Result<()> {
+        instructions::set_census_active::handler(ctx, true)
+    }
+
+    /// Re-link the census to a replacement Merkle tree (admin-only, census must be paused)
+    pub fn migrate_merkle_tree(
+        ctx: Context<MigrateMerkleTree>,
+        new_tree: Pubkey,
+        new_root: [u8; 32],
+    ) -> Result<()> {
+        instructions::migrate_merkle_tree::handler(ctx, new_tree, new_root)
+    }
+
+    /// Authorize a verifier to sign attestations (admin-only)
+    pub fn add_verifier(ctx: Context<UpdateConfig>, verifier: Pubkey) -> Result<()> {
+        instructions::add_verifier::handler(ctx, verifier)
+    }
+
+    /// Revoke a verifier's authorization (admin-only)
+    pub fn remove_verifier(ctx: Context<UpdateConfig>, verifier: Pubkey) -> Result<()> {
+        instructions::remove_verifier::handler(ctx, verifier)
+    }
+
+    /// Require M-of-N verifier signatures per attestation (admin-only)
+    pub fn set_verifier_threshold(ctx: Context<UpdateConfig>, verifier_threshold: u8) -> Result<()> {
+        instructions::set_verifier_threshold::handler(ctx, verifier_threshold)
+    }
+
+    /// Upgrade an older CensusState layout to the current version (admin-only)
+    pub fn migrate_state(ctx: Context<MigrateState>) -> Result<()> {
+        instructions::migrate_state::handler(ctx)
+    }
+
+    /// Read a closed scope's final population (read-only, via return data)
+    pub fn read_scope_snapshot(ctx: Context<ReadScopeSnapshot>, scope: u64) -> Result<ScopeSummary> {
+        instructions::read_scope_snapshot::handler(ctx, scope)
+    }
+
+    /// Cap the citizen roster (admin-only, 0 = unlimited)
+    pub fn set_max_citizens(ctx: Context<UpdateConfig>, max_citizens: u64) -> Result<()> {
+        instructions::set_max_citizens::handler(ctx, max_citizens)
+    }
+
+    /// Liveness ping from an authorized verifier
+    pub fn verifier_heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+        instructions::verifier_heartbeat::handler(ctx)
+    }
+
+    /// Grow a nullifier written by an older program version (anyone)
+    pub fn migrate_nullifier(ctx: Context<MigrateNullifier>, nullifier_hash: [u8; 32]) -> Result<()> {
+        instructions::migrate_nullifier::handler(ctx, nullifier_hash)
+    }
+
+    /// Correct current_population by hand (admin-only, census must be paused)
+    pub fn adjust_population(ctx: Context<UpdateConfig>, new_value: u64, reason_code: u8) -> Result<()> {
+        instructions::adjust_population::handler(ctx, new_value, reason_code)
+    }
+
+    /// Create the current scope's numeric histogram (admin-only)
+    pub fn init_numeric_aggregate(
+        ctx: Context<InitNumericAggregate>,
+        lower_bounds: [u64; constants::NUMERIC_BUCKET_COUNT],
+    ) -> Result<()> {
+        instructions::init_numeric_aggregate::handler(ctx, lower_bounds)
+    }
+
+    /// Let the previous scope accept in-flight submissions after an advance (admin-only)
+    pub fn set_scope_grace_seconds(ctx: Context<UpdateConfig>, scope_grace_seconds: i64) -> Result<()> {
+        instructions::set_scope_grace_seconds::handler(ctx, scope_grace_seconds)
+    }
+
+    /// Require Groth16 proofs and disable attestations (admin-only)
+    pub fn set_require_zk_proofs(ctx: Context<UpdateConfig>, require_zk_proofs: bool) -> Result<()> {
+        instructions::set_require_zk_proofs::handler(ctx, require_zk_proofs)
+    }
+
+    /// Set the account that receives rent from closed accounts (admin-only)
+    pub fn set_rent_sink(ctx: Context<UpdateConfig>, rent_sink: Pubkey) -> Result<()> {
+        instructions::set_rent_sink::handler(ctx, rent_sink)
+    }
+
+    /// Is the current scope expired, and if not, how long until it is? (read-only)
+    pub fn is_scope_expired(ctx: Context<ReadCensusState>) -> Result<ScopeExpiry> {
+        instructions::is_scope_expired::handler(ctx)
+    }
+
+    /// Switch the external-nullifier derivation, accepting both for this scope (admin-only)
+    pub fn rotate_external_nullifier(ctx: Context<UpdateConfig>, new_version: u8) -> Result<()> {
+        instructions::rotate_external_nullifier::handler(ctx, new_version)
+    }
+
+    /// Seed root, leaf count and registrations from an off-chain tree, once (admin-only)
+    pub fn import_genesis(
+        ctx: Context<UpdateConfig>,
+        root: [u8; 32],
+        leaf_count: u64,
+        total_registered: u64,
+    ) -> Result<()> {
+        instructions::import_genesis::handler(ctx, root, leaf_count, total_registered)
+    }
+
+    /// Cap the counts per scope (admin-only, 0 = unlimited)
+    pub fn set_max_population_per_scope(
+        ctx: Context<UpdateConfig>,
+        max_population_per_scope: u64,
+    ) -> Result<()> {
+        instructions::set_max_population_per_scope::handler(ctx, max_population_per_scope)
+    }
+
+    /// Authorized verifiers and the signature threshold (read-only)
+    pub fn list_verifiers(ctx: Context<ReadCensusState>) -> Result<VerifierList> {
+        instructions::list_verifiers::handler(ctx)
+    }
+
+    /// Replace a verifier key in one step (admin-only)
+    pub fn rotate_verifier(ctx: Context<UpdateConfig>, old: Pubkey, new: Pubkey) -> Result<()> {
+        instructions::rotate_verifier::handler(ctx, old, new)
+    }
+
+    /// Reject counts beyond the registered roster (admin-only)
+    pub fn set_enforce_population_invariant(
+        ctx: Context<UpdateConfig>,
+        enforce_population_invariant: bool,
+    ) -> Result<()> {
+        instructions::set_enforce_population_invariant::handler(ctx, enforce_population_invariant)
+    }
+
+    /// Set the per-scope population milestones to announce (admin-only)
+    pub fn set_milestones(
+        ctx: Context<UpdateConfig>,
+        milestones: [u64; constants::MILESTONE_COUNT],
+    ) -> Result<()> {
+        instructions::set_milestones::handler(ctx, milestones)
+    }
+
+    /// Link off-chain census metadata JSON by URI (admin-only)
+    pub fn set_metadata(
+        ctx: Context<SetMetadata>,
+        uri: [u8; constants::METADATA_URI_LEN],
+    ) -> Result<()> {
+        instructions::set_metadata::handler(ctx, uri)
+    }
+
+    /// Run every submit_attestation check without counting (read-only)
+    #[allow(clippy::too_many_arguments)]
+    pub fn dry_run_attestation<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DryRunAttestation<'info>>,
+        timestamp: i64,
+        nonce: [u8; constants::ATTESTATION_NONCE_LEN],
+        merkle_root: [u8; 32],
+        nullifier_hash: [u8; 32],
+        external_nullifier: [u8; 32],
+        signal_hash: [u8; 32],
+        signature: [u8; 64],
+        bound_payer: Option<Pubkey>,
+        membership: Option<MembershipProof>,
+    ) -> Result<AttestationDryRun> {
+        instructions::dry_run_attestation::handler(
+            ctx,
+            timestamp,
+            nonce,
+            merkle_root,
+            nullifier_hash,
+            external_nullifier,
+            signal_hash,
+            signature,
+            bound_payer,
+            membership,
+        )
+    }
+
+    /// Set the minimum seconds between counts from one payer (admin-only, 0 = off)
+    pub fn set_payer_cooldown_seconds(
+        ctx: Context<UpdateConfig>,
+        payer_cooldown_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_payer_cooldown_seconds::handler(ctx, payer_cooldown_seconds)
+    }
+
+    /// Set the initial verifier allowlist and threshold atomically (admin-only,
+    /// only while no verifiers are configured)
+    pub fn init_verifiers(
+        ctx: Context<UpdateConfig>,
+        verifiers: Vec<Pubkey>,
+        verifier_threshold: u8,
+    ) -> Result<()> {
+        instructions::init_verifiers::handler(ctx, verifiers, verifier_threshold)
+    }
+
+    /// Verification key proofs are checked against, with its hash (read-only)
+    pub fn get_verification_key(ctx: Context<ReadCensusState>) -> Result<VerificationKeyExport> {
+        instructions::get_verification_key::handler(ctx)
+    }
+
+    /// Set how signals are interpreted: 0 = attribute, 1 = opaque commit (admin-only)
+    pub fn set_signal_mode(ctx: Context<UpdateConfig>, signal_mode: u8) -> Result<()> {
+        instructions::set_signal_mode::handler(ctx, signal_mode)
+    }
+
+    /// Freeze registrations once the current scope has counted anyone (admin-only)
+    pub fn set_freeze_registrations_during_scope(
+        ctx: Context<UpdateConfig>,
+        freeze_registrations_during_scope: bool,
+    ) -> Result<()> {
+        instructions::set_freeze_registrations_during_scope::handler(
+            ctx,
+            freeze_registrations_during_scope,
+        )
+    }
+
+    /// Validate each scope's proofs against the root it opened with (admin-only)
+    pub fn set_pin_root_per_scope(ctx: Context<UpdateConfig>, pin_root_per_scope: bool) -> Result<()> {
+        instructions::set_pin_root_per_scope::handler(ctx, pin_root_per_scope)
+    }
+
+    /// Reject any count into a scope once it has been closed (admin-only)
+    pub fn set_seal_closed_scopes(ctx: Context<UpdateConfig>, seal_closed_scopes: bool) -> Result<()> {
+        instructions::set_seal_closed_scopes::handler(ctx, seal_closed_scopes)
+    }
+
+    /// Hand the linked Merkle tree's authority to another key (admin-only, census must be paused)
+    pub fn set_tree_authority(ctx: Context<SetTreeAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::set_tree_authority::handler(ctx, new_authority)
+    }
+
+    /// Accept submissions again after close_counting (admin-only)
+    pub fn open_counting(ctx: Context<UpdateConfig>) -> Result<()> {
+        instructions::set_counting_open::handler(ctx, true)
+    }
+
+    /// Stop submissions while keeping registrations open (admin-only)
+    pub fn close_counting(ctx: Context<UpdateConfig>) -> Result<()> {
+        instructions::set_counting_open::handler(ctx, false)
+    }
+
+    /// A scope's participant count and attribute breakdown (read-only)
+    pub fn read_aggregate(ctx: Context<ReadAggregate>, scope: u64) -> Result<AggregateSummary> {
+        instructions::read_aggregate::handler(ctx, scope)
+    }
+
+    /// Anchor scope numbers to a calendar starting at `scope_epoch` (admin-only)
+    pub fn set_scope_calendar(
+        ctx: Context<UpdateConfig>,
+        scope_epoch: i64,
+        calendar_scopes: bool,
+    ) -> Result<()> {
+        instructions::set_scope_calendar::handler(ctx, scope_epoch, calendar_scopes)
+    }
+
+    /// try_advance_scope plus the next scope's aggregate, atomically (anyone)
+    pub fn roll_scope(ctx: Context<RollScope>, next_scope: u64) -> Result<ScopeAdvanceResult> {
+        instructions::roll_scope::handler(ctx, next_scope)
+    }
+
+    /// Track the current scope's counted nullifiers in a bitmap (admin-only)
+    pub fn init_nullifier_bitmap(ctx: Context<InitNullifierBitmap>) -> Result<()> {
+        instructions::init_nullifier_bitmap::handler(ctx)
+    }
+
+    /// Set the circuit/vkey commitment signed into attestations (admin-only)
+    pub fn set_circuit_hash(ctx: Context<UpdateConfig>, circuit_hash: [u8; 32]) -> Result<()> {
+        instructions::set_circuit_hash::handler(ctx, circuit_hash)
+    }
+
+    /// Stop accepting a verifier's attestations after `grace_seconds` (admin-only)
+    pub fn deprecate_verifier(
+        ctx: Context<UpdateConfig>,
+        verifier: Pubkey,
+        grace_seconds: i64,
+    ) -> Result<()> {
+        instructions::deprecate_verifier::handler(ctx, verifier, grace_seconds)
+    }
+
+    /// Set the commitment formats register_citizen accepts, one bit per version (admin-only)
+    pub fn set_supported_commitment_versions(
+        ctx: Context<UpdateConfig>,
+        supported_commitment_versions: u32,
+    ) -> Result<()> {
+        instructions::set_supported_commitment_versions::handler(ctx, supported_commitment_versions)
+    }
+
+    /// Require an identity leaf inclusion proof on attestations (admin-only)
+    pub fn set_strict_membership(ctx: Context<UpdateConfig>, strict_membership: bool) -> Result<()> {
+        instructions::set_strict_membership::handler(ctx, strict_membership)
+    }
+
+    /// Leaves left before the Merkle tree is full (read-only)
+    pub fn tree_capacity_remaining(ctx: Context<ReadCensusState>) -> Result<u64> {
+        instructions::tree_capacity_remaining::handler(ctx)
+    }
+
+    /// Close stale nullifiers passed as remaining accounts, refunding rent to the rent sink
+    pub fn batch_close_nullifiers<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BatchCloseNullifiers<'info>>,
+    ) -> Result<NullifierSweep> {
+        instructions::batch_close_nullifiers::handler(ctx)
+    }
+
+    /// Reject counts with the declined signal (admin-only)
+    pub fn set_require_signal(ctx: Context<UpdateConfig>, require_signal: bool) -> Result<()> {
+        instructions::set_require_signal::handler(ctx, require_signal)
+    }
+
+    /// Correct a mis-registered identity commitment while paused (admin-only)
+    pub fn replace_commitment<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ReplaceCommitment<'info>>,
+        leaf_index: u64,
+        old: [u8; 32],
+        new: [u8; 32],
+    ) -> Result<()> {
+        instructions::replace_commitment::handler(ctx, leaf_index, old, new)
+    }
+
+    /// Emit ParticipationRecorded for rewards programs on each count (admin-only)
+    pub fn set_emit_participation_events(
+        ctx: Context<UpdateConfig>,
+        emit_participation_events: bool,
+    ) -> Result<()> {
+        instructions::set_emit_participation_events::handler(ctx, emit_participation_events)
+    }
+
+    /// Create the growable verifier registry and authorize attestations from it (admin-only)
+    pub fn init_verifier_registry(ctx: Context<InitVerifierRegistry>) -> Result<()> {
+        instructions::init_verifier_registry::handler(ctx)
+    }
+
+    /// Add a verifier to the registry (admin-only)
+    pub fn add_registry_verifier(ctx: Context<AddRegistryVerifier>, verifier: Pubkey) -> Result<()> {
+        instructions::add_registry_verifier::handler(ctx, verifier)
+    }
+
+    /// Remove a verifier from the registry (admin-only)
+    pub fn remove_registry_verifier(
+        ctx: Context<RemoveRegistryVerifier>,
+        verifier: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_registry_verifier::handler(ctx, verifier)
+    }
+
+    /// Link or unlink (default pubkey) the verifier registry (admin-only)
+    pub fn set_verifier_registry(ctx: Context<UpdateConfig>, verifier_registry: Pubkey) -> Result<()> {
+        instructions::set_verifier_registry::handler(ctx, verifier_registry)
+    }
+
+    /// Set how much a verifier's signature counts toward the weight threshold (admin-only)
+    pub fn set_verifier_weight(ctx: Context<UpdateConfig>, verifier: Pubkey, weight: u16) -> Result<()> {
+        instructions::set_verifier_weight::handler(ctx, verifier, weight)
+    }
+
+    /// Set the summed signer weight each attestation needs, 0 to ignore weights (admin-only)
+    pub fn set_weight_threshold(ctx: Context<UpdateConfig>, weight_threshold: u16) -> Result<()> {
+        instructions::set_weight_threshold::handler(ctx, weight_threshold)
+    }
+
+    /// Set how often CitizenRegistered checkpoints the tree root, 0 to never (admin-only)
+    pub fn set_root_checkpoint_interval(
+        ctx: Context<UpdateConfig>,
+        root_checkpoint_interval: u64,
+    ) -> Result<()> {
+        instructions::set_root_checkpoint_interval::handler(ctx, root_checkpoint_interval)
+    }
+
+    /// Set how old a submit_census participation timestamp may be, 0 for no bound (admin-only)
+    pub fn set_max_proof_age(ctx: Context<UpdateConfig>, max_proof_age: i64) -> Result<()> {
+        instructions::set_max_proof_age::handler(ctx, max_proof_age)
+    }
+
+    /// When the current scope reaches its duration, with the current scope number (read-only)
+    pub fn next_scope_boundary(ctx: Context<ReadCensusState>) -> Result<ScopeBoundary> {
+        instructions::next_scope_boundary::handler(ctx)
+    }
+
+    /// Set how long registrations stay valid (admin-only, 0 = never lapse)
+    pub fn set_membership_duration(
+        ctx: Context<UpdateConfig>,
+        membership_duration: i64,
+    ) -> Result<()> {
+        instructions::set_membership_duration::handler(ctx, membership_duration)
+    }
+
+    /// Extend a registration's expiry by membership_duration (admin-only)
+    pub fn renew_membership(
+        ctx: Context<RenewMembership>,
+        identity_commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::renew_membership::handler(ctx, identity_commitment)
+    }
+
+    /// Tally the current scope's counts into a CensusAggregate (admin-only)
+    pub fn init_aggregate(ctx: Context<InitAggregate>) -> Result<()> {
+        instructions::init_aggregate::handler(ctx)
+    }
+
+    /// Set how long published roots stay valid for submit_census (admin-only, 0 = by count only)
+    pub fn set_root_validity_seconds(
+        ctx: Context<UpdateConfig>,
+        root_validity_seconds: i64,
+    ) -> Result<()> {
+        instructions::set_root_validity_seconds::handler(ctx, root_validity_seconds)
+    }
 }
@@ -0,0 +1,72 @@
+//! PDA derivation for every account the program owns
+//!
+//! Clients and tests should derive addresses here instead of repeating the
+//! seed constants, so a seed change can't leave them deriving stale PDAs.
+
+use anchor_lang::prelude::*;
+use crate::constants::*;
+
+/// Singleton CensusState
+pub fn census_state_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CENSUS_STATE_SEED], program_id)
+}
+
+/// Nullifier record for `nullifier_hash`
+pub fn nullifier_pda(nullifier_hash: &[u8; 32], program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NULLIFIER_SEED, nullifier_hash], program_id)
+}
+
+/// Merkle tree account created by `admin`
+pub fn merkle_tree_pda(admin: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[MERKLE_TREE_SEED, admin.as_ref()], program_id)
+}
+
+/// CensusAggregate for `scope`
+pub fn aggregate_pda(scope: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[AGGREGATE_SEED, &scope.to_le_bytes()], program_id)
+}
+
+/// Singleton CensusMetadata
+pub fn census_metadata_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CENSUS_METADATA_SEED], program_id)
+}
+
+/// NumericAggregate for `scope`
+pub fn numeric_aggregate_pda(scope: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NUMERIC_AGGREGATE_SEED, &scope.to_le_bytes()], program_id)
+}
+
+/// Singleton AttributeSchema
+pub fn attribute_schema_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ATTRIBUTE_SCHEMA_SEED], program_id)
+}
+
+/// VerifierStats for `verifier`
+pub fn verifier_stats_pda(verifier: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VERIFIER_STATS_SEED, verifier.as_ref()], program_id)
+}
+
+/// PayerCooldown for `payer`
+pub fn payer_cooldown_pda(payer: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PAYER_COOLDOWN_SEED, payer.as_ref()], program_id)
+}
+
+/// ScopeSnapshot written when `scope` closed
+pub fn scope_snapshot_pda(scope: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[SCOPE_SNAPSHOT_SEED, &scope.to_le_bytes()], program_id)
+}
+
+/// NullifierBitmap for `scope`
+pub fn nullifier_bitmap_pda(scope: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[NULLIFIER_BITMAP_SEED, &scope.to_le_bytes()], program_id)
+}
+
+/// Singleton VerifierRegistry
+pub fn verifier_registry_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VERIFIER_REGISTRY_SEED], program_id)
+}
+
+/// CitizenRecord for `identity_commitment`
+pub fn citizen_record_pda(identity_commitment: &[u8; 32], program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CITIZEN_RECORD_SEED, identity_commitment], program_id)
+}
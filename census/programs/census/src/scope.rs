@@ -0,0 +1,183 @@
+//! Scope timing helpers
+//!
+//! A `scope_duration` of 0 is "manual-only" mode: scopes never expire on
+//! their own and only the admin can close them via `advance_scope`.
+//!
+//! For `scope_grace_seconds` after an advance, submissions carrying the
+//! previous scope's external nullifier still count toward that scope.
+//!
+//! Rotating the external-nullifier derivation (`external_nullifier_for`):
+//! 1. Ship clients that can derive both the old and the new version
+//! 2. Admin calls `rotate_external_nullifier(new_version)`; the current scope
+//!    becomes the transition scope and accepts both derivations
+//! 3. Clients switch to the new version
+//! 4. Once the scope advances, the old derivation is rejected automatically
+//!    (except for grace-window submissions into the transition scope)
+//!
+//! Nullifier accumulator: every count folds its nullifier into a per-scope
+//! hash chain, `acc = poseidon(acc, nullifier_hash)`, starting from 32 zero
+//! bytes. Counts toward the current scope extend
+//! `CensusState::running_nullifier_accumulator`; advancing copies it into the
+//! scope's ScopeSnapshot and resets it, and grace-window counts extend the
+//! snapshot's copy. To check a closed scope off-chain, fold the
+//! `nullifier_hash` of its CensusCounted events in `seq` order through
+//! `accumulate_nullifier` and compare with `ScopeSnapshot::nullifier_accumulator`.
+
+use anchor_lang::prelude::*;
+use crate::{
+    constants::{EXTERNAL_NULLIFIER_DOMAIN, EXTERNAL_NULLIFIER_DOMAIN_TAG},
+    error::CensusError,
+    groth16,
+};
+
+/// Has the scope that started at `scope_start_time` run past its duration?
+/// A scope covers `[scope_start_time, scope_start_time + scope_duration)`.
+/// Always false in manual-only mode (`scope_duration == 0`).
+pub fn is_scope_expired(scope_start_time: i64, scope_duration: i64, now: i64) -> bool {
+    if scope_duration == 0 {
+        return false;
+    }
+    now >= scope_start_time.saturating_add(scope_duration)
+}
+
+/// Seconds left before the scope expires; zero or negative once it has.
+/// i64::MAX in manual-only mode.
+pub fn seconds_until_expiry(scope_start_time: i64, scope_duration: i64, now: i64) -> i64 {
+    if scope_duration == 0 {
+        return i64::MAX;
+    }
+    scope_start_time.saturating_add(scope_duration).saturating_sub(now)
+}
+
+/// When the scope that started at `scope_start_time` expires, i.e. the
+/// first second try_advance_scope succeeds. i64::MAX in manual-only mode.
+pub fn next_scope_boundary(scope_start_time: i64, scope_duration: i64) -> i64 {
+    if scope_duration == 0 {
+        return i64::MAX;
+    }
+    scope_start_time.saturating_add(scope_duration)
+}
+
+/// Scope number in effect at `timestamp` for a calendar that started
+/// scope 1 at `genesis` and advances every `duration` seconds. Lets clients
+/// derive the external nullifier without reading CensusState. Only exact
+/// while scopes are advanced on schedule: advancing resets
+/// `scope_start_time` to the advance time, so a late advance shifts the
+/// calendar (unless `calendar_scopes` is on). Before genesis, and in
+/// manual-only mode, this is scope 1.
+pub fn scope_at(timestamp: i64, genesis: i64, duration: i64) -> u64 {
+    if duration <= 0 || timestamp < genesis {
+        return 1;
+    }
+    let elapsed = (timestamp as i128 - genesis as i128) as u128;
+    let index = (elapsed / duration as u128) as u64;
+    index.saturating_add(1)
+}
+
+/// When `scope` opens on the calendar of `scope_at`; None if it overflows
+pub fn scope_start(scope: u64, genesis: i64, duration: i64) -> Option<i64> {
+    let index = i64::try_from(scope.checked_sub(1)?).ok()?;
+    genesis.checked_add(index.checked_mul(duration)?)
+}
+
+/// Scope a calendar advance at `now` opens after `current_scope`, with its
+/// start: the calendar's scope at `now` (at least `current_scope + 1`) at
+/// its calendar start, clamped to `now` when the census runs ahead of the
+/// calendar. The new scope is therefore live at `now` and not yet expired,
+/// so a second advance in the same second fails. None on overflow
+pub fn calendar_advance(current_scope: u64, now: i64, genesis: i64, duration: i64) -> Option<(u64, i64)> {
+    let scope = current_scope.checked_add(1)?.max(scope_at(now, genesis, duration));
+    let start = scope_start(scope, genesis, duration)?.min(now);
+    Some((scope, start))
+}
+
+/// Next value of a scope's nullifier accumulator after counting
+/// `nullifier_hash` (a big-endian field element, as the circuit outputs it)
+pub fn accumulate_nullifier(accumulator: &[u8; 32], nullifier_hash: &[u8; 32]) -> Result<[u8; 32]> {
+    groth16::poseidon_hash(&[*accumulator, *nullifier_hash])
+}
+
+/// External nullifier a proof for `scope` commits to under `version`:
+/// - v0: scope LE, zero-padded to 32 bytes
+/// - v1: poseidon(EXTERNAL_NULLIFIER_DOMAIN, scope), big-endian
+/// - v2: `expected_external_nullifier(scope, census_state, EXTERNAL_NULLIFIER_DOMAIN_TAG)`
+///
+/// `census_state` is the CensusState address; only v2 depends on it.
+pub fn external_nullifier_for(version: u8, scope: u64, census_state: &Pubkey) -> Result<[u8; 32]> {
+    match version {
+        0 => {
+            let mut external_nullifier = [0u8; 32];
+            external_nullifier[..8].copy_from_slice(&scope.to_le_bytes());
+            Ok(external_nullifier)
+        }
+        1 => {
+            let mut scope_field = [0u8; 32];
+            scope_field[24..].copy_from_slice(&scope.to_be_bytes());
+            groth16::poseidon_hash(&[EXTERNAL_NULLIFIER_DOMAIN, scope_field])
+        }
+        2 => expected_external_nullifier(scope, census_state, EXTERNAL_NULLIFIER_DOMAIN_TAG),
+        _ => err!(CensusError::InvalidExternalNullifierVersion),
+    }
+}
+
+/// Scope a v0 external nullifier encodes (scope LE, zero-padded); None if it
+/// isn't in that layout. v1 and v2 are hashes, so their scope can only be
+/// found by re-deriving candidates (see `CensusState::future_scope_offset`).
+pub fn decode_scope(external_nullifier: &[u8; 32]) -> Option<u64> {
+    let (scope, padding) = external_nullifier.split_at(8);
+    if padding.iter().any(|&byte| byte != 0) {
+        return None;
+    }
+    Some(u64::from_le_bytes(scope.try_into().unwrap()))
+}
+
+/// Census-bound external nullifier for `scope`: poseidon(domain_tag,
+/// census_state[..16], census_state[16..], scope), each a big-endian field
+/// element. Binding the census address keeps proofs for one census from
+/// replaying into another. Clients derive v2 external nullifiers with this.
+pub fn expected_external_nullifier(
+    scope: u64,
+    census_state: &Pubkey,
+    domain_tag: [u8; 8],
+) -> Result<[u8; 32]> {
+    let field = |bytes: &[u8]| {
+        let mut field = [0u8; 32];
+        field[32 - bytes.len()..].copy_from_slice(bytes);
+        field
+    };
+    let address = census_state.to_bytes();
+    groth16::poseidon_hash(&[
+        field(&domain_tag),
+        field(&address[..16]),
+        field(&address[16..]),
+        field(&scope.to_be_bytes()),
+    ])
+}
+
+/// Which scope a submission counts toward, given whether its external
+/// nullifier matches the current and the previous scope: the current scope,
+/// or the previous one while still inside its grace window.
+pub fn submission_scope(
+    current_scope: u64,
+    scope_start_time: i64,
+    scope_grace_seconds: i64,
+    now: i64,
+    matches_current: bool,
+    matches_previous: bool,
+) -> Option<u64> {
+    if matches_current {
+        return Some(current_scope);
+    }
+
+    let previous_scope = current_scope.checked_sub(1)?;
+    let in_grace = in_grace_window(scope_start_time, scope_grace_seconds, now);
+    (in_grace && matches_previous).then_some(previous_scope)
+}
+
+/// Does the previous scope still take submissions, `scope_grace_seconds`
+/// into the scope that started at `scope_start_time`?
+pub fn in_grace_window(scope_start_time: i64, scope_grace_seconds: i64, now: i64) -> bool {
+    scope_grace_seconds > 0
+        && now >= scope_start_time
+        && now < scope_start_time.saturating_add(scope_grace_seconds)
+}
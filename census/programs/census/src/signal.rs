@@ -0,0 +1,41 @@
+//! Decoding the broadcast signal into a CensusAggregate attribute slot
+//!
+//! Signal values 1..=ATTRIBUTE_COUNT (as a big-endian field element) select
+//! attribute slot `value - 1`. Signal 0 is the "declined" sentinel: the
+//! submission counts as a participant but discloses no attribute. Any other
+//! signal (e.g. a hashed message) likewise has no attribute.
+//!
+//! Every countable submission with an attribute emits `SignalObserved`, so
+//! operators who index off-chain can tally the distribution without paying
+//! rent for a per-scope aggregate PDA.
+//!
+//! `CensusState::signal_mode` picks the interpretation. SIGNAL_MODE_OPAQUE
+//! skips decoding and emits every signal verbatim in `SignalCommitted`, for
+//! deployments where the signal is an anonymous message (e.g. a vote choice
+//! hash) rather than a demographic code.
+
+use crate::constants::ATTRIBUTE_COUNT;
+
+/// Signal value meaning "participating, no demographic disclosed"
+pub const SIGNAL_DECLINED: u8 = 0;
+
+/// Signals are attribute codes, decoded by `decode_signal`
+pub const SIGNAL_MODE_ATTRIBUTE: u8 = 0;
+
+/// Signals are opaque 32-byte commitments, logged without interpretation
+pub const SIGNAL_MODE_OPAQUE: u8 = 1;
+
+/// Is `signal_hash` the declined sentinel (a zero field element)?
+pub fn is_declined(signal_hash: &[u8; 32]) -> bool {
+    signal_hash[..31].iter().all(|&b| b == 0) && signal_hash[31] == SIGNAL_DECLINED
+}
+
+/// Attribute slot selected by `signal_hash`, or None when declined / not an attribute
+pub fn decode_signal(signal_hash: &[u8; 32]) -> Option<u8> {
+    let (high, low) = signal_hash.split_at(31);
+    if high.iter().any(|&b| b != 0) {
+        return None;
+    }
+    let value = low[0] as usize;
+    (1..=ATTRIBUTE_COUNT).contains(&value).then(|| (value - 1) as u8)
+}
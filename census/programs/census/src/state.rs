@@ -1,4 +1,10 @@
 use anchor_lang::prelude::*;
+use crate::constants::{
+    ATTESTATION_NONCE_LEN, ATTRIBUTE_COUNT, ATTRIBUTE_LABEL_LEN, FUTURE_SCOPE_LOOKAHEAD,
+    MAX_LEAVES, MAX_VERIFIERS, METADATA_URI_LEN,
+    NULLIFIER_BITMAP_BYTES, NULLIFIER_BITMAP_HASHES,
+    MILESTONE_COUNT, NULLIFIER_EXTRA_LEN, NUMERIC_BUCKET_COUNT, RECENT_ROOTS_CAPACITY,
+};
 
 // ============================================================================
 // CENSUS STATE - Main configuration account
@@ -11,6 +17,7 @@ pub struct CensusState {
     pub admin: Pubkey,
     
     /// Current Merkle tree root (updated on each registration)
+    /// Earlier roots stay acceptable while in `recent_roots`
     pub merkle_root: [u8; 32],
     
     /// Address of the SPL Concurrent Merkle Tree account
@@ -23,6 +30,7 @@ pub struct CensusState {
     pub scope_start_time: i64,
     
     /// Duration of each census scope in seconds (e.g., 1 week = 604800)
+    /// 0 = manual-only mode: scopes never expire, only advance_scope closes them
     pub scope_duration: i64,
     
     /// Total number of unique citizens registered (all-time)
@@ -39,12 +47,436 @@ pub struct CensusState {
     
     /// Bump seed for PDA derivation
     pub bump: u8,
+    
+    /// Seconds after which a used nullifier can be counted again (0 = never)
+    pub nullifier_ttl: i64,
+    
+    /// Counts recorded via trustless Groth16 proofs (submit_census)
+    pub counted_by_proof: u64,
+    
+    /// Counts recorded via trusted verifier attestations (submit_attestation)
+    pub counted_by_attestation: u64,
+    
+    /// Verifiers trusted to sign attestations (first `verifier_count` are active)
+    pub authorized_verifiers: [Pubkey; MAX_VERIFIERS],
+    
+    /// Number of active entries in `authorized_verifiers`
+    pub verifier_count: u8,
+    
+    /// Distinct authorized verifier signatures required per attestation (M of N)
+    pub verifier_threshold: u8,
+    
+    /// Monotonic count of successful submissions (both paths, never reset)
+    /// Indexers detect missed events by gaps in `seq`
+    pub submission_seq: u64,
+    
+    /// Layout version of this account (CENSUS_STATE_VERSION at init/migration)
+    /// New fields are only ever appended after this point
+    pub version: u8,
+    
+    /// Cap on total_registered (0 = unlimited)
+    pub max_citizens: u64,
+    
+    /// Incremented every time merkle_root changes
+    pub root_version: u64,
+    
+    /// Counting stays closed until total_registered reaches this (privacy floor)
+    pub min_registrations_to_activate: u64,
+    
+    /// Seconds after an advance during which the previous scope still
+    /// accepts submissions (0 = no grace)
+    pub scope_grace_seconds: i64,
+    
+    /// Only accept Groth16 proofs; submit_attestation is disabled
+    pub require_zk_proofs: bool,
+    
+    /// Only account that close instructions may refund rent to (default = admin)
+    pub rent_sink: Pubkey,
+    
+    /// Derivation clients use for external nullifiers (scope::external_nullifier_for)
+    pub external_nullifier_version: u8,
+    
+    /// Derivation in use before the last rotation
+    pub previous_external_nullifier_version: u8,
+    
+    /// Scope in which the last rotation happened; it and earlier scopes still
+    /// accept the previous derivation (0 = never rotated)
+    pub external_nullifier_transition_scope: u64,
+    
+    /// Sanity ceiling on counts per scope (0 = unlimited)
+    pub max_population_per_scope: u64,
+    
+    /// Reject counts that would push a scope's population past the roster
+    pub enforce_population_invariant: bool,
+    
+    /// Population thresholds that emit PopulationMilestone (0 = unused slot)
+    pub milestones: [u64; MILESTONE_COUNT],
+    
+    /// Bit i set once milestones[i] fired this scope (reset on advance)
+    pub milestones_fired: u8,
+    
+    /// Minimum seconds between counts submitted by the same payer (0 = off)
+    pub payer_cooldown_seconds: i64,
+    
+    /// Hash chain over nullifiers counted this scope (see
+    /// scope::accumulate_nullifier); copied into the ScopeSnapshot on advance
+    pub running_nullifier_accumulator: [u8; 32],
+    
+    /// How submissions' signal_hash is interpreted (see signal.rs)
+    pub signal_mode: u8,
+    
+    /// Reject registrations once the current scope has counted anyone, so
+    /// the root stays stable while proofs are in flight
+    pub freeze_registrations_during_scope: bool,
+    
+    /// merkle_root when the current scope opened (or pinning was enabled)
+    pub scope_root: [u8; 32],
+    
+    /// Validate proofs against scope_root instead of the live merkle_root
+    pub pin_root_per_scope: bool,
+    
+    /// Most recent scope closed by an advance (0 = none yet)
+    pub last_closed_scope: u64,
+    
+    /// Reject counts into any scope <= last_closed_scope, grace window included
+    pub seal_closed_scopes: bool,
+    
+    /// Accept submissions? Unlike is_active (the full pause), closing
+    /// counting leaves registrations and admin config open
+    pub counting_open: bool,
+    
+    /// Genesis of the scope calendar: scope 1 opens here and scope N
+    /// `(N - 1) * scope_duration` seconds later (see scope::scope_at)
+    pub scope_epoch: i64,
+    
+    /// try_advance_scope jumps to the calendar's scope instead of +1
+    pub calendar_scopes: bool,
+    
+    /// Latest scope with a NullifierBitmap (0 = none); its counts must pass it
+    pub nullifier_bitmap_scope: u64,
+    
+    /// Commitment to the circuit/vkey attestations are issued for; signed
+    /// into every attestation message (see attestation.rs)
+    pub circuit_hash: [u8; 32],
+    
+    /// Per allowlist slot: when a deprecated verifier stops being accepted
+    /// (0 = not deprecated). Moves with its verifier on removal
+    pub verifier_accepted_until: [i64; MAX_VERIFIERS],
+    
+    /// Identity commitment formats register_citizen accepts: bit `v` set
+    /// accepts commitment_version `v`
+    pub supported_commitment_versions: u32,
+    
+    /// When set, submit_attestation also requires an on-chain inclusion
+    /// proof of the identity leaf (see submit_attestation::verify_membership).
+    /// dry_run_attestation does not check it
+    pub strict_membership: bool,
+    
+    /// Reject counts carrying the declined signal (mandatory surveys)
+    pub require_signal: bool,
+    
+    /// Emit ParticipationRecorded (naming the payer) on every count, for
+    /// external rewards programs
+    pub emit_participation_events: bool,
+    
+    /// VerifierRegistry that authorizes attestation signers in place of
+    /// `authorized_verifiers` (default = use the inline allowlist). Deprecation
+    /// grace and heartbeats only cover the inline allowlist
+    pub verifier_registry: Pubkey,
+    
+    /// Per allowlist slot: the verifier's weight toward `weight_threshold`
+    /// (nonzero for active slots). Moves with its verifier on removal
+    pub verifier_weights: [u16; MAX_VERIFIERS],
+    
+    /// Summed weight of distinct valid signers each attestation needs, on
+    /// top of `verifier_threshold` (0 = weights ignored)
+    pub weight_threshold: u16,
+    
+    /// CitizenRegistered carries the linked tree's root on every leaf_count
+    /// that is a multiple of this (0 = never)
+    pub root_checkpoint_interval: u64,
+    
+    /// submit_census requires a proven participation_timestamp at most this
+    /// many seconds old (0 = no freshness bound); settable only once the
+    /// verification key has the timestamp input
+    pub max_proof_age: i64,
+    
+    /// Set once by import_genesis (or by migration for a census that already
+    /// has leaves); import_genesis refuses to run again after that
+    pub genesis_imported: bool,
+    
+    /// Seconds a registration stays valid; register_citizen then requires a
+    /// CitizenRecord carrying its expiry (0 = memberships never lapse)
+    pub membership_duration: i64,
+    
+    /// Latest scope with a CensusAggregate (0 = none); its counts must update it
+    pub aggregate_scope: u64,
+    
+    /// Roots published by `set_root`, oldest first, so the last is the live
+    /// root once one has been published. Only the first
+    /// `active_recent_roots` slots are in use
+    pub recent_roots: [[u8; 32]; RECENT_ROOTS_CAPACITY],
+    
+    /// Slots of `recent_roots` in use: the next root is written here until
+    /// the buffer is full, after which the oldest is evicted
+    pub active_recent_roots: u8,
+    
+    /// When each `recent_roots` entry was published (cluster clock)
+    pub recent_roots_set_at: [i64; RECENT_ROOTS_CAPACITY],
+    
+    /// A recent root other than the live one is accepted only this many
+    /// seconds after it was published (0 = bounded by count alone)
+    pub root_validity_seconds: i64,
+}
+
+impl CensusState {
+    /// Have enough citizens registered for counting to open?
+    pub fn is_ready(&self) -> bool {
+        self.total_registered >= self.min_registrations_to_activate
+    }
+
+    /// Submissions counted over the census's lifetime, by either path
+    pub fn lifetime_counted(&self) -> u64 {
+        self.counted_by_proof.saturating_add(self.counted_by_attestation)
+    }
+
+    /// Could one more count fit, given `scope_population` already counted in
+    /// its scope? No scope holds more than MAX_LEAVES citizens, so neither
+    /// can a scope's population reach it nor the lifetime total reach
+    /// MAX_LEAVES per scope opened so far. Failing means counting is broken.
+    pub fn is_population_possible(&self, scope_population: u64) -> bool {
+        scope_population < MAX_LEAVES
+            && self.lifetime_counted() < MAX_LEAVES.saturating_mul(self.current_scope)
+    }
+
+    /// Root submissions must prove against: the scope's pinned root when
+    /// `pin_root_per_scope` is on, else the live merkle_root
+    pub fn accepted_root(&self) -> &[u8; 32] {
+        if self.pin_root_per_scope {
+            &self.scope_root
+        } else {
+            &self.merkle_root
+        }
+    }
+
+    /// Publish `root` as the live merkle_root at `now`: bump root_version
+    /// and append it to recent_roots
+    pub fn set_root(&mut self, root: [u8; 32], now: i64) -> Result<()> {
+        self.merkle_root = root;
+        self.root_version = self.root_version
+            .checked_add(1)
+            .ok_or(crate::error::CensusError::ArithmeticOverflow)?;
+        self.push_recent_root(root, now);
+        Ok(())
+    }
+
+    /// Append `root`, published at `set_at`, to recent_roots, evicting the
+    /// oldest once full
+    pub fn push_recent_root(&mut self, root: [u8; 32], set_at: i64) {
+        let active = self.active_recent_roots as usize;
+        if active < RECENT_ROOTS_CAPACITY {
+            self.recent_roots[active] = root;
+            self.recent_roots_set_at[active] = set_at;
+            self.active_recent_roots += 1;
+        } else {
+            self.recent_roots.rotate_left(1);
+            self.recent_roots_set_at.rotate_left(1);
+            self.recent_roots[RECENT_ROOTS_CAPACITY - 1] = root;
+            self.recent_roots_set_at[RECENT_ROOTS_CAPACITY - 1] = set_at;
+        }
+    }
+
+    /// The in-use slots of recent_roots; unused zeroed slots never match
+    pub fn recent_roots(&self) -> &[[u8; 32]] {
+        let active = (self.active_recent_roots as usize).min(RECENT_ROOTS_CAPACITY);
+        &self.recent_roots[..active]
+    }
+
+    /// Can a submit_census proof be against `root` at `now`? Under
+    /// `pin_root_per_scope` only the pinned root; otherwise the live root or
+    /// a recent one, so proofs in flight survive a registration. A recent
+    /// root must also be at most `root_validity_seconds` old, when set.
+    pub fn accepts_proof_root(&self, root: &[u8; 32], now: i64) -> bool {
+        if self.pin_root_per_scope {
+            return root == &self.scope_root;
+        }
+        root == &self.merkle_root
+            || self.recent_roots().iter().zip(&self.recent_roots_set_at).any(|(recent, &set_at)| {
+                recent == root
+                    && (self.root_validity_seconds == 0
+                        || now.saturating_sub(set_at) <= self.root_validity_seconds)
+            })
+    }
+
+    /// Is `scope` closed to further counts? Only when `seal_closed_scopes` is on
+    pub fn is_scope_sealed(&self, scope: u64) -> bool {
+        self.seal_closed_scopes && scope <= self.last_closed_scope
+    }
+
+    /// Can a nullifier recorded in `scope` be closed at `now`? Only once no
+    /// submission can count toward that scope again: it is closed, and
+    /// past the grace window if it is the previous scope
+    pub fn is_nullifier_closeable(&self, scope: u64, now: i64) -> bool {
+        scope < self.current_scope
+            && !(scope + 1 == self.current_scope
+                && crate::scope::in_grace_window(self.scope_start_time, self.scope_grace_seconds, now))
+    }
+
+    /// Calendar-aligned scope at `timestamp`, counted from scope_epoch
+    pub fn calendar_scope(&self, timestamp: i64) -> u64 {
+        crate::scope::scope_at(timestamp, self.scope_epoch, self.scope_duration)
+    }
+
+    /// Has the current scope run past its duration? (never, in manual-only mode)
+    pub fn is_scope_expired(&self, now: i64) -> bool {
+        crate::scope::is_scope_expired(self.scope_start_time, self.scope_duration, now)
+    }
+
+    /// Scope a submission with `external_nullifier` counts toward, if any
+    pub fn submission_scope(
+        &self,
+        census_key: &Pubkey,
+        external_nullifier: &[u8; 32],
+        now: i64,
+    ) -> Result<Option<u64>> {
+        let matches_current =
+            self.accepts_external_nullifier(census_key, self.current_scope, external_nullifier)?;
+        let matches_previous = match self.current_scope.checked_sub(1) {
+            Some(previous_scope) => {
+                self.accepts_external_nullifier(census_key, previous_scope, external_nullifier)?
+            }
+            None => false,
+        };
+
+        Ok(crate::scope::submission_scope(
+            self.current_scope,
+            self.scope_start_time,
+            self.scope_grace_seconds,
+            now,
+            matches_current,
+            matches_previous,
+        ))
+    }
+
+    /// How many scopes ahead of current_scope `external_nullifier` was derived
+    /// for, if it is: decoded directly under v0, else probed up to
+    /// FUTURE_SCOPE_LOOKAHEAD scopes ahead. Only worth calling once
+    /// submission_scope has rejected it.
+    pub fn future_scope_offset(
+        &self,
+        census_key: &Pubkey,
+        external_nullifier: &[u8; 32],
+    ) -> Result<Option<u64>> {
+        if self.external_nullifier_version == 0 {
+            let offset = crate::scope::decode_scope(external_nullifier)
+                .and_then(|scope| scope.checked_sub(self.current_scope))
+                .filter(|&offset| offset > 0);
+            return Ok(offset);
+        }
+
+        for offset in 1..=FUTURE_SCOPE_LOOKAHEAD {
+            let Some(scope) = self.current_scope.checked_add(offset) else {
+                break;
+            };
+            let derived = crate::scope::external_nullifier_for(
+                self.external_nullifier_version,
+                scope,
+                census_key,
+            )?;
+            if &derived == external_nullifier {
+                return Ok(Some(offset));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Is `external_nullifier` a valid derivation for `scope`? Scopes up to
+    /// the transition scope also accept the pre-rotation derivation.
+    /// `census_key` is this account's address.
+    pub fn accepts_external_nullifier(
+        &self,
+        census_key: &Pubkey,
+        scope: u64,
+        external_nullifier: &[u8; 32],
+    ) -> Result<bool> {
+        let derive = |version| crate::scope::external_nullifier_for(version, scope, census_key);
+        if external_nullifier == &derive(self.external_nullifier_version)? {
+            return Ok(true);
+        }
+
+        let transitioning = self.external_nullifier_transition_scope != 0
+            && scope <= self.external_nullifier_transition_scope;
+        Ok(transitioning && external_nullifier == &derive(self.previous_external_nullifier_version)?)
+    }
+
+    /// Active entries of the verifier allowlist
+    pub fn verifiers(&self) -> &[Pubkey] {
+        &self.authorized_verifiers[..self.verifier_count as usize]
+    }
+
+    /// Mark every milestone the current population has reached but that
+    /// hasn't fired this scope, returning their thresholds
+    pub fn take_reached_milestones(&mut self) -> Vec<u64> {
+        let mut reached = Vec::new();
+        for (i, &milestone) in self.milestones.iter().enumerate() {
+            let bit = 1u8 << i;
+            if milestone != 0 && self.current_population >= milestone && self.milestones_fired & bit == 0 {
+                self.milestones_fired |= bit;
+                reached.push(milestone);
+            }
+        }
+        reached
+    }
+
+    /// Does register_citizen accept commitments in format `version`?
+    pub fn accepts_commitment_version(&self, version: u8) -> bool {
+        1u32.checked_shl(version.into())
+            .is_some_and(|bit| self.supported_commitment_versions & bit != 0)
+    }
+
+    /// Is `verifier` on the allowlist (deprecated or not)?
+    pub fn is_authorized_verifier(&self, verifier: &Pubkey) -> bool {
+        self.verifiers().contains(verifier)
+    }
+
+    /// Is `verifier` trusted to sign attestations at `now`? A deprecated
+    /// verifier stays trusted until its accepted_until passes
+    pub fn accepts_verifier(&self, verifier: &Pubkey, now: i64) -> bool {
+        self.verifiers().iter().position(|v| v == verifier).is_some_and(|index| {
+            let accepted_until = self.verifier_accepted_until[index];
+            accepted_until == 0 || now < accepted_until
+        })
+    }
+
+    /// Weight `verifier` carries toward `weight_threshold` (0 if not on the allowlist)
+    pub fn verifier_weight(&self, verifier: &Pubkey) -> u16 {
+        self.verifiers()
+            .iter()
+            .position(|v| v == verifier)
+            .map_or(0, |index| self.verifier_weights[index])
+    }
+
+    /// Summed weight of the whole allowlist, the most any attestation can gather
+    pub fn total_verifier_weight(&self) -> u32 {
+        self.verifier_weights[..self.verifier_count as usize]
+            .iter()
+            .map(|&weight| u32::from(weight))
+            .sum()
+    }
 }
 
 // ============================================================================
 // NULLIFIER - Tracks used nullifiers to prevent double-counting
 // ============================================================================
 
+/// Invariant: within a scope an identity has exactly one nullifier_hash
+/// (the circuit derives it from the identity secret and the external
+/// nullifier only, never from the signal), and both submit_census and
+/// submit_attestation key this PDA by it. So one person can't count once
+/// per path, or once per signal.
+///
+/// Fields are only ever appended; accounts created by older program versions
+/// are grown to the current layout by migrate_nullifier.
 #[account]
 #[derive(InitSpace)]
 pub struct Nullifier {
@@ -59,6 +491,24 @@ pub struct Nullifier {
     
     /// Bump seed for PDA derivation
     pub bump: u8,
+    
+    /// Optional app-specific tag written by the submitter (zeroed if unused)
+    pub extra: [u8; NULLIFIER_EXTRA_LEN],
+    
+    /// Nonce of the attestation that recorded this nullifier (zero for proofs)
+    pub attestation_nonce: [u8; ATTESTATION_NONCE_LEN],
+}
+
+impl Nullifier {
+    /// Has this PDA already been written by a previous submission?
+    pub fn is_recorded(&self) -> bool {
+        self.nullifier_hash != [0u8; 32]
+    }
+
+    /// Has a recorded nullifier outlived the TTL, making it countable again?
+    pub fn is_expired(&self, ttl: i64, now: i64) -> bool {
+        ttl > 0 && self.timestamp.saturating_add(ttl) < now
+    }
 }
 
 // ============================================================================
@@ -71,12 +521,165 @@ pub struct CensusAggregate {
     /// Census scope this aggregate belongs to
     pub scope: u64,
     
-    /// Total unique participants
+    /// Submissions counted into the scope since the aggregate was created,
+    /// declined signals included (see process_count)
     pub participant_count: u64,
     
     /// Anonymous attribute counts (e.g., remote workers, builders, etc.)
-    /// Signal values 1-10 map to different attributes
-    pub attribute_counts: [u64; 10],
+    /// Signal values 1-10 map to different attributes; 0 discloses none
+    pub attribute_counts: [u64; ATTRIBUTE_COUNT],
+    
+    /// Timestamp of last update
+    pub last_updated: i64,
+    
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl CensusAggregate {
+    /// Count one participant; `attribute` comes from signal::decode_signal,
+    /// so declined signals count toward participants only. An attribute slot
+    /// can never outnumber participants; if one does, the aggregate is
+    /// corrupt and the count fails
+    pub fn record(&mut self, attribute: Option<u8>, now: i64) -> Result<()> {
+        self.participant_count = self.participant_count
+            .checked_add(1)
+            .ok_or(crate::error::CensusError::ArithmeticOverflow)?;
+        if let Some(index) = attribute {
+            let count = &mut self.attribute_counts[index as usize];
+            *count = count.checked_add(1).ok_or(crate::error::CensusError::ArithmeticOverflow)?;
+            require!(
+                *count <= self.participant_count,
+                crate::error::CensusError::AggregateInvariantViolated
+            );
+        }
+        self.last_updated = now;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// NULLIFIER BITMAP - Bloom filter of a scope's counted nullifiers
+// ============================================================================
+
+/// Every count into the scope sets NULLIFIER_BITMAP_HASHES bits, so one read
+/// answers "was this nullifier counted?" for many nullifiers. A clear bit
+/// means definitely not counted; all bits set means probably counted. False
+/// positives grow with the count, about 0.5% at 500 and 3% at 1,000, so
+/// confirm a positive against the Nullifier PDA when it matters.
+///
+/// Only the latest bitmap scope (CensusState::nullifier_bitmap_scope) is
+/// enforced: once a newer scope has a bitmap, grace-window counts into the
+/// older one may skip it.
+#[account]
+#[derive(InitSpace)]
+pub struct NullifierBitmap {
+    /// Census scope this bitmap belongs to
+    pub scope: u64,
+    
+    /// Bloom filter bits, bit i at bits[i / 8] & (1 << (i % 8))
+    pub bits: [u8; NULLIFIER_BITMAP_BYTES],
+    
+    /// Nullifiers inserted so far
+    pub inserted: u64,
+    
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl NullifierBitmap {
+    /// Bit positions for `nullifier_hash`: the first NULLIFIER_BITMAP_HASHES
+    /// little-endian u32 words of sha256(nullifier_hash), mod the bit count
+    pub fn positions(nullifier_hash: &[u8; 32]) -> [usize; NULLIFIER_BITMAP_HASHES] {
+        let digest = solana_sha256_hasher::hash(nullifier_hash).to_bytes();
+        let mut positions = [0; NULLIFIER_BITMAP_HASHES];
+        for (position, word) in positions.iter_mut().zip(digest.chunks_exact(4)) {
+            let word = u32::from_le_bytes(word.try_into().unwrap()) as usize;
+            *position = word % (NULLIFIER_BITMAP_BYTES * 8);
+        }
+        positions
+    }
+
+    pub fn insert(&mut self, nullifier_hash: &[u8; 32]) -> Result<()> {
+        for position in Self::positions(nullifier_hash) {
+            self.bits[position / 8] |= 1 << (position % 8);
+        }
+        self.inserted = self.inserted
+            .checked_add(1)
+            .ok_or(crate::error::CensusError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// False: never counted. True: probably counted (see the type's doc)
+    pub fn may_contain(&self, nullifier_hash: &[u8; 32]) -> bool {
+        Self::positions(nullifier_hash)
+            .iter()
+            .all(|&position| self.bits[position / 8] & (1 << (position % 8)) != 0)
+    }
+}
+
+// ============================================================================
+// NUMERIC AGGREGATE - Histogram of a numeric attribute (e.g. age)
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct NumericAggregate {
+    /// Census scope this histogram belongs to
+    pub scope: u64,
+    
+    /// Inclusive lower bound of each bucket, ascending
+    /// Bucket i covers [lower_bounds[i], lower_bounds[i + 1])
+    pub lower_bounds: [u64; NUMERIC_BUCKET_COUNT],
+    
+    /// Submissions whose proof placed the value in each bucket
+    pub bucket_counts: [u64; NUMERIC_BUCKET_COUNT],
+    
+    /// Timestamp of last update
+    pub last_updated: i64,
+    
+    /// Bump seed
+    pub bump: u8,
+}
+
+// ============================================================================
+// ATTRIBUTE SCHEMA - Human-readable labels for CensusAggregate slots
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct AttributeSchema {
+    /// ASCII label for each attribute slot, zero-padded
+    /// labels[i] describes CensusAggregate.attribute_counts[i]
+    pub labels: [[u8; ATTRIBUTE_LABEL_LEN]; ATTRIBUTE_COUNT],
+    
+    /// Timestamp of last update
+    pub last_updated: i64,
+    
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl AttributeSchema {
+    /// Non-empty printable ASCII (letters, digits, punctuation, spaces), then
+    /// only zero padding: no control bytes, no garbage after a NUL
+    pub fn is_valid_label(label: &[u8; ATTRIBUTE_LABEL_LEN]) -> bool {
+        let len = label.iter().position(|&b| b == 0).unwrap_or(ATTRIBUTE_LABEL_LEN);
+        len > 0
+            && label[..len].iter().all(|&b| b.is_ascii_graphic() || b == b' ')
+            && label[len..].iter().all(|&b| b == 0)
+    }
+}
+
+// ============================================================================
+// CENSUS METADATA - Optional pointer to off-chain census context
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct CensusMetadata {
+    /// ASCII URI of off-chain JSON (question, description, terms), zero-padded
+    pub uri: [u8; METADATA_URI_LEN],
     
     /// Timestamp of last update
     pub last_updated: i64,
@@ -85,6 +688,226 @@ pub struct CensusAggregate {
     pub bump: u8,
 }
 
+// ============================================================================
+// SCOPE SNAPSHOT - Final tally of a closed scope
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct ScopeSnapshot {
+    /// Scope this snapshot closes
+    pub scope: u64,
+    
+    /// Population counted when the scope was closed
+    pub final_population: u64,
+    
+    /// Registered citizens when the scope was closed
+    pub total_registered_at_close: u64,
+    
+    /// Timestamp the scope was closed
+    pub closed_at: i64,
+    
+    /// Bump seed
+    pub bump: u8,
+    
+    /// Timestamp the scope opened
+    pub opened_at: i64,
+    
+    /// Merkle root in effect when the scope was closed
+    pub closing_merkle_root: [u8; 32],
+    
+    /// Final running_nullifier_accumulator, extended by grace-window counts
+    pub nullifier_accumulator: [u8; 32],
+}
+
+/// Outcome of dry_run_attestation; `error_code` is the error submit_attestation
+/// would fail with (0 when it would pass)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AttestationDryRun {
+    pub passed: bool,
+    pub error_code: u32,
+    pub counted_scope: u64,
+}
+
+/// Identity leaf an attestation counts, proven against the linked tree under
+/// `strict_membership`. Proof nodes not covered by the canopy are passed as
+/// remaining accounts, leaf level first
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MembershipProof {
+    pub leaf: [u8; 32],
+    pub leaf_index: u32,
+}
+
+/// Outcome of batch_close_nullifiers
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct NullifierSweep {
+    pub closed: u32,
+    pub skipped: u32,
+    pub lamports_reclaimed: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ScopeExpiry {
+    pub is_expired: bool,
+    pub seconds_until_expiry: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ScopeBoundary {
+    pub current_scope: u64,
+    pub next_boundary: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerifierList {
+    pub verifiers: Vec<Pubkey>,
+    pub verifier_threshold: u8,
+    pub verifier_weights: Vec<u16>,
+    pub weight_threshold: u16,
+}
+
+/// Verification key submit_census checks proofs against, with its hash
+/// (groth16::VERIFICATION_KEY_HASH)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct VerificationKeyExport {
+    pub alpha: [u8; 64],
+    pub beta: [u8; 128],
+    pub gamma: [u8; 128],
+    pub delta: [u8; 128],
+    pub ic: Vec<[u8; 64]>,
+    pub hash: [u8; 32],
+}
+
+/// Returned by advance_scope / try_advance_scope so keepers can chain
+/// follow-up actions without re-reading CensusState
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ScopeAdvanceResult {
+    pub new_scope: u64,
+    pub scope_start_time: i64,
+    pub final_population: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ScopeSummary {
+    pub final_population: u64,
+    pub closed_at: i64,
+    pub total_registered_at_close: u64,
+    pub opened_at: i64,
+    pub closing_merkle_root: [u8; 32],
+    pub nullifier_accumulator: [u8; 32],
+}
+
+/// A scope's CensusAggregate, as returned by read_aggregate. Slot i of
+/// `attribute_counts` is labeled by AttributeSchema.labels[i]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct AggregateSummary {
+    pub scope: u64,
+    pub participant_count: u64,
+    pub attribute_counts: [u64; ATTRIBUTE_COUNT],
+    pub last_updated: i64,
+}
+
+// ============================================================================
+// VERIFIER REGISTRY - Growable verifier allowlist for large verifier sets
+// ============================================================================
+
+/// Resized by one key per add/remove, so the set is bounded only by account
+/// size rather than MAX_VERIFIERS
+#[account]
+pub struct VerifierRegistry {
+    /// Bump seed
+    pub bump: u8,
+    
+    /// Keys trusted to sign attestations while the registry is linked
+    pub verifiers: Vec<Pubkey>,
+}
+
+impl VerifierRegistry {
+    /// Account size (with discriminator) holding `verifier_count` keys
+    pub fn space(verifier_count: usize) -> usize {
+        8 + 1 + 4 + 32 * verifier_count
+    }
+
+    pub fn contains(&self, verifier: &Pubkey) -> bool {
+        self.verifiers.contains(verifier)
+    }
+}
+
+// ============================================================================
+// VERIFIER STATS - Per-verifier liveness for monitoring
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct VerifierStats {
+    /// Verifier these stats belong to
+    pub verifier: Pubkey,
+    
+    /// Timestamp of the verifier's last heartbeat
+    pub last_seen: i64,
+    
+    /// Bump seed
+    pub bump: u8,
+}
+
+// ============================================================================
+// PAYER COOLDOWN - Per-payer (relayer) submission throttle
+// ============================================================================
+
+#[account]
+#[derive(InitSpace)]
+pub struct PayerCooldown {
+    /// Timestamp of the payer's last counted submission
+    pub last_count_at: i64,
+    
+    /// Bump seed
+    pub bump: u8,
+}
+
+// ============================================================================
+// CITIZEN RECORD - Per-registration expiry for time-limited citizenship
+// ============================================================================
+
+/// Created by register_citizen while `membership_duration > 0`. Only strict
+/// submissions reveal the leaf they count, so only they can check it; an
+/// expired leaf stays in the tree until replace_commitment sweeps it
+#[account]
+#[derive(InitSpace)]
+pub struct CitizenRecord {
+    /// The registered leaf
+    pub identity_commitment: [u8; 32],
+    
+    /// When the membership lapses (0 = never)
+    pub expires_at: i64,
+    
+    /// Bump seed
+    pub bump: u8,
+    
+    /// SDK format the commitment was built with (see register_citizen)
+    pub commitment_version: u8,
+}
+
+impl CitizenRecord {
+    /// Expired from `expires_at` on; 0 never expires
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expires_at != 0 && now >= self.expires_at
+    }
+}
+
+// ============================================================================
+// CENSUS STATS - Returned by get_census_stats
+// ============================================================================
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CensusStats {
+    pub current_scope: u64,
+    pub current_population: u64,
+    pub total_registered: u64,
+    pub counted_by_proof: u64,
+    pub counted_by_attestation: u64,
+    pub is_active: bool,
+}
+
 // ============================================================================
 // EVENTS - Emitted for off-chain indexing
 // ============================================================================
@@ -92,7 +915,11 @@ pub struct CensusAggregate {
 #[event]
 pub struct CitizenRegistered {
     pub commitment: [u8; 32],
+    pub commitment_version: u8,
     pub leaf_index: u64,
+    /// Tree root after this append, on checkpoint leaves only
+    /// (see `root_checkpoint_interval`)
+    pub new_root: Option<[u8; 32]>,
     pub timestamp: i64,
 }
 
@@ -101,6 +928,59 @@ pub struct CensusCounted {
     pub nullifier_hash: [u8; 32],
     pub scope: u64,
     pub new_population: u64,
+    pub seq: u64,
+    pub timestamp: i64,
+}
+
+/// A Groth16 proof passed verification in submit_census. Failures revert
+/// (and log their reason), so `success` is true on every emitted event.
+#[event]
+pub struct ProofVerified {
+    pub success: bool,
+    pub scope: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CensusAttested {
+    pub nullifier_hash: [u8; 32],
+    pub scope: u64,
+    pub verifier: Pubkey,
+    pub seq: u64,
+    pub timestamp: i64,
+}
+
+/// A count was paid for by `payer` (the relayer, or the citizen submitting
+/// directly), for rewards programs to index. Opt-in via
+/// `emit_participation_events`: the payer is visible on the transaction
+/// anyway, but this makes linking payers to scopes a one-event lookup
+#[event]
+pub struct ParticipationRecorded {
+    pub payer: Pubkey,
+    pub scope: u64,
+    pub timestamp: i64,
+}
+
+/// One count toward an attribute slot (see signal.rs), for off-chain tallies
+#[event]
+pub struct SignalObserved {
+    pub scope: u64,
+    pub attribute_index: u8,
+    pub timestamp: i64,
+}
+
+/// A counted submission's signal, verbatim, under SIGNAL_MODE_OPAQUE
+#[event]
+pub struct SignalCommitted {
+    pub scope: u64,
+    pub signal_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PopulationMilestone {
+    pub scope: u64,
+    pub milestone: u64,
     pub timestamp: i64,
 }
 
@@ -111,3 +991,117 @@ pub struct ScopeAdvanced {
     pub final_population: u64,
     pub timestamp: i64,
 }
+
+#[event]
+pub struct MerkleTreeMigrated {
+    pub old_tree: Pubkey,
+    pub new_tree: Pubkey,
+    pub new_root: [u8; 32],
+    pub leaf_count: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct TreeAuthorityTransferred {
+    pub tree: Pubkey,
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CommitmentReplaced {
+    pub leaf_index: u64,
+    pub old_commitment: [u8; 32],
+    pub new_commitment: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierAdded {
+    pub verifier: Pubkey,
+    pub verifier_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierRemoved {
+    pub verifier: Pubkey,
+    pub verifier_count: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RegistryVerifierAdded {
+    pub verifier: Pubkey,
+    pub verifier_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RegistryVerifierRemoved {
+    pub verifier: Pubkey,
+    pub verifier_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierDeprecated {
+    pub verifier: Pubkey,
+    pub accepted_until: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierRotated {
+    pub old: Pubkey,
+    pub new: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct StateMigrated {
+    pub from_version: u8,
+    pub to_version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VerifierHeartbeat {
+    pub verifier: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Manual correction of current_population by the admin (never an organic count)
+#[event]
+pub struct PopulationAdjusted {
+    pub old: u64,
+    pub new: u64,
+    pub reason_code: u8,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ExternalNullifierRotated {
+    pub old_version: u8,
+    pub new_version: u8,
+    pub transition_scope: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct GenesisImported {
+    pub root: [u8; 32],
+    pub leaf_count: u64,
+    pub total_registered: u64,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MembershipRenewed {
+    pub identity_commitment: [u8; 32],
+    pub expires_at: i64,
+    pub timestamp: i64,
+}
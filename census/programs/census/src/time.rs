@@ -0,0 +1,16 @@
+//! Shared time-window checks
+//!
+//! Timestamps produced off-chain (signed attestation times, proven
+//! participation times) go through `within_window`, so clock-skew handling
+//! is uniform. Timestamps the program stored itself (scope starts, nullifier
+//! records) come from the cluster clock and are compared without skew.
+
+use crate::constants::MAX_CLOCK_SKEW;
+
+/// Is `now` within `window` seconds after the off-chain `target`?
+/// Half-open, `[target, target + window)`, tolerating `target` up to
+/// MAX_CLOCK_SKEW seconds ahead of `now`. Saturates instead of overflowing
+/// on extreme values.
+pub fn within_window(now: i64, target: i64, window: i64) -> bool {
+    now >= target.saturating_sub(MAX_CLOCK_SKEW) && now < target.saturating_add(window)
+}
@@ -0,0 +1,238 @@
+//! Access to the SPL Concurrent Merkle Tree account
+//!
+//! The account is parsed by offset rather than through spl-account-compression
+//! types, which are built against an older anchor-lang. This also lets us read
+//! trees of any depth/buffer size, not just the ones in `constants.rs`. For
+//! the same reason, instructions to the compression program are built by hand.
+//!
+//! Layout: [header (56 bytes)][ConcurrentMerkleTree<DEPTH, BUFFER>][canopy]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use crate::{
+    constants::{MAX_LEAVES, SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID},
+    error::CensusError,
+};
+
+/// Size of the V1 ConcurrentMerkleTreeHeader
+const HEADER_SIZE: usize = 56;
+
+/// CompressionAccountType::ConcurrentMerkleTree
+const ACCOUNT_TYPE_TREE: u8 = 1;
+
+/// ConcurrentMerkleTreeHeaderData::V1
+const HEADER_VERSION_V1: u8 = 0;
+
+/// Anchor discriminator of spl-account-compression's `transfer_authority`
+const TRANSFER_AUTHORITY_DISCRIMINATOR: [u8; 8] = [48, 169, 76, 72, 229, 180, 55, 161];
+
+/// Anchor discriminator of spl-account-compression's `replace_leaf`
+const REPLACE_LEAF_DISCRIMINATOR: [u8; 8] = [204, 165, 76, 100, 73, 147, 0, 128];
+
+/// Anchor discriminator of spl-account-compression's `append`
+const APPEND_DISCRIMINATOR: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+
+/// Anchor discriminator of spl-account-compression's `verify_leaf`
+const VERIFY_LEAF_DISCRIMINATOR: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
+/// Fields of the tree account that the census program cares about
+pub struct TreeSnapshot {
+    pub max_depth: u32,
+    pub max_buffer_size: u32,
+    pub authority: Pubkey,
+    pub root: [u8; 32],
+    /// Number of leaves appended so far (= index of the next leaf)
+    pub leaf_count: u64,
+}
+
+/// Parse the header and current root/leaf count of a tree account
+pub fn read_tree(tree: &AccountInfo) -> Result<TreeSnapshot> {
+    require!(
+        tree.owner == &SPL_ACCOUNT_COMPRESSION_ID,
+        CensusError::InvalidMerkleTree
+    );
+    let data = tree.try_borrow_data()?;
+    parse_tree_data(&data)
+}
+
+/// Parse raw tree account data. Sizes come from the account itself, so all
+/// offset math is checked: a corrupt header fails with InvalidMerkleTree
+/// instead of overflowing.
+pub fn parse_tree_data(data: &[u8]) -> Result<TreeSnapshot> {
+    require!(data.len() >= HEADER_SIZE, CensusError::InvalidMerkleTree);
+    require!(
+        data[0] == ACCOUNT_TYPE_TREE && data[1] == HEADER_VERSION_V1,
+        CensusError::InvalidMerkleTree
+    );
+
+    // Header: account_type u8, version u8, max_buffer_size u32, max_depth u32,
+    //         authority Pubkey, creation_slot u64, is_batch_initialized bool, pad
+    let max_buffer_size = read_u32(data, 2)?;
+    let max_depth = read_u32(data, 6)?;
+    let authority = Pubkey::try_from(&data[10..42])
+        .map_err(|_| error!(CensusError::InvalidMerkleTree))?;
+
+    // Tree: sequence_number u64, active_index u64, buffer_size u64,
+    //       change_logs [ChangeLog; BUFFER], rightmost_proof Path
+    // ChangeLog = root [u8;32], path [[u8;32]; DEPTH], index u32, pad u32
+    // Path      = proof [[u8;32]; DEPTH], leaf [u8;32], index u32, pad u32
+    let depth = max_depth as usize;
+    let path_size = depth.checked_mul(32).ok_or(CensusError::InvalidMerkleTree)?;
+    let change_log_size = path_size.checked_add(32 + 8).ok_or(CensusError::InvalidMerkleTree)?;
+    let tree_start = HEADER_SIZE;
+    let active_index = usize::try_from(read_u64(data, tree_start + 8)?)
+        .map_err(|_| error!(CensusError::InvalidMerkleTree))?;
+    require!(
+        active_index < max_buffer_size as usize,
+        CensusError::InvalidMerkleTree
+    );
+
+    let change_logs_start = tree_start + 24;
+    let offset_of = |index: usize, extra: usize| {
+        index
+            .checked_mul(change_log_size)
+            .and_then(|o| o.checked_add(change_logs_start))
+            .and_then(|o| o.checked_add(extra))
+            .ok_or_else(|| error!(CensusError::InvalidMerkleTree))
+    };
+    let root: [u8; 32] = read_bytes(data, offset_of(active_index, 0)?, 32)?
+        .try_into()
+        .unwrap();
+
+    let leaf_count_offset = offset_of(max_buffer_size as usize, path_size)?
+        .checked_add(32)
+        .ok_or(CensusError::InvalidMerkleTree)?;
+    let leaf_count = read_u32(data, leaf_count_offset)? as u64;
+
+    Ok(TreeSnapshot {
+        max_depth,
+        max_buffer_size,
+        authority,
+        root,
+        leaf_count,
+    })
+}
+
+/// Leaves still free in a TREE_DEPTH tree holding `leaf_count`; 0 (not an
+/// underflow) if the count somehow exceeds the capacity
+pub fn remaining_capacity(leaf_count: u64) -> u64 {
+    MAX_LEAVES.saturating_sub(leaf_count)
+}
+
+/// Index of the leaf register_citizen just appended, given the linked
+/// tree's leaf count after the append and the census's `leaf_count` before
+/// it. Exactly one new leaf keeps emitted indices gapless and
+/// strictly increasing; any other count (no append, a second or replayed
+/// append, an unregistered one) is a LeafIndexMismatch
+pub fn appended_leaf_index(tree_leaf_count: u64, census_leaf_count: u64) -> Result<u64> {
+    require!(
+        census_leaf_count.checked_add(1) == Some(tree_leaf_count),
+        CensusError::LeafIndexMismatch
+    );
+    Ok(census_leaf_count)
+}
+
+/// Does the append that brings the tree to `leaf_count` leaves checkpoint
+/// its root? Every `interval`-th leaf does; an interval of 0 never does
+pub fn is_root_checkpoint(leaf_count: u64, interval: u64) -> bool {
+    interval != 0 && leaf_count.is_multiple_of(interval)
+}
+
+fn read_bytes(data: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| data.get(offset..end))
+        .ok_or_else(|| error!(CensusError::InvalidMerkleTree))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(read_bytes(data, offset, 4)?.try_into().unwrap()))
+}
+
+fn read_u64(data: &[u8], offset: usize) -> Result<u64> {
+    Ok(u64::from_le_bytes(read_bytes(data, offset, 8)?.try_into().unwrap()))
+}
+
+/// spl-account-compression `transfer_authority`: hands `tree` from
+/// `authority` (which must sign) to `new_authority`
+pub fn transfer_authority_ix(tree: &Pubkey, authority: &Pubkey, new_authority: &Pubkey) -> Instruction {
+    let mut data = TRANSFER_AUTHORITY_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(new_authority.as_ref());
+    Instruction {
+        program_id: SPL_ACCOUNT_COMPRESSION_ID,
+        accounts: vec![
+            AccountMeta::new(*tree, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+        data,
+    }
+}
+
+/// spl-account-compression `append`: adds `leaf` as the tree's next leaf.
+/// `authority` (the tree's) must sign
+pub fn append_ix(tree: &Pubkey, authority: &Pubkey, leaf: &[u8; 32]) -> Instruction {
+    let mut data = APPEND_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(leaf);
+    Instruction {
+        program_id: SPL_ACCOUNT_COMPRESSION_ID,
+        accounts: vec![
+            AccountMeta::new(*tree, false),
+            AccountMeta::new_readonly(*authority, true),
+            AccountMeta::new_readonly(SPL_NOOP_ID, false),
+        ],
+        data,
+    }
+}
+
+/// spl-account-compression `verify_leaf`: fails unless `leaf` sits at
+/// `index` under `root`, which must be a root in the tree's change log.
+/// `proof` holds the nodes the canopy doesn't, leaf level first
+pub fn verify_leaf_ix(
+    tree: &Pubkey,
+    root: &[u8; 32],
+    leaf: &[u8; 32],
+    index: u32,
+    proof: &[Pubkey],
+) -> Instruction {
+    let mut data = VERIFY_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(root);
+    data.extend_from_slice(leaf);
+    data.extend_from_slice(&index.to_le_bytes());
+    let mut accounts = vec![AccountMeta::new_readonly(*tree, false)];
+    accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(*node, false)));
+    Instruction {
+        program_id: SPL_ACCOUNT_COMPRESSION_ID,
+        accounts,
+        data,
+    }
+}
+
+/// spl-account-compression `replace_leaf`: swaps `previous_leaf` at `index`
+/// for `new_leaf`, proven under `root`. `authority` (the tree's) must sign;
+/// `proof` holds the nodes the canopy doesn't, leaf level first
+pub fn replace_leaf_ix(
+    tree: &Pubkey,
+    authority: &Pubkey,
+    root: &[u8; 32],
+    previous_leaf: &[u8; 32],
+    new_leaf: &[u8; 32],
+    index: u32,
+    proof: &[Pubkey],
+) -> Instruction {
+    let mut data = REPLACE_LEAF_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(root);
+    data.extend_from_slice(previous_leaf);
+    data.extend_from_slice(new_leaf);
+    data.extend_from_slice(&index.to_le_bytes());
+    let mut accounts = vec![
+        AccountMeta::new(*tree, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(SPL_NOOP_ID, false),
+    ];
+    accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(*node, false)));
+    Instruction {
+        program_id: SPL_ACCOUNT_COMPRESSION_ID,
+        accounts,
+        data,
+    }
+}
@@ -0,0 +1,97 @@
+use anchor_lang::prelude::Pubkey;
+use census::attestation::{
+    build_attestation_message, AttestationFields, ATTESTATION_BOUND_MESSAGE_LEN,
+    ATTESTATION_MEMBERSHIP_LEN, ATTESTATION_MESSAGE_LEN,
+};
+use census::state::MembershipProof;
+
+#[test]
+fn message_matches_test_vector() {
+    let message = build_attestation_message(
+        0x0102_0304_0506_0708,
+        &[0x11; 16],
+        &[0xaa; 32],
+        &[0xbb; 32],
+        &[0xcc; 32],
+        &[0xdd; 32],
+        &[0xee; 32],
+        None,
+        None,
+    );
+
+    let mut expected = vec![0x04, 0x08, 0x07, 0x06, 0x05, 0x04, 0x03, 0x02, 0x01];
+    expected.extend_from_slice(&[0x11; 16]);
+    for byte in [0xaa, 0xbb, 0xcc, 0xdd, 0xee] {
+        expected.extend_from_slice(&[byte; 32]);
+    }
+
+    assert_eq!(message.len(), ATTESTATION_MESSAGE_LEN);
+    assert_eq!(message, expected);
+}
+
+#[test]
+fn bound_payer_is_appended() {
+    let payer = Pubkey::new_from_array([0xee; 32]);
+    let unbound = build_attestation_message(1, &[0; 16], &[1; 32], &[2; 32], &[3; 32], &[4; 32], &[5; 32], None, None);
+    let bound = build_attestation_message(
+        1,
+        &[0; 16],
+        &[1; 32],
+        &[2; 32],
+        &[3; 32],
+        &[4; 32],
+        &[5; 32],
+        Some(&payer),
+        None,
+    );
+
+    assert_eq!(bound.len(), ATTESTATION_MESSAGE_LEN + 32);
+    assert_eq!(&bound[..ATTESTATION_MESSAGE_LEN], &unbound[..]);
+    assert_eq!(&bound[ATTESTATION_MESSAGE_LEN..], payer.as_ref());
+}
+
+#[test]
+fn message_length_matches_wire_constants() {
+    let mut fields = AttestationFields {
+        timestamp: -1,
+        nonce: [0xff; 16],
+        merkle_root: [1; 32],
+        nullifier_hash: [2; 32],
+        external_nullifier: [3; 32],
+        signal_hash: [4; 32],
+        circuit_hash: [5; 32],
+        bound_payer: None,
+        membership: None,
+    };
+    assert_eq!(fields.message().len(), ATTESTATION_MESSAGE_LEN);
+
+    fields.bound_payer = Some(Pubkey::new_from_array([6; 32]));
+    assert_eq!(fields.message().len(), ATTESTATION_BOUND_MESSAGE_LEN);
+
+    fields.membership = Some(MembershipProof { leaf: [7; 32], leaf_index: 8 });
+    assert_eq!(
+        fields.message().len(),
+        ATTESTATION_BOUND_MESSAGE_LEN + ATTESTATION_MEMBERSHIP_LEN
+    );
+}
+
+#[test]
+fn membership_is_appended_last() {
+    let membership = MembershipProof { leaf: [0x77; 32], leaf_index: 0x0102_0304 };
+    let plain = build_attestation_message(1, &[0; 16], &[1; 32], &[2; 32], &[3; 32], &[4; 32], &[5; 32], None, None);
+    let bound = build_attestation_message(
+        1,
+        &[0; 16],
+        &[1; 32],
+        &[2; 32],
+        &[3; 32],
+        &[4; 32],
+        &[5; 32],
+        None,
+        Some(&membership),
+    );
+
+    assert_eq!(&bound[..ATTESTATION_MESSAGE_LEN], &plain[..]);
+    assert_eq!(&bound[ATTESTATION_MESSAGE_LEN..ATTESTATION_MESSAGE_LEN + 32], &[0x77; 32]);
+    assert_eq!(&bound[ATTESTATION_MESSAGE_LEN + 32..], &[0x04, 0x03, 0x02, 0x01]);
+}
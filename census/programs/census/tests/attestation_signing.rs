@@ -0,0 +1,77 @@
+#![cfg(feature = "std")]
+
+use census::attestation::{sign_attestation, verify_attestation, AttestationFields};
+use ed25519_dalek::{Keypair, PublicKey, SecretKey};
+
+fn keypair_from_seed(seed: u8) -> Keypair {
+    let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+    let public = PublicKey::from(&secret);
+    Keypair { secret, public }
+}
+
+fn fields() -> AttestationFields {
+    AttestationFields {
+        timestamp: 1_700_000_000,
+        nonce: [0x11; 16],
+        merkle_root: [0xaa; 32],
+        nullifier_hash: [0xbb; 32],
+        external_nullifier: [0xcc; 32],
+        signal_hash: [0xdd; 32],
+        circuit_hash: [0xee; 32],
+        bound_payer: None,
+        membership: None,
+    }
+}
+
+#[test]
+fn signed_attestation_verifies() {
+    let keypair = keypair_from_seed(7);
+    let pubkey = keypair.public.to_bytes();
+    let signature = sign_attestation(&keypair, &fields());
+
+    assert!(verify_attestation(&pubkey, &fields(), &signature));
+}
+
+#[test]
+fn altered_fields_or_signer_fail() {
+    let keypair = keypair_from_seed(7);
+    let signature = sign_attestation(&keypair, &fields());
+
+    let mut altered = fields();
+    altered.nonce[0] ^= 1;
+    assert!(!verify_attestation(&keypair.public.to_bytes(), &altered, &signature));
+
+    // Signed for another circuit
+    let mut other_circuit = fields();
+    other_circuit.circuit_hash[0] ^= 1;
+    assert!(!verify_attestation(&keypair.public.to_bytes(), &other_circuit, &signature));
+
+    let other = keypair_from_seed(8).public.to_bytes();
+    assert!(!verify_attestation(&other, &fields(), &signature));
+}
+
+#[test]
+fn payer_binding_is_signed() {
+    let keypair = keypair_from_seed(7);
+    let mut bound = fields();
+    bound.bound_payer = Some(anchor_lang::prelude::Pubkey::new_from_array([9; 32]));
+    let signature = sign_attestation(&keypair, &bound);
+
+    assert!(verify_attestation(&keypair.public.to_bytes(), &bound, &signature));
+    assert!(!verify_attestation(&keypair.public.to_bytes(), &fields(), &signature));
+}
+
+#[test]
+fn membership_binding_is_signed() {
+    let keypair = keypair_from_seed(7);
+    let mut bound = fields();
+    bound.membership = Some(census::state::MembershipProof { leaf: [9; 32], leaf_index: 3 });
+    let signature = sign_attestation(&keypair, &bound);
+
+    assert!(verify_attestation(&keypair.public.to_bytes(), &bound, &signature));
+
+    // The same attestation paired with another public leaf fails
+    let mut other_leaf = bound.clone();
+    other_leaf.membership = Some(census::state::MembershipProof { leaf: [9; 32], leaf_index: 4 });
+    assert!(!verify_attestation(&keypair.public.to_bytes(), &other_leaf, &signature));
+}
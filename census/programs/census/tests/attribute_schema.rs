@@ -0,0 +1,23 @@
+use census::constants::ATTRIBUTE_LABEL_LEN;
+use census::state::AttributeSchema;
+
+fn label(bytes: &[u8]) -> [u8; ATTRIBUTE_LABEL_LEN] {
+    let mut label = [0u8; ATTRIBUTE_LABEL_LEN];
+    label[..bytes.len()].copy_from_slice(bytes);
+    label
+}
+
+#[test]
+fn printable_zero_padded_labels_are_valid() {
+    assert!(AttributeSchema::is_valid_label(&label(b"Age 18-25")));
+    assert!(AttributeSchema::is_valid_label(&[b'x'; ATTRIBUTE_LABEL_LEN]));
+}
+
+#[test]
+fn malformed_labels_are_rejected() {
+    assert!(!AttributeSchema::is_valid_label(&label(b"")));
+    assert!(!AttributeSchema::is_valid_label(&label(b"Age\n")));
+    assert!(!AttributeSchema::is_valid_label(&label(b"\x7fAge")));
+    assert!(!AttributeSchema::is_valid_label(&label(b"Age\0junk")));
+    assert!(!AttributeSchema::is_valid_label(&label("Âge".as_bytes())));
+}
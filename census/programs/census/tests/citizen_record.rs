@@ -0,0 +1,23 @@
+use census::state::CitizenRecord;
+
+fn expiring_at(expires_at: i64) -> CitizenRecord {
+    CitizenRecord {
+        identity_commitment: [7; 32],
+        expires_at,
+        bump: 0,
+        commitment_version: 1,
+    }
+}
+
+#[test]
+fn membership_lapses_at_its_expiry() {
+    let record = expiring_at(1_000);
+
+    assert!(!record.is_expired(999));
+    assert!(record.is_expired(1_000));
+}
+
+#[test]
+fn zero_expiry_never_lapses() {
+    assert!(!expiring_at(0).is_expired(i64::MAX));
+}
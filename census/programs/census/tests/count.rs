@@ -0,0 +1,163 @@
+use anchor_lang::error::Error;
+use anchor_lang::prelude::{AnchorDeserialize, Pubkey};
+use anchor_lang::Space;
+use census::constants::{ATTESTATION_NONCE_LEN, ATTRIBUTE_COUNT, NULLIFIER_EXTRA_LEN};
+use census::error::CensusError;
+use census::instructions::submit_census::{
+    find_duplicate_nullifier, process_count, Count, CountAccounts, CountSource,
+};
+use census::signal::SIGNAL_DECLINED;
+use census::state::{CensusAggregate, CensusState, Nullifier};
+
+fn census_state() -> CensusState {
+    let mut census_state =
+        CensusState::deserialize(&mut &vec![0u8; CensusState::INIT_SPACE][..]).unwrap();
+    census_state.current_scope = 1;
+    census_state.aggregate_scope = 1;
+    census_state
+}
+
+fn aggregate(scope: u64) -> CensusAggregate {
+    CensusAggregate {
+        scope,
+        participant_count: 0,
+        attribute_counts: [0; ATTRIBUTE_COUNT],
+        last_updated: 0,
+        bump: 0,
+    }
+}
+
+fn nullifier() -> Nullifier {
+    Nullifier {
+        nullifier_hash: [0; 32],
+        scope: 0,
+        timestamp: 0,
+        bump: 0,
+        extra: [0; NULLIFIER_EXTRA_LEN],
+        attestation_nonce: [0; ATTESTATION_NONCE_LEN],
+    }
+}
+
+/// An attestation count into scope 1 with the given signal value
+fn count(nullifier_byte: u8, signal: u8) -> Count {
+    let mut signal_hash = [0u8; 32];
+    signal_hash[31] = signal;
+    Count {
+        nullifier_hash: [nullifier_byte; 32],
+        scope: 1,
+        signal_hash,
+        extra: [0; NULLIFIER_EXTRA_LEN],
+        attestation_nonce: [nullifier_byte; ATTESTATION_NONCE_LEN],
+        bucket_index: None,
+        source: CountSource::Attestation,
+        payer: Pubkey::new_unique(),
+    }
+}
+
+fn accounts<'a>(
+    nullifier: &'a mut Nullifier,
+    aggregate: Option<&'a mut CensusAggregate>,
+) -> CountAccounts<'a> {
+    CountAccounts {
+        nullifier,
+        nullifier_bump: 0,
+        previous_scope_snapshot: None,
+        payer_cooldown: None,
+        payer_cooldown_bump: None,
+        numeric_aggregate: None,
+        nullifier_bitmap: None,
+        aggregate,
+    }
+}
+
+#[test]
+fn counted_submission_updates_its_aggregate() {
+    let mut census_state = census_state();
+    let mut aggregate = aggregate(1);
+
+    let population = process_count(
+        &mut census_state,
+        accounts(&mut nullifier(), Some(&mut aggregate)),
+        &count(1, 3),
+        50,
+    )
+    .unwrap();
+
+    assert_eq!(population, 1);
+    assert_eq!(aggregate.participant_count, 1);
+    assert_eq!(aggregate.attribute_counts[2], 1);
+    assert_eq!(aggregate.attribute_counts.iter().sum::<u64>(), 1);
+    assert_eq!(aggregate.last_updated, 50);
+}
+
+#[test]
+fn aggregated_scope_rejects_counts_without_its_aggregate() {
+    let mut census_state = census_state();
+    let expected = Error::from(CensusError::InvalidAggregate);
+
+    assert_eq!(
+        process_count(&mut census_state, accounts(&mut nullifier(), None), &count(1, 3), 50)
+            .unwrap_err(),
+        expected
+    );
+    let mut stale = aggregate(0);
+    assert_eq!(
+        process_count(
+            &mut census_state,
+            accounts(&mut nullifier(), Some(&mut stale)),
+            &count(1, 3),
+            50,
+        )
+        .unwrap_err(),
+        expected
+    );
+    assert_eq!(census_state.current_population, 0);
+    assert_eq!(stale.participant_count, 0);
+}
+
+#[test]
+fn declined_signal_counts_as_participant_only() {
+    let mut census_state = census_state();
+    let mut aggregate = aggregate(1);
+
+    for (nullifier_byte, signal) in [(1, SIGNAL_DECLINED), (2, 4)] {
+        process_count(
+            &mut census_state,
+            accounts(&mut nullifier(), Some(&mut aggregate)),
+            &count(nullifier_byte, signal),
+            50,
+        )
+        .unwrap();
+    }
+
+    assert_eq!(aggregate.participant_count, 2);
+    assert_eq!(aggregate.attribute_counts.iter().sum::<u64>(), 1);
+    assert_eq!(aggregate.attribute_counts[3], 1);
+}
+
+#[test]
+fn corrupt_aggregate_fails_the_count() {
+    // An attribute slot already at the participant count: one more count
+    // into it would outnumber participants
+    let mut census_state = census_state();
+    let mut aggregate = aggregate(1);
+    aggregate.attribute_counts[2] = 1;
+
+    assert_eq!(
+        process_count(
+            &mut census_state,
+            accounts(&mut nullifier(), Some(&mut aggregate)),
+            &count(1, 3),
+            50,
+        )
+        .unwrap_err(),
+        Error::from(CensusError::AggregateInvariantViolated)
+    );
+}
+
+#[test]
+fn batch_duplicate_nullifier_reports_its_index() {
+    assert_eq!(find_duplicate_nullifier(&[]), None);
+    assert_eq!(find_duplicate_nullifier(&[[1; 32], [2; 32], [3; 32]]), None);
+    assert_eq!(find_duplicate_nullifier(&[[1; 32], [2; 32], [1; 32], [2; 32]]), Some(2));
+}
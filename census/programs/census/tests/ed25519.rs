@@ -0,0 +1,52 @@
+use anchor_lang::prelude::Pubkey;
+use census::ed25519::{build_ed25519_ix_data, parse_ed25519_ix_data, Ed25519Signer};
+
+fn message() -> Vec<u8> {
+    census::attestation::build_attestation_message(7, &[7; 16], &[7; 32], &[7; 32], &[7; 32], &[7; 32], &[7; 32], None, None)
+}
+
+fn signers(n: u8) -> Vec<Ed25519Signer> {
+    (0..n)
+        .map(|i| Ed25519Signer {
+            pubkey: Pubkey::new_from_array([i + 1; 32]),
+            signature: [i + 100; 64],
+        })
+        .collect()
+}
+
+#[test]
+fn batched_layout_round_trips() {
+    let message = message();
+    let signers = signers(3);
+    let data = build_ed25519_ix_data(&signers, &message);
+
+    let parsed = parse_ed25519_ix_data(&data, &message).unwrap();
+    assert_eq!(parsed, signers);
+}
+
+#[test]
+fn batched_layout_rejects_other_message() {
+    let message = message();
+    let data = build_ed25519_ix_data(&signers(2), &message);
+
+    let mut other = message.clone();
+    other[1] ^= 1;
+    assert!(parse_ed25519_ix_data(&data, &other).is_err());
+
+    let mut wrong_version = message.clone();
+    wrong_version[0] = 0xff;
+    assert!(parse_ed25519_ix_data(&data, &wrong_version).is_err());
+}
+
+#[test]
+fn batched_data_stores_the_message_once() {
+    // Each extra signer adds only its offsets, pubkey and signature; the
+    // message is shared. This checks data size, not compute cost
+    let message = message();
+    let single = build_ed25519_ix_data(&signers(1), &message).len();
+    for n in 2..=5u8 {
+        let batched = build_ed25519_ix_data(&signers(n), &message).len();
+        assert!(batched < single * n as usize);
+        assert_eq!(batched - single, (n as usize - 1) * (14 + 32 + 64));
+    }
+}
@@ -0,0 +1,46 @@
+use anchor_lang::AnchorSerialize;
+use census::field::FieldElement;
+
+// BN254 scalar field modulus r, big-endian
+const MODULUS: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+#[test]
+fn byte_orders_agree() {
+    let mut be = [0u8; 32];
+    be[31] = 7;
+    let mut le = [0u8; 32];
+    le[0] = 7;
+
+    let element = FieldElement::from_be(be).unwrap();
+    assert_eq!(FieldElement::from_le(le), Some(element));
+    assert_eq!(FieldElement::from_u64(7), element);
+    assert_eq!(element.to_syscall(), be);
+    assert_eq!(element.to_le(), le);
+}
+
+#[test]
+fn values_at_or_above_the_modulus_are_rejected() {
+    let mut below = MODULUS;
+    below[31] -= 1;
+
+    assert!(FieldElement::from_be(below).is_some());
+    assert!(FieldElement::from_be(MODULUS).is_none());
+    assert!(FieldElement::from_be([0xff; 32]).is_none());
+
+    // A small little-endian value read as big-endian is usually far too large
+    let mut le_255 = [0u8; 32];
+    le_255[0] = 0xff;
+    assert!(FieldElement::from_be(le_255).is_none());
+}
+
+#[test]
+fn encodes_like_raw_bytes() {
+    let element = FieldElement::from_u64(0x0102);
+    let mut raw = [0u8; 32];
+    raw[30] = 0x01;
+    raw[31] = 0x02;
+    assert_eq!(element.try_to_vec().unwrap(), raw.try_to_vec().unwrap());
+}
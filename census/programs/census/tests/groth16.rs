@@ -0,0 +1,163 @@
+use anchor_lang::error::Error;
+use ark_bn254::Fq;
+use census::error::CensusError;
+use census::constants::NUMERIC_BUCKET_COUNT;
+use census::field::FieldElement;
+use census::groth16::{
+    decode_g2, g2_to_syscall_order, get_verification_key, is_valid_g1_point, is_valid_g2_point,
+    verify_groth16_proof, CENSUS_PUBLIC_INPUTS, MAX_PUBLIC_INPUTS, VERIFICATION_KEY_HASH,
+};
+use census::instructions::submit_census::check_bucket_index;
+
+/// Decimal field element as 32 big-endian bytes
+fn be(decimal: &str) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for digit in decimal.bytes() {
+        let mut carry = (digit - b'0') as u16;
+        for byte in bytes.iter_mut().rev() {
+            let value = *byte as u16 * 10 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+    }
+    bytes
+}
+
+/// snarkjs `pi_b` ([[x.c0, x.c1], [y.c0, y.c1]]) laid out in that order
+fn snarkjs_g2(pi_b: [[&str; 2]; 2]) -> [u8; 128] {
+    let mut point = [0u8; 128];
+    for (chunk, value) in point.chunks_exact_mut(32).zip(pi_b.iter().flatten()) {
+        chunk.copy_from_slice(&be(value));
+    }
+    point
+}
+
+// The BN254 G2 generator as snarkjs prints it
+const GENERATOR_PI_B: [[&str; 2]; 2] = [
+    [
+        "10857046999023057135944570762232829481370756359578518086990519993285655852781",
+        "11559732032986387107991004021392285783925812861821192530917403151452391805634",
+    ],
+    [
+        "8495653923123431417604973247489272438418190587263600148770280649306958101930",
+        "4082367875863433681332203403145435568316851327593401208105741076214120093531",
+    ],
+];
+
+#[test]
+fn verification_key_points_are_valid() {
+    let vkey = get_verification_key();
+
+    assert!(is_valid_g1_point(&vkey.alpha));
+    assert!(is_valid_g2_point(&vkey.beta));
+    assert!(is_valid_g2_point(&vkey.gamma));
+    assert!(is_valid_g2_point(&vkey.delta));
+    assert!(vkey.ic.iter().all(is_valid_g1_point));
+}
+
+#[test]
+fn off_curve_points_are_rejected() {
+    let vkey = get_verification_key();
+
+    let mut off_curve_g1 = vkey.alpha;
+    off_curve_g1[63] ^= 1;
+    let mut off_curve_g2 = vkey.beta;
+    off_curve_g2[127] ^= 1;
+
+    assert!(!is_valid_g1_point(&off_curve_g1));
+    assert!(!is_valid_g1_point(&[0u8; 64]));
+    assert!(!is_valid_g1_point(&[0xff; 64]));
+    assert!(!is_valid_g2_point(&off_curve_g2));
+    assert!(!is_valid_g2_point(&[0u8; 128]));
+}
+
+#[test]
+fn verify_rejects_malformed_proof_points() {
+    let vkey = get_verification_key();
+    let inputs = [FieldElement::ZERO; 4];
+
+    let mut off_curve_a = vkey.alpha;
+    off_curve_a[63] ^= 1;
+    let mut off_curve_b = vkey.beta;
+    off_curve_b[127] ^= 1;
+
+    let expected = Error::from(CensusError::InvalidProofFormat);
+    assert_eq!(
+        verify_groth16_proof(&off_curve_a, &vkey.beta, &vkey.alpha, &inputs, &vkey).unwrap_err(),
+        expected
+    );
+    assert_eq!(
+        verify_groth16_proof(&vkey.alpha, &off_curve_b, &vkey.alpha, &inputs, &vkey).unwrap_err(),
+        expected
+    );
+    assert_eq!(
+        verify_groth16_proof(&vkey.alpha, &vkey.beta, &off_curve_a, &inputs, &vkey).unwrap_err(),
+        expected
+    );
+}
+
+#[test]
+fn verify_rejects_oversized_public_inputs() {
+    let vkey = get_verification_key();
+    let inputs = vec![FieldElement::ZERO; MAX_PUBLIC_INPUTS + 1];
+
+    // Valid-looking points, so only the input count can be the reason
+    assert_eq!(
+        verify_groth16_proof(&vkey.alpha, &vkey.beta, &vkey.alpha, &inputs, &vkey).unwrap_err(),
+        Error::from(CensusError::InvalidProofFormat)
+    );
+}
+
+#[test]
+fn snarkjs_pi_b_decodes_without_swapping() {
+    let point = snarkjs_g2(GENERATOR_PI_B);
+    let decoded = decode_g2(&point).expect("generator decodes");
+    assert_eq!(decoded.x.c0, GENERATOR_PI_B[0][0].parse::<Fq>().unwrap());
+    assert_eq!(decoded.x.c1, GENERATOR_PI_B[0][1].parse::<Fq>().unwrap());
+
+    // Pre-swapping to the syscall's c1-first order is what breaks proofs
+    // that verify off-chain, so that encoding must not decode
+    assert_eq!(decode_g2(&g2_to_syscall_order(&point)), None);
+}
+
+#[test]
+fn syscall_order_swaps_each_coordinate() {
+    let point = snarkjs_g2(GENERATOR_PI_B);
+    let swapped = g2_to_syscall_order(&point);
+
+    assert_eq!(swapped[0..32], point[32..64]);
+    assert_eq!(swapped[32..64], point[0..32]);
+    assert_eq!(swapped[64..96], point[96..128]);
+    assert_eq!(swapped[96..128], point[64..96]);
+    assert_eq!(g2_to_syscall_order(&swapped), point);
+}
+
+#[test]
+fn verification_key_hash_matches_constant() {
+    assert_eq!(get_verification_key().hash(), VERIFICATION_KEY_HASH);
+}
+
+#[test]
+fn deployed_key_rejects_a_bucket_index() {
+    let vkey = get_verification_key();
+
+    assert_eq!(
+        check_bucket_index(&vkey, 0).unwrap_err(),
+        Error::from(CensusError::BucketIndexUnsupported)
+    );
+    assert_eq!(
+        check_bucket_index(&vkey, NUMERIC_BUCKET_COUNT as u8).unwrap_err(),
+        Error::from(CensusError::InvalidBucketIndex)
+    );
+
+    // A key with the bucket input accepts it
+    let mut bucketed = get_verification_key();
+    bucketed.ic.push(bucketed.ic[0]);
+    assert!(check_bucket_index(&bucketed, 0).is_ok());
+}
+
+#[test]
+fn deployed_key_proves_only_the_fixed_inputs() {
+    // No timestamp input yet, so set_max_proof_age must refuse a bound
+    assert_eq!(get_verification_key().public_input_count(), CENSUS_PUBLIC_INPUTS);
+}
@@ -0,0 +1,27 @@
+use census::constants::{ATTESTATION_NONCE_LEN, NULLIFIER_EXTRA_LEN};
+use census::state::Nullifier;
+
+fn recorded_at(timestamp: i64) -> Nullifier {
+    Nullifier {
+        nullifier_hash: [7; 32],
+        timestamp,
+        scope: 1,
+        bump: 0,
+        extra: [0; NULLIFIER_EXTRA_LEN],
+        attestation_nonce: [0; ATTESTATION_NONCE_LEN],
+    }
+}
+
+#[test]
+fn nullifier_expires_only_after_its_ttl() {
+    let recorded = recorded_at(1_000);
+
+    assert!(!recorded.is_expired(60, 1_000));
+    assert!(!recorded.is_expired(60, 1_060));
+    assert!(recorded.is_expired(60, 1_061));
+}
+
+#[test]
+fn zero_ttl_never_expires() {
+    assert!(!recorded_at(1_000).is_expired(0, i64::MAX));
+}
@@ -0,0 +1,39 @@
+use census::constants::{NULLIFIER_BITMAP_BYTES, NULLIFIER_BITMAP_HASHES};
+use census::state::NullifierBitmap;
+
+fn empty_bitmap() -> NullifierBitmap {
+    NullifierBitmap {
+        scope: 1,
+        bits: [0; NULLIFIER_BITMAP_BYTES],
+        inserted: 0,
+        bump: 0,
+    }
+}
+
+#[test]
+fn inserted_nullifiers_are_reported() {
+    let mut bitmap = empty_bitmap();
+    let nullifiers: Vec<[u8; 32]> = (0..100u8).map(|i| [i; 32]).collect();
+    for nullifier in &nullifiers {
+        bitmap.insert(nullifier).unwrap();
+    }
+
+    // No false negatives, whatever the fill
+    assert!(nullifiers.iter().all(|n| bitmap.may_contain(n)));
+    assert_eq!(bitmap.inserted, 100);
+}
+
+#[test]
+fn empty_bitmap_contains_nothing() {
+    let bitmap = empty_bitmap();
+    assert!(!bitmap.may_contain(&[7; 32]));
+}
+
+#[test]
+fn positions_are_in_range_and_deterministic() {
+    let positions = NullifierBitmap::positions(&[9; 32]);
+    assert_eq!(positions.len(), NULLIFIER_BITMAP_HASHES);
+    assert!(positions.iter().all(|&p| p < NULLIFIER_BITMAP_BYTES * 8));
+    assert_eq!(positions, NullifierBitmap::positions(&[9; 32]));
+    assert_ne!(positions, NullifierBitmap::positions(&[10; 32]));
+}
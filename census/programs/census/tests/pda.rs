@@ -0,0 +1,47 @@
+use anchor_lang::prelude::Pubkey;
+use census::pda;
+
+#[test]
+fn pdas_match_seeds_used_by_clients() {
+    let (census_state, _) = pda::census_state_pda(&census::ID);
+    let (expected, _) = Pubkey::find_program_address(&[b"census_state"], &census::ID);
+    assert_eq!(census_state, expected);
+
+    let hash = [7u8; 32];
+    let (nullifier, _) = pda::nullifier_pda(&hash, &census::ID);
+    let (expected, _) = Pubkey::find_program_address(&[b"nullifier", &hash], &census::ID);
+    assert_eq!(nullifier, expected);
+
+    let (snapshot, _) = pda::scope_snapshot_pda(3, &census::ID);
+    let (expected, _) =
+        Pubkey::find_program_address(&[b"scope_snapshot", &3u64.to_le_bytes()], &census::ID);
+    assert_eq!(snapshot, expected);
+
+    let (metadata, _) = pda::census_metadata_pda(&census::ID);
+    let (expected, _) = Pubkey::find_program_address(&[b"census_metadata"], &census::ID);
+    assert_eq!(metadata, expected);
+
+    let (registry, _) = pda::verifier_registry_pda(&census::ID);
+    let (expected, _) = Pubkey::find_program_address(&[b"verifier_registry"], &census::ID);
+    assert_eq!(registry, expected);
+
+    let (record, _) = pda::citizen_record_pda(&hash, &census::ID);
+    let (expected, _) = Pubkey::find_program_address(&[b"citizen_record", &hash], &census::ID);
+    assert_eq!(record, expected);
+}
+
+#[test]
+fn scoped_pdas_differ_per_scope() {
+    assert_ne!(
+        pda::aggregate_pda(1, &census::ID).0,
+        pda::aggregate_pda(2, &census::ID).0
+    );
+    assert_ne!(
+        pda::scope_snapshot_pda(1, &census::ID).0,
+        pda::scope_snapshot_pda(2, &census::ID).0
+    );
+    assert_ne!(
+        pda::nullifier_bitmap_pda(1, &census::ID).0,
+        pda::nullifier_bitmap_pda(2, &census::ID).0
+    );
+}
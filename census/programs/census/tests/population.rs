@@ -0,0 +1,31 @@
+use anchor_lang::prelude::AnchorDeserialize;
+use anchor_lang::Space;
+use census::constants::MAX_LEAVES;
+use census::state::CensusState;
+
+fn census_at_scope(current_scope: u64) -> CensusState {
+    let mut census_state =
+        CensusState::deserialize(&mut &vec![0u8; CensusState::INIT_SPACE][..]).unwrap();
+    census_state.current_scope = current_scope;
+    census_state
+}
+
+#[test]
+fn scope_population_stops_at_tree_capacity() {
+    let census_state = census_at_scope(1);
+
+    assert!(census_state.is_population_possible(MAX_LEAVES - 1));
+    assert!(!census_state.is_population_possible(MAX_LEAVES));
+}
+
+#[test]
+fn lifetime_counts_stop_at_capacity_per_scope() {
+    let mut census_state = census_at_scope(2);
+    census_state.counted_by_proof = MAX_LEAVES;
+    census_state.counted_by_attestation = MAX_LEAVES - 1;
+    assert!(census_state.is_population_possible(0));
+
+    census_state.counted_by_attestation = MAX_LEAVES;
+    assert!(!census_state.is_population_possible(0));
+    assert_eq!(census_state.lifetime_counted(), 2 * MAX_LEAVES);
+}
@@ -0,0 +1,27 @@
+use census::groth16::{poseidon_hash, MAX_POSEIDON_INPUTS};
+
+fn field(value: u8) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[31] = value;
+    bytes
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[test]
+fn matches_circomlib_test_vector() {
+    // circomlibjs: poseidon([1, 2])
+    let hash = poseidon_hash(&[field(1), field(2)]).unwrap();
+    assert_eq!(
+        hex(&hash),
+        "115cc0f5e7d690413df64c6b9662e9cf2a3617f2743245519e19607a4417189a"
+    );
+}
+
+#[test]
+fn rejects_unsupported_arity() {
+    assert!(poseidon_hash(&[]).is_err());
+    assert!(poseidon_hash(&vec![field(1); MAX_POSEIDON_INPUTS + 1]).is_err());
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::AnchorDeserialize;
+use anchor_lang::Space;
+use census::constants::RECENT_ROOTS_CAPACITY;
+use census::state::CensusState;
+
+fn census_state() -> CensusState {
+    CensusState::deserialize(&mut &vec![0u8; CensusState::INIT_SPACE][..]).unwrap()
+}
+
+fn root(n: usize) -> [u8; 32] {
+    let mut root = [0u8; 32];
+    root[..8].copy_from_slice(&(n as u64 + 1).to_le_bytes());
+    root
+}
+
+#[test]
+fn partial_fill_ignores_unused_slots() {
+    let mut census_state = census_state();
+    for n in 0..3 {
+        census_state.set_root(root(n), 100).unwrap();
+    }
+
+    assert_eq!(census_state.active_recent_roots, 3);
+    assert_eq!(census_state.recent_roots(), &[root(0), root(1), root(2)]);
+    assert!((0..3).all(|n| census_state.accepts_proof_root(&root(n), 100)));
+    // The zeroed slots past the cursor are not roots anyone published
+    assert!(!census_state.accepts_proof_root(&[0u8; 32], 100));
+    assert_eq!(census_state.root_version, 3);
+}
+
+#[test]
+fn wraparound_evicts_the_oldest_roots() {
+    let mut census_state = census_state();
+    let published = RECENT_ROOTS_CAPACITY + 2;
+    for n in 0..published {
+        census_state.set_root(root(n), 100).unwrap();
+    }
+
+    assert_eq!(census_state.active_recent_roots as usize, RECENT_ROOTS_CAPACITY);
+    assert!(!census_state.accepts_proof_root(&root(0), 100));
+    assert!(!census_state.accepts_proof_root(&root(1), 100));
+    assert!((2..published).all(|n| census_state.accepts_proof_root(&root(n), 100)));
+    assert_eq!(census_state.recent_roots().first(), Some(&root(2)));
+    assert_eq!(census_state.recent_roots().last(), Some(&census_state.merkle_root));
+}
+
+#[test]
+fn pinned_scope_root_overrides_recent_roots() {
+    let mut census_state = census_state();
+    census_state.set_root(root(0), 100).unwrap();
+    census_state.set_root(root(1), 100).unwrap();
+    census_state.scope_root = root(0);
+    census_state.pin_root_per_scope = true;
+
+    assert!(census_state.accepts_proof_root(&root(0), 100));
+    assert!(!census_state.accepts_proof_root(&root(1), 100));
+}
+
+#[test]
+fn in_window_historical_root_is_accepted() {
+    let mut census_state = census_state();
+    census_state.root_validity_seconds = 60;
+    census_state.set_root(root(0), 100).unwrap();
+    census_state.set_root(root(1), 130).unwrap();
+
+    assert!(census_state.accepts_proof_root(&root(0), 160));
+
+    // 0 = bounded by count alone
+    census_state.root_validity_seconds = 0;
+    assert!(census_state.accepts_proof_root(&root(0), 10_000));
+}
+
+#[test]
+fn expired_historical_root_is_rejected() {
+    let mut census_state = census_state();
+    census_state.root_validity_seconds = 60;
+    census_state.set_root(root(0), 100).unwrap();
+    census_state.set_root(root(1), 130).unwrap();
+
+    // Still in recent_roots, but published more than 60s ago
+    assert!(!census_state.accepts_proof_root(&root(0), 161));
+    // The live root never expires
+    assert!(census_state.accepts_proof_root(&root(1), 10_000));
+}
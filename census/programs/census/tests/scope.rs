@@ -0,0 +1,228 @@
+use anchor_lang::prelude::{AnchorDeserialize, Pubkey};
+use anchor_lang::Space;
+use census::constants::{EXTERNAL_NULLIFIER_DOMAIN_TAG, FUTURE_SCOPE_LOOKAHEAD};
+use census::groth16::poseidon_hash;
+use census::scope::{
+    accumulate_nullifier, calendar_advance, decode_scope, expected_external_nullifier,
+    external_nullifier_for, is_scope_expired, next_scope_boundary, scope_at, scope_start,
+    seconds_until_expiry, submission_scope,
+};
+use census::state::CensusState;
+
+const WEEK: i64 = 7 * 24 * 60 * 60;
+
+#[test]
+fn timed_scope_expires_at_boundary() {
+    let start = 1_700_000_000;
+    assert!(!is_scope_expired(start, WEEK, start));
+    assert!(!is_scope_expired(start, WEEK, start + WEEK - 1));
+    assert!(is_scope_expired(start, WEEK, start + WEEK));
+}
+
+#[test]
+fn zero_duration_never_expires() {
+    let start = 1_700_000_000;
+    assert!(!is_scope_expired(start, 0, start));
+    assert!(!is_scope_expired(start, 0, start + 100 * WEEK));
+    assert!(!is_scope_expired(start, 0, i64::MAX));
+}
+
+#[test]
+fn previous_scope_counts_only_during_grace() {
+    let start = 1_700_000_000;
+
+    assert_eq!(submission_scope(5, start, 60, start + 600, true, false), Some(5));
+    assert_eq!(submission_scope(5, start, 60, start + 59, false, true), Some(4));
+    assert_eq!(submission_scope(5, start, 60, start + 60, false, true), None);
+    assert_eq!(submission_scope(5, start, 0, start, false, true), None);
+    assert_eq!(submission_scope(5, start, 60, start, false, false), None);
+    assert_eq!(submission_scope(0, start, 60, start, false, true), None);
+}
+
+#[test]
+fn external_nullifier_versions_differ() {
+    let census = Pubkey::new_unique();
+    let v0 = external_nullifier_for(0, 5, &census).unwrap();
+    let v1 = external_nullifier_for(1, 5, &census).unwrap();
+    let v2 = external_nullifier_for(2, 5, &census).unwrap();
+
+    assert_eq!(v0[0], 5);
+    assert!(v0[8..].iter().all(|&b| b == 0));
+    assert_ne!(v0, v1);
+    assert_ne!(v1, v2);
+    assert_ne!(v1, external_nullifier_for(1, 6, &census).unwrap());
+    assert!(external_nullifier_for(3, 5, &census).is_err());
+}
+
+#[test]
+fn handler_external_nullifier_matches_helper() {
+    let census_key = Pubkey::new_unique();
+    let mut census_state =
+        CensusState::deserialize(&mut &vec![0u8; CensusState::INIT_SPACE][..]).unwrap();
+    census_state.current_scope = 5;
+    census_state.external_nullifier_version = 2;
+
+    let expected = expected_external_nullifier(5, &census_key, EXTERNAL_NULLIFIER_DOMAIN_TAG).unwrap();
+    assert_eq!(external_nullifier_for(2, 5, &census_key).unwrap(), expected);
+    assert!(census_state.accepts_external_nullifier(&census_key, 5, &expected).unwrap());
+    assert_eq!(
+        census_state.submission_scope(&census_key, &expected, 0).unwrap(),
+        Some(5)
+    );
+
+    // Bound to the census: another census's derivation doesn't count here
+    let other = expected_external_nullifier(5, &Pubkey::new_unique(), EXTERNAL_NULLIFIER_DOMAIN_TAG).unwrap();
+    assert_ne!(other, expected);
+    assert!(!census_state.accepts_external_nullifier(&census_key, 5, &other).unwrap());
+}
+
+#[test]
+fn seconds_until_expiry_agrees_with_expiry() {
+    let start = 1_700_000_000;
+
+    assert_eq!(seconds_until_expiry(start, WEEK, start), WEEK);
+    assert_eq!(seconds_until_expiry(start, WEEK, start + WEEK - 1), 1);
+    assert!(!is_scope_expired(start, WEEK, start + WEEK - 1));
+    assert_eq!(seconds_until_expiry(start, WEEK, start + WEEK), 0);
+    assert!(is_scope_expired(start, WEEK, start + WEEK));
+    assert_eq!(seconds_until_expiry(start, 0, start + WEEK), i64::MAX);
+}
+
+#[test]
+fn next_boundary_is_the_first_expired_second() {
+    let start = 1_700_000_000;
+    let boundary = next_scope_boundary(start, WEEK);
+
+    assert_eq!(boundary, start + WEEK);
+    assert!(!is_scope_expired(start, WEEK, boundary - 1));
+    assert!(is_scope_expired(start, WEEK, boundary));
+    assert_eq!(next_scope_boundary(start, 0), i64::MAX);
+    assert_eq!(next_scope_boundary(i64::MAX - 1, WEEK), i64::MAX);
+}
+
+#[test]
+fn scope_at_follows_the_calendar() {
+    let genesis = 1_700_000_000;
+
+    assert_eq!(scope_at(genesis, genesis, WEEK), 1);
+    assert_eq!(scope_at(genesis + WEEK - 1, genesis, WEEK), 1);
+    assert_eq!(scope_at(genesis + WEEK, genesis, WEEK), 2);
+    assert_eq!(scope_at(genesis + 10 * WEEK, genesis, WEEK), 11);
+    assert_eq!(scope_at(genesis - 1, genesis, WEEK), 1);
+    assert_eq!(scope_at(genesis + WEEK, genesis, 0), 1);
+    assert_eq!(scope_at(i64::MAX, i64::MIN, 1), u64::MAX);
+}
+
+#[test]
+fn extreme_timestamps_do_not_overflow() {
+    assert!(!is_scope_expired(i64::MAX, i64::MAX, i64::MAX - 1));
+    assert!(is_scope_expired(i64::MIN, 1, i64::MAX));
+    assert_eq!(seconds_until_expiry(i64::MAX, i64::MAX, i64::MIN), i64::MAX);
+    assert_eq!(seconds_until_expiry(i64::MIN, 1, i64::MAX), i64::MIN);
+    assert_eq!(submission_scope(u64::MAX, i64::MAX, i64::MAX, i64::MIN, false, true), None);
+    assert_eq!(submission_scope(5, i64::MAX - 2, i64::MAX, i64::MAX - 1, false, true), Some(4));
+}
+
+#[test]
+fn nullifier_accumulator_chains_in_order() {
+    let (first, second) = ([0x01; 32], [0x02; 32]);
+    let once = accumulate_nullifier(&[0; 32], &first).unwrap();
+    assert_eq!(once, poseidon_hash(&[[0; 32], first]).unwrap());
+
+    let forward = accumulate_nullifier(&once, &second).unwrap();
+    let reversed = accumulate_nullifier(&accumulate_nullifier(&[0; 32], &second).unwrap(), &first).unwrap();
+    assert_ne!(forward, reversed);
+
+    // Non-canonical field elements are rejected rather than reduced
+    assert!(accumulate_nullifier(&[0; 32], &[0xff; 32]).is_err());
+}
+
+#[test]
+fn scope_start_inverts_scope_at() {
+    let genesis = 1_700_000_000;
+    for scope in [1, 2, 11] {
+        let start = scope_start(scope, genesis, WEEK).unwrap();
+        assert_eq!(scope_at(start, genesis, WEEK), scope);
+        assert_eq!(scope_at(start - 1, genesis, WEEK), scope.max(2) - 1);
+    }
+    assert_eq!(scope_start(0, genesis, WEEK), None);
+    assert_eq!(scope_start(u64::MAX, genesis, WEEK), None);
+}
+
+#[test]
+fn calendar_advance_catches_up_to_the_calendar() {
+    let genesis = 1_700_000_000;
+    let now = genesis + 3 * WEEK + 5;
+
+    // A late advance from scope 1 skips to the calendar's scope 4
+    assert_eq!(calendar_advance(1, now, genesis, WEEK), Some((4, genesis + 3 * WEEK)));
+    assert_eq!(calendar_advance(u64::MAX, now, genesis, WEEK), None);
+}
+
+#[test]
+fn calendar_advance_cannot_repeat_within_one_timestamp() {
+    // The census runs ahead of the calendar (scope 9 while it says 4), so the
+    // next scope's calendar start is still weeks away
+    let genesis = 1_700_000_000;
+    let now = genesis + 3 * WEEK + 5;
+    let mut census_state =
+        CensusState::deserialize(&mut &vec![0u8; CensusState::INIT_SPACE][..]).unwrap();
+    census_state.current_scope = 9;
+    census_state.scope_duration = WEEK;
+    census_state.scope_epoch = genesis;
+
+    let (scope, start) = calendar_advance(9, now, genesis, WEEK).unwrap();
+    census_state.current_scope = scope;
+    census_state.scope_start_time = start;
+
+    assert_eq!(scope, 10);
+    assert_eq!(start, now);
+    // try_advance_scope and roll_scope need an expired scope, so a second
+    // call at `now` fails instead of skipping to scope 11
+    assert!(!census_state.is_scope_expired(now));
+    assert!(!census_state.is_scope_expired(now + WEEK - 1));
+    assert!(census_state.is_scope_expired(now + WEEK));
+}
+
+#[test]
+fn nullifiers_close_once_their_scope_stops_counting() {
+    let start = 1_700_000_000;
+    let mut census_state =
+        CensusState::deserialize(&mut &vec![0u8; CensusState::INIT_SPACE][..]).unwrap();
+    census_state.current_scope = 5;
+    census_state.scope_start_time = start;
+    census_state.scope_grace_seconds = 60;
+
+    assert!(!census_state.is_nullifier_closeable(5, start + 600));
+    assert!(census_state.is_nullifier_closeable(3, start));
+    // The previous scope still counts during its grace window
+    assert!(!census_state.is_nullifier_closeable(4, start + 59));
+    assert!(census_state.is_nullifier_closeable(4, start + 60));
+}
+
+#[test]
+fn future_scope_external_nullifiers_are_recognized() {
+    let census_key = Pubkey::new_unique();
+    let mut census_state =
+        CensusState::deserialize(&mut &vec![0u8; CensusState::INIT_SPACE][..]).unwrap();
+    census_state.current_scope = 5;
+
+    // v0 decodes directly, however far ahead
+    let v0 = |scope| external_nullifier_for(0, scope, &census_key).unwrap();
+    assert_eq!(decode_scope(&v0(9)), Some(9));
+    assert_eq!(decode_scope(&[0xff; 32]), None);
+    assert_eq!(census_state.future_scope_offset(&census_key, &v0(9)).unwrap(), Some(4));
+    assert_eq!(census_state.future_scope_offset(&census_key, &v0(4)).unwrap(), None);
+
+    // Hashed derivations are only found within the lookahead
+    census_state.external_nullifier_version = 2;
+    let v2 = |scope| external_nullifier_for(2, scope, &census_key).unwrap();
+    assert_eq!(census_state.future_scope_offset(&census_key, &v2(6)).unwrap(), Some(1));
+    assert_eq!(
+        census_state
+            .future_scope_offset(&census_key, &v2(5 + FUTURE_SCOPE_LOOKAHEAD + 1))
+            .unwrap(),
+        None
+    );
+    assert_eq!(census_state.future_scope_offset(&census_key, &v2(4)).unwrap(), None);
+}
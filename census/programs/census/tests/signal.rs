@@ -0,0 +1,71 @@
+use census::constants::ATTRIBUTE_COUNT;
+use census::signal::{decode_signal, is_declined, SIGNAL_DECLINED};
+use census::state::CensusAggregate;
+
+fn signal(value: u8) -> [u8; 32] {
+    let mut signal_hash = [0u8; 32];
+    signal_hash[31] = value;
+    signal_hash
+}
+
+#[test]
+fn small_signals_select_attribute_slots() {
+    assert_eq!(decode_signal(&signal(1)), Some(0));
+    assert_eq!(decode_signal(&signal(10)), Some(9));
+}
+
+#[test]
+fn other_signals_have_no_attribute() {
+    assert_eq!(decode_signal(&signal(SIGNAL_DECLINED)), None);
+    assert_eq!(decode_signal(&signal(11)), None);
+
+    let mut hashed = signal(1);
+    hashed[0] = 0x2a;
+    assert_eq!(decode_signal(&hashed), None);
+}
+
+#[test]
+fn declined_signal_counts_participant_only() {
+    let mut aggregate = CensusAggregate {
+        scope: 1,
+        participant_count: 0,
+        attribute_counts: [0; ATTRIBUTE_COUNT],
+        last_updated: 0,
+        bump: 0,
+    };
+
+    aggregate.record(decode_signal(&signal(SIGNAL_DECLINED)), 10).unwrap();
+    aggregate.record(decode_signal(&signal(3)), 20).unwrap();
+
+    assert_eq!(aggregate.participant_count, 2);
+    assert_eq!(aggregate.attribute_counts.iter().sum::<u64>(), 1);
+    assert_eq!(aggregate.attribute_counts[2], 1);
+    assert_eq!(aggregate.last_updated, 20);
+}
+
+#[test]
+fn attribute_cannot_outnumber_participants() {
+    // Corrupt aggregate: an attribute already counted more than participants
+    let mut attribute_counts = [0; ATTRIBUTE_COUNT];
+    attribute_counts[2] = 1;
+    let mut aggregate = CensusAggregate {
+        scope: 1,
+        participant_count: 0,
+        attribute_counts,
+        last_updated: 0,
+        bump: 0,
+    };
+
+    assert!(aggregate.record(decode_signal(&signal(3)), 10).is_err());
+    assert!(aggregate.record(decode_signal(&signal(4)), 10).is_ok());
+}
+
+#[test]
+fn only_zero_is_declined() {
+    assert!(is_declined(&signal(SIGNAL_DECLINED)));
+    assert!(!is_declined(&signal(1)));
+
+    let mut hashed = signal(SIGNAL_DECLINED);
+    hashed[0] = 0x2a;
+    assert!(!is_declined(&hashed));
+}
@@ -0,0 +1,24 @@
+use census::constants::MAX_CLOCK_SKEW;
+use census::time::within_window;
+
+const NOW: i64 = 1_700_000_000;
+
+#[test]
+fn window_is_half_open() {
+    assert!(within_window(NOW, NOW, 300));
+    assert!(within_window(NOW + 299, NOW, 300));
+    assert!(!within_window(NOW + 300, NOW, 300));
+}
+
+#[test]
+fn future_target_tolerates_clock_skew() {
+    assert!(within_window(NOW, NOW + MAX_CLOCK_SKEW, 300));
+    assert!(!within_window(NOW, NOW + MAX_CLOCK_SKEW + 1, 300));
+}
+
+#[test]
+fn extreme_values_do_not_overflow() {
+    assert!(within_window(i64::MAX - 1, i64::MAX - 10, 300));
+    assert!(within_window(i64::MIN, i64::MIN, 300));
+    assert!(!within_window(i64::MIN, i64::MAX, i64::MAX));
+}
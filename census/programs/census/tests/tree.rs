@@ -0,0 +1,142 @@
+use anchor_lang::prelude::Pubkey;
+use census::constants::{MAX_LEAVES, SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID};
+use census::tree::{
+    append_ix, appended_leaf_index, is_root_checkpoint, parse_tree_data, remaining_capacity,
+    replace_leaf_ix, transfer_authority_ix, verify_leaf_ix,
+};
+
+/// Header + tree prefix for a tree with the given sizes and active index
+fn header(max_buffer_size: u32, max_depth: u32, active_index: u64) -> Vec<u8> {
+    let mut data = vec![0u8; 56 + 24];
+    data[0] = 1; // ConcurrentMerkleTree
+    data[1] = 0; // V1
+    data[2..6].copy_from_slice(&max_buffer_size.to_le_bytes());
+    data[6..10].copy_from_slice(&max_depth.to_le_bytes());
+    data[56 + 8..56 + 16].copy_from_slice(&active_index.to_le_bytes());
+    data
+}
+
+#[test]
+fn corrupt_sizes_are_rejected_without_overflow() {
+    assert!(parse_tree_data(&header(u32::MAX, u32::MAX, u64::from(u32::MAX) - 1)).is_err());
+    assert!(parse_tree_data(&header(u32::MAX, 20, u64::MAX)).is_err());
+    assert!(parse_tree_data(&header(64, 20, 0)).is_err()); // truncated
+}
+
+#[test]
+fn reads_root_and_leaf_count() {
+    let (buffer, depth) = (2u32, 3usize);
+    let change_log_size = 32 + 32 * depth + 8;
+    let mut data = header(buffer, depth as u32, 1);
+    data.resize(56 + 24 + buffer as usize * change_log_size + 32 * depth + 32 + 8, 0);
+
+    let root_offset = 56 + 24 + change_log_size;
+    data[root_offset..root_offset + 32].copy_from_slice(&[9; 32]);
+    let leaf_count_offset = 56 + 24 + buffer as usize * change_log_size + 32 * depth + 32;
+    data[leaf_count_offset..leaf_count_offset + 4].copy_from_slice(&5u32.to_le_bytes());
+
+    let tree = parse_tree_data(&data).unwrap();
+    assert_eq!(tree.root, [9; 32]);
+    assert_eq!(tree.leaf_count, 5);
+    assert_eq!(tree.max_depth, 3);
+}
+
+#[test]
+fn transfer_authority_ix_matches_compression_layout() {
+    let (tree, authority, new_authority) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = transfer_authority_ix(&tree, &authority, &new_authority);
+
+    assert_eq!(ix.program_id, SPL_ACCOUNT_COMPRESSION_ID);
+    // sha256("global:transfer_authority")[..8], then the new authority
+    assert_eq!(ix.data[..8], [48, 169, 76, 72, 229, 180, 55, 161]);
+    assert_eq!(ix.data[8..], new_authority.to_bytes());
+    assert!(ix.accounts[0].pubkey == tree && ix.accounts[0].is_writable);
+    assert!(ix.accounts[1].pubkey == authority && ix.accounts[1].is_signer);
+}
+
+#[test]
+fn verify_leaf_ix_matches_compression_layout() {
+    let tree = Pubkey::new_unique();
+    let proof = [Pubkey::new_unique(), Pubkey::new_unique()];
+    let ix = verify_leaf_ix(&tree, &[1; 32], &[2; 32], 7, &proof);
+
+    assert_eq!(ix.program_id, SPL_ACCOUNT_COMPRESSION_ID);
+    // sha256("global:verify_leaf")[..8], root, leaf, index (LE)
+    assert_eq!(ix.data[..8], [124, 220, 22, 223, 104, 10, 250, 224]);
+    assert_eq!(ix.data[8..40], [1; 32]);
+    assert_eq!(ix.data[40..72], [2; 32]);
+    assert_eq!(ix.data[72..], 7u32.to_le_bytes());
+    // Tree first, then the proof nodes in order, all read-only
+    assert_eq!(ix.accounts.len(), 3);
+    assert!(ix.accounts[0].pubkey == tree && !ix.accounts[0].is_writable);
+    assert_eq!(ix.accounts[1].pubkey, proof[0]);
+    assert_eq!(ix.accounts[2].pubkey, proof[1]);
+}
+
+#[test]
+fn append_ix_matches_compression_layout() {
+    let (tree, authority) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = append_ix(&tree, &authority, &[9; 32]);
+
+    assert_eq!(ix.program_id, SPL_ACCOUNT_COMPRESSION_ID);
+    // sha256("global:append")[..8], then the leaf
+    assert_eq!(ix.data[..8], [149, 120, 18, 222, 236, 225, 88, 203]);
+    assert_eq!(ix.data[8..], [9; 32]);
+    assert!(ix.accounts[0].pubkey == tree && ix.accounts[0].is_writable);
+    assert!(ix.accounts[1].pubkey == authority && ix.accounts[1].is_signer);
+    assert_eq!(ix.accounts[2].pubkey, SPL_NOOP_ID);
+}
+
+#[test]
+fn remaining_capacity_saturates_at_zero() {
+    assert_eq!(remaining_capacity(0), 1 << 20);
+    assert_eq!(remaining_capacity(MAX_LEAVES - 1), 1);
+    assert_eq!(remaining_capacity(MAX_LEAVES), 0);
+    assert_eq!(remaining_capacity(MAX_LEAVES + 5), 0);
+}
+
+#[test]
+fn replace_leaf_ix_matches_compression_layout() {
+    let (tree, authority, node) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = replace_leaf_ix(&tree, &authority, &[1; 32], &[2; 32], &[3; 32], 7, &[node]);
+
+    assert_eq!(ix.program_id, SPL_ACCOUNT_COMPRESSION_ID);
+    // sha256("global:replace_leaf")[..8], root, previous leaf, new leaf, index (LE)
+    assert_eq!(ix.data[..8], [204, 165, 76, 100, 73, 147, 0, 128]);
+    assert_eq!(ix.data[8..40], [1; 32]);
+    assert_eq!(ix.data[40..72], [2; 32]);
+    assert_eq!(ix.data[72..104], [3; 32]);
+    assert_eq!(ix.data[104..], 7u32.to_le_bytes());
+    // Modify accounts, then the proof
+    assert!(ix.accounts[0].pubkey == tree && ix.accounts[0].is_writable);
+    assert!(ix.accounts[1].pubkey == authority && ix.accounts[1].is_signer);
+    assert_eq!(ix.accounts[2].pubkey, SPL_NOOP_ID);
+    assert_eq!(ix.accounts[3].pubkey, node);
+}
+
+#[test]
+fn roots_checkpoint_every_interval_leaves() {
+    let checkpoints: Vec<u64> = (1..=10).filter(|&count| is_root_checkpoint(count, 4)).collect();
+    assert_eq!(checkpoints, vec![4, 8]);
+    assert!(is_root_checkpoint(1, 1));
+    assert!(!is_root_checkpoint(4, 0));
+}
+
+#[test]
+fn appended_leaf_indices_stay_gapless() {
+    // One append per registration: indices follow leaf_count exactly
+    let mut leaf_count = 0;
+    let mut indices = Vec::new();
+    for _ in 0..4 {
+        indices.push(appended_leaf_index(leaf_count + 1, leaf_count).unwrap());
+        leaf_count += 1;
+    }
+    assert_eq!(indices, vec![0, 1, 2, 3]);
+
+    // No append (replayed registration), two appends, or a tree behind the census
+    assert!(appended_leaf_index(leaf_count, leaf_count).is_err());
+    assert!(appended_leaf_index(leaf_count + 2, leaf_count).is_err());
+    assert!(appended_leaf_index(leaf_count - 1, leaf_count).is_err());
+    assert!(appended_leaf_index(u64::MAX, u64::MAX).is_err());
+}
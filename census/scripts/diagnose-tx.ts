@@ -57,6 +57,9 @@ async function main() {
     Buffer.from(mockProofB),
     Buffer.from(mockProofC),
     ...mockPublicInputs,
+    Buffer.from([0]), // expected_root_version: None
+    Buffer.from([0]), // extra: None
+    Buffer.from([0]), // bucket_index: None
   ]);
 
   console.log("\n📋 Instruction Data:");
@@ -47,8 +47,20 @@ async function main() {
   // Initialize with 1 week scope duration (604800 seconds)
   const scopeDuration = new anchor.BN(604800);
 
+  // No cap on registrations (0 = unlimited)
+  const maxCitizens = new anchor.BN(0);
+
+  // Keep counting closed until enough citizens register to hide among
+  const minRegistrationsToActivate = new anchor.BN(10);
+
+  // Keep the verifier attestation path available
+  const requireZkProofs = false;
+
+  // Declined signals still count (signal optional)
+  const requireSignal = false;
+
   const tx = await program.methods
-    .initialize(scopeDuration)
+    .initialize(scopeDuration, maxCitizens, minRegistrationsToActivate, requireZkProofs, requireSignal)
     .rpc();
 
   console.log("\n✅ Census State Initialized!");
@@ -63,11 +63,14 @@ async function main() {
       Buffer.from(mockProofB),
       Buffer.from(mockProofC),
       ...mockPublicInputs.map(p => Buffer.from(p)),
+      Buffer.from([0]), // expected_root_version: None
+      Buffer.from([0]), // extra: None
+      Buffer.from([0]), // bucket_index: None
     ]);
 
     console.log("  Discriminator:", discriminator.toString('hex'));
     console.log("  Data length:", data.length, "bytes");
-    console.log("  Expected: 8 (disc) + 64 (A) + 128 (B) + 64 (C) + 4*32 (inputs) =", 8 + 64 + 128 + 64 + 128, "bytes");
+    console.log("  Expected: 8 (disc) + 64 (A) + 128 (B) + 64 (C) + 4*32 (inputs) + 3 (None x3) =", 8 + 64 + 128 + 64 + 128 + 3, "bytes");
 
     const manualIx = new TransactionInstruction({
       keys: [
@@ -101,7 +104,7 @@ async function main() {
     
     // Method 2: Anchor SDK (proper way)
     const anchorTx = await program.methods
-      .submitCensus(mockProofA, mockProofB, mockProofC, mockPublicInputs)
+      .submitCensus(mockProofA, mockProofB, mockProofC, mockPublicInputs, null, null, null, null)
       .rpc();
 
     console.log("✅ Anchor SDK SUCCESS!");
@@ -1,18 +1,27 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { Census } from "../target/types/census";
+import { CensusCaller } from "../target/types/census_caller";
 import { expect } from "chai";
 import { 
   PublicKey, 
   Keypair, 
   SystemProgram,
-  LAMPORTS_PER_SOL 
+  LAMPORTS_PER_SOL,
+  ComputeBudgetProgram,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
 } from "@solana/web3.js";
 import {
   SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
   SPL_NOOP_PROGRAM_ID,
   getConcurrentMerkleTreeAccountSize,
+  createAllocTreeIx,
+  createInitEmptyMerkleTreeIx,
+  createTransferAuthorityIx,
+  ConcurrentMerkleTreeAccount,
 } from "@solana/spl-account-compression";
+import { createHash } from "crypto";
 
 describe("zk-census", () => {
   // Configure the client
@@ -20,6 +29,7 @@ describe("zk-census", () => {
   anchor.setProvider(provider);
 
   const program = anchor.workspace.Census as Program<Census>;
+  const caller = anchor.workspace.CensusCaller as Program<CensusCaller>;
   const admin = provider.wallet;
 
   // PDAs
@@ -28,11 +38,130 @@ describe("zk-census", () => {
   let merkleTreePda: PublicKey;
   let merkleTreeBump: number;
 
+  const scopeSnapshotPdaFor = (scope: number) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("scope_snapshot"), new anchor.BN(scope).toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+
+  // Build a valid single-verifier attestation over the current census state,
+  // signed by the admin wallet. Overrides let negative tests break one field
+  const buildAttestation = async (
+    overrides: {
+      timestamp?: anchor.BN;
+      merkleRoot?: number[];
+      nullifierHash?: Buffer;
+      scope?: anchor.BN;
+    } = {}
+  ) => {
+    const state = await program.account.censusState.fetch(censusStatePda);
+    const timestamp = overrides.timestamp ?? new anchor.BN(Math.floor(Date.now() / 1000));
+    const merkleRoot = overrides.merkleRoot ?? state.merkleRoot;
+    const nonce = Buffer.alloc(16);
+    crypto.getRandomValues(nonce);
+    let nullifierHash = overrides.nullifierHash;
+    if (!nullifierHash) {
+      nullifierHash = Buffer.alloc(32);
+      crypto.getRandomValues(nullifierHash);
+      nullifierHash[0] = 0; // keep it below the field modulus
+    }
+    const externalNullifier = Buffer.alloc(32);
+    (overrides.scope ?? state.currentScope).toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+    const signalHash = Buffer.alloc(32);
+    signalHash[31] = 1;
+
+    // [version, timestamp, nonce, root, nullifier, external, signal, circuit hash]
+    const message = Buffer.concat([
+      Buffer.from([0x04]),
+      timestamp.toArrayLike(Buffer, "le", 8),
+      nonce,
+      Buffer.from(merkleRoot),
+      nullifierHash,
+      externalNullifier,
+      signalHash,
+      Buffer.from(state.circuitHash),
+    ]);
+    const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: (admin as anchor.Wallet).payer.secretKey,
+      message,
+    });
+    const [nullifierPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("nullifier"), nullifierHash],
+      program.programId
+    );
+
+    return {
+      timestamp,
+      nonce,
+      merkleRoot,
+      nullifierHash,
+      externalNullifier,
+      signalHash,
+      signature: ed25519Ix.data.subarray(48, 112),
+      ed25519Ix,
+      nullifierPda,
+    };
+  };
+
+  // submit_attestation for an attestation from buildAttestation, paid and
+  // signed by the admin wallet, tallied into `aggregate` if given
+  const submitAttestationFor = (
+    attestation: Awaited<ReturnType<typeof buildAttestation>>,
+    aggregate: PublicKey | null = null
+  ) =>
+    program.methods
+      .submitAttestation(
+        attestation.timestamp,
+        [...attestation.nonce],
+        [...attestation.merkleRoot],
+        [...attestation.nullifierHash],
+        [...attestation.externalNullifier],
+        [...attestation.signalHash],
+        [...attestation.signature],
+        null,
+        null,
+        null
+      )
+      .accounts({
+        payer: admin.publicKey,
+        verifier: admin.publicKey,
+        censusState: censusStatePda,
+        nullifier: attestation.nullifierPda,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        systemProgram: SystemProgram.programId,
+        previousScopeSnapshot: null,
+        payerCooldown: null,
+        nullifierBitmap: null,
+        merkleTree: null,
+        compressionProgram: null,
+        verifierRegistry: null,
+        citizenRecord: null,
+        aggregate,
+      })
+      .preInstructions([attestation.ed25519Ix]);
+
+  // Events a confirmed transaction emitted
+  const eventsOf = async (signature: string) => {
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return [...parser.parseLogs(tx.meta.logMessages)];
+  };
+
   // Tree parameters (matching program constants)
   const TREE_DEPTH = 20;
   const MAX_BUFFER_SIZE = 64;
   const CANOPY_DEPTH = 10;
 
+  // The program's RECOMMENDED_COMPUTE_UNITS_SUBMIT_CENSUS, from its IDL
+  const RECOMMENDED_COMPUTE_UNITS_SUBMIT_CENSUS = Number(
+    program.idl.constants
+      .find(({ name }) => name === "recommendedComputeUnitsSubmitCensus")
+      .value.replace(/_/g, "")
+  );
+
   before(async () => {
     // Derive PDAs
     [censusStatePda, censusStateBump] = PublicKey.findProgramAddressSync(
@@ -47,6 +176,23 @@ describe("zk-census", () => {
   });
 
   describe("Initialize", () => {
+    it("should reject a negative scope duration", async () => {
+      try {
+        await program.methods
+          .initialize(new anchor.BN(-1), new anchor.BN(0), new anchor.BN(0), false, false)
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidScopeDuration error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidScopeDuration");
+      }
+    });
+
     it("should initialize the census system", async () => {
       // Calculate Merkle tree account size
       const treeSize = getConcurrentMerkleTreeAccountSize(
@@ -67,8 +213,14 @@ describe("zk-census", () => {
       // Weekly census duration (7 days in seconds)
       const scopeDuration = new anchor.BN(7 * 24 * 60 * 60);
 
+      // No registration cap, counting opens immediately, attestations allowed
+      const maxCitizens = new anchor.BN(0);
+      const minRegistrationsToActivate = new anchor.BN(0);
+      const requireZkProofs = false;
+      const requireSignal = false;
+
       const tx = await program.methods
-        .initialize(scopeDuration)
+        .initialize(scopeDuration, maxCitizens, minRegistrationsToActivate, requireZkProofs, requireSignal)
         .accounts({
           admin: admin.publicKey,
           censusState: censusStatePda,
@@ -88,6 +240,8 @@ describe("zk-census", () => {
       expect(censusState.currentScope.toNumber()).to.equal(1);
       expect(censusState.isActive).to.be.true;
       expect(censusState.totalRegistered.toNumber()).to.equal(0);
+      expect(censusState.maxCitizens.toNumber()).to.equal(0);
+      expect(censusState.rentSink.toBase58()).to.equal(admin.publicKey.toBase58());
     });
   });
 
@@ -98,14 +252,15 @@ describe("zk-census", () => {
       crypto.getRandomValues(identityCommitment);
 
       const tx = await program.methods
-        .registerCitizen([...identityCommitment])
+        .registerCitizen([...identityCommitment], 1)
         .accounts({
           admin: admin.publicKey,
           censusState: censusStatePda,
-          merkleTree: merkleTreePda,
+          merkleTree: null,
           compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
           noop: SPL_NOOP_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          citizenRecord: null,
         })
         .rpc();
 
@@ -117,6 +272,77 @@ describe("zk-census", () => {
       expect(censusState.leafCount.toNumber()).to.equal(1);
     });
 
+    it("should reject registration once max_citizens is reached", async () => {
+      await program.methods
+        .setMaxCitizens(new anchor.BN(1))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const identityCommitment = Buffer.alloc(32);
+      crypto.getRandomValues(identityCommitment);
+
+      try {
+        await program.methods
+          .registerCitizen([...identityCommitment], 1)
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            merkleTree: null,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            noop: SPL_NOOP_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            citizenRecord: null,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown RegistrationCapReached error");
+      } catch (err: any) {
+        expect(err.message).to.include("RegistrationCapReached");
+      } finally {
+        await program.methods
+          .setMaxCitizens(new anchor.BN(0))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should reject unsupported commitment versions", async () => {
+      const identityCommitment = Buffer.alloc(32);
+      crypto.getRandomValues(identityCommitment);
+
+      try {
+        await program.methods
+          .registerCitizen([...identityCommitment], 2)
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            merkleTree: null,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            noop: SPL_NOOP_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            citizenRecord: null,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown UnsupportedCommitmentVersion error");
+      } catch (err: any) {
+        expect(err.message).to.include("UnsupportedCommitmentVersion");
+      }
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.supportedCommitmentVersions).to.equal(1 << 1);
+    });
+
+    it("should report the tree's remaining capacity", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const remaining = await program.methods
+        .treeCapacityRemaining()
+        .accounts({ censusState: censusStatePda })
+        .view();
+
+      expect(remaining.toNumber()).to.equal(2 ** TREE_DEPTH - state.leafCount.toNumber());
+    });
+
     it("should reject non-admin registration attempts", async () => {
       const fakeAdmin = Keypair.generate();
       
@@ -132,14 +358,15 @@ describe("zk-census", () => {
 
       try {
         await program.methods
-          .registerCitizen([...identityCommitment])
+          .registerCitizen([...identityCommitment], 1)
           .accounts({
             admin: fakeAdmin.publicKey,
             censusState: censusStatePda,
-            merkleTree: merkleTreePda,
+            merkleTree: null,
             compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
             noop: SPL_NOOP_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
+            citizenRecord: null,
           })
           .signers([fakeAdmin])
           .rpc();
@@ -188,13 +415,22 @@ describe("zk-census", () => {
             [...proofA],
             [...proofB],
             [...proofC],
-            publicInputs.map(p => [...p]) as any
+            publicInputs.map(p => [...p]) as any,
+            null,
+            null,
+            null,
+            null
           )
           .accounts({
             payer: admin.publicKey,
             censusState: censusStatePda,
             nullifier: nullifierPda,
             systemProgram: SystemProgram.programId,
+            numericAggregate: null,
+            previousScopeSnapshot: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            aggregate: null,
           })
           .rpc();
       } catch (err: any) {
@@ -202,6 +438,73 @@ describe("zk-census", () => {
         console.log("Expected error with mock proof:", err.message);
       }
     });
+
+    it("should fit submit_census within the recommended compute budget", async () => {
+      // Curve generators pass point validation, so the full pairing runs
+      // before the (wrong) proof is rejected, costing what a real proof does
+      const be = (value: string) => new anchor.BN(value).toArrayLike(Buffer, "be", 32);
+      const g1 = Buffer.concat([be("1"), be("2")]);
+      const g2 = Buffer.concat([
+        be("10857046999023057135944570762232829481370756359578518086990519993285655852781"),
+        be("11559732032986387107991004021392285783925812861821192530917403151452391805634"),
+        be("8495653923123431417604973247489272438418190587263600148770280649306958101930"),
+        be("4082367875863433681332203403145435568316851327593401208105741076214120093531"),
+      ]);
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const nullifierHash = Buffer.alloc(32);
+      crypto.getRandomValues(nullifierHash);
+      nullifierHash[0] = 0; // keep it below the field modulus
+      const externalNullifier = Buffer.alloc(32);
+      state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+      const publicInputs = [
+        Buffer.from(state.merkleRoot),
+        nullifierHash,
+        be("1"),
+        externalNullifier,
+      ];
+      const [nullifierPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nullifier"), nullifierHash],
+        program.programId
+      );
+
+      const tx = await program.methods
+        .submitCensus(
+          [...g1],
+          [...g2],
+          [...g1],
+          publicInputs.map(p => [...p]) as any,
+          null,
+          null,
+          null,
+          null
+        )
+        .accounts({
+          payer: admin.publicKey,
+          censusState: censusStatePda,
+          nullifier: nullifierPda,
+          systemProgram: SystemProgram.programId,
+          numericAggregate: null,
+          previousScopeSnapshot: null,
+          payerCooldown: null,
+          nullifierBitmap: null,
+          aggregate: null,
+        })
+        .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 1_400_000 })])
+        .transaction();
+      tx.feePayer = admin.publicKey;
+      tx.recentBlockhash = (await provider.connection.getLatestBlockhash()).blockhash;
+
+      const simulation = await provider.connection.simulateTransaction(
+        await admin.signTransaction(tx)
+      );
+      const logs = simulation.value.logs ?? [];
+      expect(logs.join("\n")).to.include("Error Code: InvalidProof.");
+      console.log("submit_census compute units:", simulation.value.unitsConsumed);
+      expect(simulation.value.unitsConsumed).to.be.at.most(
+        RECOMMENDED_COMPUTE_UNITS_SUBMIT_CENSUS
+      );
+    });
   });
 
   describe("Advance Scope", () => {
@@ -209,19 +512,3060 @@ describe("zk-census", () => {
       const stateBefore = await program.account.censusState.fetch(censusStatePda);
       const scopeBefore = stateBefore.currentScope.toNumber();
 
-      const tx = await program.methods
-        .advanceScope()
-        .accounts({
-          admin: admin.publicKey,
-          censusState: censusStatePda,
-        })
-        .rpc();
+      const [scopeSnapshotPda] = scopeSnapshotPdaFor(scopeBefore);
+
+      const advance = program.methods.advanceScope().accounts({
+        admin: admin.publicKey,
+        censusState: censusStatePda,
+        scopeSnapshot: scopeSnapshotPda,
+      });
+
+      // The new scope comes back as return data
+      const simulation = await advance.simulate();
+      const prefix = `Program return: ${program.programId.toBase58()} `;
+      const returnLog = simulation.raw.find((log: string) => log.startsWith(prefix));
+      const result = program.coder.types.decode(
+        "ScopeAdvanceResult",
+        Buffer.from(returnLog.slice(prefix.length), "base64")
+      );
+      expect(result.newScope.toNumber()).to.equal(scopeBefore + 1);
+      expect(result.finalPopulation.toNumber()).to.equal(
+        stateBefore.currentPopulation.toNumber()
+      );
+
+      const tx = await advance.rpc();
 
       console.log("Advance scope tx:", tx);
 
       const stateAfter = await program.account.censusState.fetch(censusStatePda);
       expect(stateAfter.currentScope.toNumber()).to.equal(scopeBefore + 1);
       expect(stateAfter.currentPopulation.toNumber()).to.equal(0);
+
+      const snapshot = await program.account.scopeSnapshot.fetch(scopeSnapshotPda);
+      expect(snapshot.scope.toNumber()).to.equal(scopeBefore);
+      expect(snapshot.finalPopulation.toNumber()).to.equal(
+        stateBefore.currentPopulation.toNumber()
+      );
+      expect(snapshot.openedAt.toNumber()).to.equal(stateBefore.scopeStartTime.toNumber());
+      expect(snapshot.closingMerkleRoot).to.deep.equal(stateBefore.merkleRoot);
+      expect(snapshot.nullifierAccumulator).to.deep.equal(
+        stateBefore.runningNullifierAccumulator
+      );
+      expect(stateAfter.runningNullifierAccumulator).to.deep.equal(new Array(32).fill(0));
+    });
+
+    it("should read a closed scope's snapshot", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const closedScope = state.currentScope.toNumber() - 1;
+      const [scopeSnapshotPda] = scopeSnapshotPdaFor(closedScope);
+
+      const summary = await program.methods
+        .readScopeSnapshot(new anchor.BN(closedScope))
+        .accounts({ scopeSnapshot: scopeSnapshotPda })
+        .view();
+
+      expect(summary.totalRegisteredAtClose.toNumber()).to.equal(
+        state.totalRegistered.toNumber()
+      );
+    });
+
+    it("should reject reading a scope that was never closed", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const openScope = state.currentScope.toNumber();
+      const [scopeSnapshotPda] = scopeSnapshotPdaFor(openScope);
+
+      try {
+        await program.methods
+          .readScopeSnapshot(new anchor.BN(openScope))
+          .accounts({ scopeSnapshot: scopeSnapshotPda })
+          .view();
+
+        expect.fail("Should have thrown SnapshotNotFound error");
+      } catch (err: any) {
+        expect(err.message).to.include("SnapshotNotFound");
+      }
+    });
+
+    it("should reject reading an aggregate that doesn't exist", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const [aggregatePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("aggregate"), state.currentScope.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .readAggregate(state.currentScope)
+          .accounts({ aggregate: aggregatePda })
+          .view();
+
+        expect.fail("Should have thrown AggregateNotFound error");
+      } catch (err: any) {
+        expect(err.message).to.include("AggregateNotFound");
+      }
+    });
+
+    it("should report the fresh scope as not expired", async () => {
+      const expiry = await program.methods
+        .isScopeExpired()
+        .accounts({ censusState: censusStatePda })
+        .view();
+
+      expect(expiry.isExpired).to.be.false;
+      expect(expiry.secondsUntilExpiry.toNumber()).to.be.greaterThan(0);
+    });
+
+    it("should report the next scope boundary", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const boundary = await program.methods
+        .nextScopeBoundary()
+        .accounts({ censusState: censusStatePda })
+        .view();
+
+      expect(boundary.currentScope.toNumber()).to.equal(state.currentScope.toNumber());
+      expect(boundary.nextBoundary.toNumber()).to.equal(
+        state.scopeStartTime.add(state.scopeDuration).toNumber()
+      );
+    });
+
+    it("should reject try_advance_scope before the scope expires", async () => {
+      try {
+        await program.methods
+          .tryAdvanceScope()
+          .accounts({
+            caller: admin.publicKey,
+            censusState: censusStatePda,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown ScopeNotExpired error");
+      } catch (err: any) {
+        expect(err.message).to.include("ScopeNotExpired");
+      }
+    });
+
+    it("should reject roll_scope before the scope expires", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const nextScope = state.currentScope.addn(1);
+      try {
+        await program.methods
+          .rollScope(nextScope)
+          .accounts({
+            caller: admin.publicKey,
+            censusState: censusStatePda,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown ScopeNotExpired error");
+      } catch (err: any) {
+        expect(err.message).to.include("ScopeNotExpired");
+      }
+    });
+  });
+
+  describe("Verifier Heartbeat", () => {
+    it("should record a heartbeat from an authorized verifier", async () => {
+      const verifier = Keypair.generate();
+      const airdropSig = await provider.connection.requestAirdrop(
+        verifier.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      await program.methods
+        .addVerifier(verifier.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const [verifierStatsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("verifier_stats"), verifier.publicKey.toBuffer()],
+        program.programId
+      );
+
+      await program.methods
+        .verifierHeartbeat()
+        .accounts({ verifier: verifier.publicKey, censusState: censusStatePda })
+        .signers([verifier])
+        .rpc();
+
+      const stats = await program.account.verifierStats.fetch(verifierStatsPda);
+      expect(stats.verifier.toBase58()).to.equal(verifier.publicKey.toBase58());
+      expect(stats.lastSeen.toNumber()).to.be.greaterThan(0);
+
+      await program.methods
+        .removeVerifier(verifier.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+    });
+
+    it("should list authorized verifiers with the threshold", async () => {
+      const verifier = Keypair.generate();
+      await program.methods
+        .addVerifier(verifier.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const list = await program.methods
+        .listVerifiers()
+        .accounts({ censusState: censusStatePda })
+        .view();
+
+      expect(list.verifiers.map((v: PublicKey) => v.toBase58())).to.include(
+        verifier.publicKey.toBase58()
+      );
+      expect(list.verifierThreshold).to.equal(1);
+
+      await program.methods
+        .removeVerifier(verifier.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+    });
+
+    it("should rotate a verifier key in place", async () => {
+      const oldVerifier = Keypair.generate();
+      const newVerifier = Keypair.generate();
+      await program.methods
+        .addVerifier(oldVerifier.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      await program.methods
+        .rotateVerifier(oldVerifier.publicKey, newVerifier.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const active = state.authorizedVerifiers
+        .slice(0, state.verifierCount)
+        .map((v: PublicKey) => v.toBase58());
+      expect(active).to.include(newVerifier.publicKey.toBase58());
+      expect(active).to.not.include(oldVerifier.publicKey.toBase58());
+
+      try {
+        await program.methods
+          .rotateVerifier(oldVerifier.publicKey, Keypair.generate().publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown VerifierNotAuthorized error");
+      } catch (err: any) {
+        expect(err.message).to.include("VerifierNotAuthorized");
+      }
+
+      await program.methods
+        .removeVerifier(newVerifier.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+    });
+
+    it("should deprecate a verifier with a grace period", async () => {
+      const verifier = Keypair.generate();
+      await program.methods
+        .addVerifier(verifier.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        await program.methods
+          .deprecateVerifier(verifier.publicKey, new anchor.BN(3600))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        const state = await program.account.censusState.fetch(censusStatePda);
+        const index = state.authorizedVerifiers
+          .slice(0, state.verifierCount)
+          .findIndex((v: PublicKey) => v.equals(verifier.publicKey));
+        const acceptedUntil = state.verifierAcceptedUntil[index].toNumber();
+        expect(acceptedUntil).to.be.greaterThan(Math.floor(Date.now() / 1000));
+
+        try {
+          await program.methods
+            .deprecateVerifier(verifier.publicKey, new anchor.BN(-1))
+            .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+            .rpc();
+
+          expect.fail("Should have thrown InvalidVerifierGrace error");
+        } catch (err: any) {
+          expect(err.message).to.include("InvalidVerifierGrace");
+        }
+      } finally {
+        await program.methods
+          .removeVerifier(verifier.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should export the verification key with its hash", async () => {
+      const vkey = await program.methods
+        .getVerificationKey()
+        .accounts({ censusState: censusStatePda })
+        .view();
+
+      const digest = createHash("sha256")
+        .update(Buffer.from(vkey.alpha))
+        .update(Buffer.from(vkey.beta))
+        .update(Buffer.from(vkey.gamma))
+        .update(Buffer.from(vkey.delta));
+      vkey.ic.forEach((point: number[]) => digest.update(Buffer.from(point)));
+      expect(Buffer.from(vkey.hash).toString("hex")).to.equal(digest.digest("hex"));
+      expect(vkey.ic.length).to.equal(5);
+    });
+
+    it("should default the attested circuit hash to the verification key hash", async () => {
+      const vkey = await program.methods
+        .getVerificationKey()
+        .accounts({ censusState: censusStatePda })
+        .view();
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.circuitHash).to.deep.equal(vkey.hash);
+
+      const circuitHash = Array(32).fill(0x42);
+      await program.methods
+        .setCircuitHash(circuitHash)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+      const updated = await program.account.censusState.fetch(censusStatePda);
+      expect(updated.circuitHash).to.deep.equal(circuitHash);
+
+      await program.methods
+        .setCircuitHash(state.circuitHash)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+    });
+
+    it("should import the initial verifier set in one call", async () => {
+      const first = Keypair.generate().publicKey;
+      const second = Keypair.generate().publicKey;
+
+      for (const [verifiers, threshold, error] of [
+        [[first, first], 1, "VerifierAlreadyAuthorized"],
+        [[first, second], 3, "InvalidVerifierThreshold"],
+      ] as [PublicKey[], number, string][]) {
+        try {
+          await program.methods
+            .initVerifiers(verifiers, threshold)
+            .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+            .rpc();
+
+          expect.fail(`Should have thrown ${error} error`);
+        } catch (err: any) {
+          expect(err.message).to.include(error);
+        }
+      }
+
+      await program.methods
+        .initVerifiers([first, second], 2)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.verifierCount).to.equal(2);
+      expect(state.verifierThreshold).to.equal(2);
+
+      try {
+        await program.methods
+          .initVerifiers([Keypair.generate().publicKey], 1)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown VerifiersAlreadyInitialized error");
+      } catch (err: any) {
+        expect(err.message).to.include("VerifiersAlreadyInitialized");
+      }
+
+      await program.methods
+        .setVerifierThreshold(1)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+      for (const verifier of [first, second]) {
+        await program.methods
+          .removeVerifier(verifier)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should require signer weight to reach the weight threshold", async () => {
+      const hardwareVerifier = Keypair.generate().publicKey;
+      for (const verifier of [admin.publicKey, hardwareVerifier]) {
+        await program.methods
+          .addVerifier(verifier)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+
+      try {
+        for (const [call, error] of [
+          [program.methods.setVerifierWeight(hardwareVerifier, 0), "InvalidVerifierWeight"],
+          [program.methods.setWeightThreshold(5), "InvalidVerifierThreshold"],
+        ] as [any, string][]) {
+          try {
+            await call.accounts({ admin: admin.publicKey, censusState: censusStatePda }).rpc();
+            expect.fail(`Should have thrown ${error} error`);
+          } catch (err: any) {
+            expect(err.message).to.include(error);
+          }
+        }
+
+        await program.methods
+          .setVerifierWeight(hardwareVerifier, 3)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+        await program.methods
+          .setWeightThreshold(3)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        const list = await program.methods
+          .listVerifiers()
+          .accounts({ censusState: censusStatePda })
+          .view();
+        expect(list.verifierWeights).to.deep.equal([1, 3]);
+        expect(list.weightThreshold).to.equal(3);
+
+        // The admin alone meets verifier_threshold = 1 but carries weight 1 of 3
+        try {
+          await submitAttestationFor(await buildAttestation()).rpc();
+          expect.fail("Should have thrown InsufficientVerifierWeight error");
+        } catch (err: any) {
+          expect(err.message).to.include("InsufficientVerifierWeight");
+        }
+      } finally {
+        await program.methods
+          .setWeightThreshold(0)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+        for (const verifier of [admin.publicKey, hardwareVerifier]) {
+          await program.methods
+            .removeVerifier(verifier)
+            .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+            .rpc();
+        }
+      }
+    });
+
+    it("should reject a heartbeat from an unauthorized verifier", async () => {
+      try {
+        await program.methods
+          .verifierHeartbeat()
+          .accounts({ verifier: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown VerifierNotAuthorized error");
+      } catch (err: any) {
+        expect(err.message).to.include("VerifierNotAuthorized");
+      }
+    });
+  });
+
+  describe("Submit Attestation", () => {
+    it("should accept an attestation where payer and verifier are the same key", async () => {
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const state = await program.account.censusState.fetch(censusStatePda);
+        const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+        const nonce = Buffer.alloc(16);
+        crypto.getRandomValues(nonce);
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        nullifierHash[0] = 0; // keep it below the field modulus
+        const externalNullifier = Buffer.alloc(32);
+        state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const signalHash = Buffer.alloc(32);
+        signalHash[31] = 1;
+
+        // [version, timestamp, nonce, root, nullifier, external, signal, circuit hash]
+        const message = Buffer.concat([
+          Buffer.from([0x04]),
+          timestamp.toArrayLike(Buffer, "le", 8),
+          nonce,
+          Buffer.from(state.merkleRoot),
+          nullifierHash,
+          externalNullifier,
+          signalHash,
+          Buffer.from(state.circuitHash),
+        ]);
+        const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: (admin as anchor.Wallet).payer.secretKey,
+          message,
+        });
+        const signature = ed25519Ix.data.subarray(48, 112);
+
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+
+        await program.methods
+          .submitAttestation(
+            timestamp,
+            [...nonce],
+            [...state.merkleRoot],
+            [...nullifierHash],
+            [...externalNullifier],
+            [...signalHash],
+            [...signature],
+            null,
+            null,
+            null
+          )
+          .accounts({
+            payer: admin.publicKey,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            previousScopeSnapshot: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            merkleTree: null,
+            compressionProgram: null,
+            verifierRegistry: null,
+            citizenRecord: null,
+            aggregate: null,
+          })
+          .preInstructions([ed25519Ix])
+          .rpc();
+
+        const nullifierInfo = await provider.connection.getAccountInfo(nullifierPda);
+        const after = await program.account.censusState.fetch(censusStatePda);
+        expect(nullifierInfo.owner.toBase58()).to.equal(program.programId.toBase58());
+        expect(after.currentPopulation.toNumber()).to.equal(
+          state.currentPopulation.toNumber() + 1
+        );
+
+        // A racer submitting the same nullifier fails before proof
+        // verification, whatever its proof
+        try {
+          await program.methods
+            .submitCensus(
+              [...Buffer.alloc(64)],
+              [...Buffer.alloc(128)],
+              [...Buffer.alloc(64)],
+              [state.merkleRoot, [...nullifierHash], [...signalHash], [...externalNullifier]],
+              null,
+              null,
+              null,
+              null
+            )
+            .accounts({
+              payer: admin.publicKey,
+              censusState: censusStatePda,
+              nullifier: nullifierPda,
+              systemProgram: SystemProgram.programId,
+              numericAggregate: null,
+              previousScopeSnapshot: null,
+              payerCooldown: null,
+              nullifierBitmap: null,
+              aggregate: null,
+            })
+            .rpc();
+
+          expect.fail("Should have thrown NullifierAlreadyUsed error");
+        } catch (err: any) {
+          expect(err.message).to.include("NullifierAlreadyUsed");
+        }
+      } finally {
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should not count one nullifier via both paths, whatever the signal", async () => {
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const state = await program.account.censusState.fetch(censusStatePda);
+        const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+        const nonce = Buffer.alloc(16);
+        crypto.getRandomValues(nonce);
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        nullifierHash[0] = 0; // keep it below the field modulus
+        const externalNullifier = Buffer.alloc(32);
+        state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const signalA = Buffer.alloc(32);
+        signalA[31] = 1;
+        const signalB = Buffer.alloc(32);
+        signalB[31] = 2;
+
+        const message = Buffer.concat([
+          Buffer.from([0x04]),
+          timestamp.toArrayLike(Buffer, "le", 8),
+          nonce,
+          Buffer.from(state.merkleRoot),
+          nullifierHash,
+          externalNullifier,
+          signalA,
+          Buffer.from(state.circuitHash),
+        ]);
+        const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: (admin as anchor.Wallet).payer.secretKey,
+          message,
+        });
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+
+        // Count via the attestation path with signal A
+        await program.methods
+          .submitAttestation(
+            timestamp,
+            [...nonce],
+            [...state.merkleRoot],
+            [...nullifierHash],
+            [...externalNullifier],
+            [...signalA],
+            [...ed25519Ix.data.subarray(48, 112)],
+            null,
+            null,
+            null
+          )
+          .accounts({
+            payer: admin.publicKey,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            previousScopeSnapshot: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            merkleTree: null,
+            compressionProgram: null,
+            verifierRegistry: null,
+            citizenRecord: null,
+            aggregate: null,
+          })
+          .preInstructions([ed25519Ix])
+          .rpc();
+
+        // The proof path with signal B hits the same nullifier PDA
+        try {
+          await program.methods
+            .submitCensus(
+              [...Buffer.alloc(64)],
+              [...Buffer.alloc(128)],
+              [...Buffer.alloc(64)],
+              [state.merkleRoot, [...nullifierHash], [...signalB], [...externalNullifier]],
+              null,
+              null,
+              null,
+              null
+            )
+            .accounts({
+              payer: admin.publicKey,
+              censusState: censusStatePda,
+              nullifier: nullifierPda,
+              systemProgram: SystemProgram.programId,
+              numericAggregate: null,
+              previousScopeSnapshot: null,
+              payerCooldown: null,
+              nullifierBitmap: null,
+              aggregate: null,
+            })
+            .rpc();
+
+          expect.fail("Should have thrown NullifierAlreadyUsed error");
+        } catch (err: any) {
+          expect(err.message).to.include("NullifierAlreadyUsed");
+        }
+
+        const after = await program.account.censusState.fetch(censusStatePda);
+        expect(after.currentPopulation.toNumber()).to.equal(
+          state.currentPopulation.toNumber() + 1
+        );
+      } finally {
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should reject an attestation without the Ed25519 instruction", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const nullifierHash = Buffer.alloc(32);
+      crypto.getRandomValues(nullifierHash);
+      const externalNullifier = Buffer.alloc(32);
+      state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+      const [nullifierPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nullifier"), nullifierHash],
+        program.programId
+      );
+
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        await program.methods
+          .submitAttestation(
+            new anchor.BN(Math.floor(Date.now() / 1000)),
+            Array(16).fill(1),
+            [...state.merkleRoot],
+            [...nullifierHash],
+            [...externalNullifier],
+            Array(32).fill(0),
+            Array(64).fill(0),
+            null,
+            null,
+            null
+          )
+          .accounts({
+            payer: admin.publicKey,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            previousScopeSnapshot: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            merkleTree: null,
+            compressionProgram: null,
+            verifierRegistry: null,
+            citizenRecord: null,
+            aggregate: null,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown MissingEd25519Instruction error");
+      } catch (err: any) {
+        expect(err.message).to.include("MissingEd25519Instruction");
+      } finally {
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should reject a payer-bound attestation from another payer", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const nullifierHash = Buffer.alloc(32);
+      crypto.getRandomValues(nullifierHash);
+      const externalNullifier = Buffer.alloc(32);
+      state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+      const [nullifierPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nullifier"), nullifierHash],
+        program.programId
+      );
+
+      try {
+        await program.methods
+          .submitAttestation(
+            new anchor.BN(Math.floor(Date.now() / 1000)),
+            Array(16).fill(2),
+            [...state.merkleRoot],
+            [...nullifierHash],
+            [...externalNullifier],
+            Array(32).fill(0),
+            Array(64).fill(0),
+            null,
+            Keypair.generate().publicKey,
+            null
+          )
+          .accounts({
+            payer: admin.publicKey,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            previousScopeSnapshot: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            merkleTree: null,
+            compressionProgram: null,
+            verifierRegistry: null,
+            citizenRecord: null,
+            aggregate: null,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown AttestationPayerMismatch error");
+      } catch (err: any) {
+        expect(err.message).to.include("AttestationPayerMismatch");
+      }
+    });
+
+    it("should report why an attestation would fail via dry_run_attestation", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const nullifierHash = Buffer.alloc(32);
+      crypto.getRandomValues(nullifierHash);
+      const externalNullifier = Buffer.alloc(32);
+      state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+      const [nullifierPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nullifier"), nullifierHash],
+        program.programId
+      );
+
+      const result = await program.methods
+        .dryRunAttestation(
+          new anchor.BN(Math.floor(Date.now() / 1000)),
+          Array(16).fill(3),
+          Array(32).fill(0xee), // stale root
+          [...nullifierHash],
+          [...externalNullifier],
+          Array(32).fill(0),
+          Array(64).fill(0),
+          null,
+          null
+        )
+        .accounts({
+          payer: admin.publicKey,
+          verifier: admin.publicKey,
+          censusState: censusStatePda,
+          nullifier: nullifierPda,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          previousScopeSnapshot: null,
+          verifierRegistry: null,
+          payerCooldown: null,
+          nullifierBitmap: null,
+          merkleTree: null,
+          compressionProgram: null,
+          citizenRecord: null,
+          aggregate: null,
+        })
+        .view();
+
+      const mismatch = program.idl.errors.find(e => e.name === "MerkleRootMismatch");
+      expect(result.passed).to.be.false;
+      expect(result.errorCode).to.equal(mismatch.code);
+    });
+
+    it("should snapshot counts bundled with advance_scope in one transaction", async () => {
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const state = await program.account.censusState.fetch(censusStatePda);
+        const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+        const nonce = Buffer.alloc(16);
+        crypto.getRandomValues(nonce);
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        nullifierHash[0] = 0; // keep it below the field modulus
+        const externalNullifier = Buffer.alloc(32);
+        state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const signalHash = Buffer.alloc(32);
+
+        const message = Buffer.concat([
+          Buffer.from([0x04]),
+          timestamp.toArrayLike(Buffer, "le", 8),
+          nonce,
+          Buffer.from(state.merkleRoot),
+          nullifierHash,
+          externalNullifier,
+          signalHash,
+          Buffer.from(state.circuitHash),
+        ]);
+        const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: (admin as anchor.Wallet).payer.secretKey,
+          message,
+        });
+        const signature = ed25519Ix.data.subarray(48, 112);
+
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+        const scope = state.currentScope.toNumber();
+        const [scopeSnapshotPda] = scopeSnapshotPdaFor(scope);
+
+        const advanceIx = await program.methods
+          .advanceScope()
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            scopeSnapshot: scopeSnapshotPda,
+          })
+          .instruction();
+
+        // [Ed25519, submit_attestation, advance_scope]
+        await program.methods
+          .submitAttestation(
+            timestamp,
+            [...nonce],
+            [...state.merkleRoot],
+            [...nullifierHash],
+            [...externalNullifier],
+            [...signalHash],
+            [...signature],
+            null,
+            null,
+            null
+          )
+          .accounts({
+            payer: admin.publicKey,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            previousScopeSnapshot: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            merkleTree: null,
+            compressionProgram: null,
+            verifierRegistry: null,
+            citizenRecord: null,
+            aggregate: null,
+          })
+          .preInstructions([ed25519Ix])
+          .postInstructions([advanceIx])
+          .rpc();
+
+        const snapshot = await program.account.scopeSnapshot.fetch(scopeSnapshotPda);
+        expect(snapshot.scope.toNumber()).to.equal(scope);
+        expect(snapshot.finalPopulation.toNumber()).to.equal(
+          state.currentPopulation.toNumber() + 1
+        );
+      } finally {
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("CPI", () => {
+    it("should count an attestation relayed by another program's PDA", async () => {
+      const [relayerPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("relayer")],
+        caller.programId
+      );
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: admin.publicKey,
+            toPubkey: relayerPda,
+            lamports: LAMPORTS_PER_SOL / 10,
+          })
+        )
+      );
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const state = await program.account.censusState.fetch(censusStatePda);
+        const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+        const nonce = Buffer.alloc(16);
+        crypto.getRandomValues(nonce);
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        nullifierHash[0] = 0; // keep it below the field modulus
+        const externalNullifier = Buffer.alloc(32);
+        state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const signalHash = Buffer.alloc(32);
+
+        const message = Buffer.concat([
+          Buffer.from([0x04]),
+          timestamp.toArrayLike(Buffer, "le", 8),
+          nonce,
+          Buffer.from(state.merkleRoot),
+          nullifierHash,
+          externalNullifier,
+          signalHash,
+          Buffer.from(state.circuitHash),
+        ]);
+        const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: (admin as anchor.Wallet).payer.secretKey,
+          message,
+        });
+        const signature = ed25519Ix.data.subarray(48, 112);
+
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+
+        await caller.methods
+          .relayAttestation(
+            timestamp,
+            [...nonce],
+            [...state.merkleRoot],
+            [...nullifierHash],
+            [...externalNullifier],
+            [...signalHash],
+            [...signature]
+          )
+          .accounts({
+            relayer: relayerPda,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            censusProgram: program.programId,
+          })
+          .preInstructions([ed25519Ix])
+          .rpc();
+
+        const nullifierInfo = await provider.connection.getAccountInfo(nullifierPda);
+        const after = await program.account.censusState.fetch(censusStatePda);
+        expect(nullifierInfo.owner.toBase58()).to.equal(program.programId.toBase58());
+        expect(after.currentPopulation.toNumber()).to.equal(
+          state.currentPopulation.toNumber() + 1
+        );
+      } finally {
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Registration Freeze", () => {
+    it("should reject registrations after the scope's first count while frozen", async () => {
+      // The CPI test above counted into the current scope
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.currentPopulation.toNumber()).to.be.greaterThan(0);
+
+      await program.methods
+        .setFreezeRegistrationsDuringScope(true)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const identityCommitment = Buffer.alloc(32);
+        crypto.getRandomValues(identityCommitment);
+        await program.methods
+          .registerCitizen([...identityCommitment], 1)
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            merkleTree: null,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            noop: SPL_NOOP_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            citizenRecord: null,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown RegistrationsFrozen error");
+      } catch (err: any) {
+        expect(err.message).to.include("RegistrationsFrozen");
+      } finally {
+        await program.methods
+          .setFreezeRegistrationsDuringScope(false)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Set Merkle Root", () => {
+    it("should check the root against the tree account when one is passed", async () => {
+      // No tree is linked, so the only account matching census_state.merkle_tree
+      // is the default pubkey, which isn't a compression tree
+      const state = await program.account.censusState.fetch(censusStatePda);
+      try {
+        await program.methods
+          .setMerkleRoot(state.merkleRoot)
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            merkleTree: state.merkleTree,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidMerkleTree error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidMerkleTree");
+      }
+    });
+  });
+
+  describe("Root Pinning", () => {
+    it("should validate against the pinned scope root while pinning is on", async () => {
+      await program.methods
+        .setPinRootPerScope(true)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+      const pinned = await program.account.censusState.fetch(censusStatePda);
+      expect(pinned.scopeRoot).to.deep.equal(pinned.merkleRoot);
+
+      const liveRoot = Array(32).fill(0x5a);
+      await program.methods
+        .setMerkleRoot(liveRoot)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda, merkleTree: null })
+        .rpc();
+
+      try {
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        const externalNullifier = Buffer.alloc(32);
+        pinned.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+        const dryRun = (root: number[]) =>
+          program.methods
+            .dryRunAttestation(
+              new anchor.BN(Math.floor(Date.now() / 1000)),
+              Array(16).fill(4),
+              root,
+              [...nullifierHash],
+              [...externalNullifier],
+              Array(32).fill(0),
+              Array(64).fill(0),
+              null,
+              null
+            )
+            .accounts({
+              payer: admin.publicKey,
+              verifier: admin.publicKey,
+              censusState: censusStatePda,
+              nullifier: nullifierPda,
+              instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+              previousScopeSnapshot: null,
+              verifierRegistry: null,
+              payerCooldown: null,
+              nullifierBitmap: null,
+              merkleTree: null,
+              compressionProgram: null,
+              citizenRecord: null,
+              aggregate: null,
+            })
+            .view();
+
+        const mismatch = program.idl.errors.find(e => e.name === "MerkleRootMismatch");
+        expect((await dryRun(liveRoot)).errorCode).to.equal(mismatch.code);
+        expect((await dryRun(pinned.scopeRoot)).errorCode).to.not.equal(mismatch.code);
+      } finally {
+        await program.methods
+          .setMerkleRoot(pinned.merkleRoot)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda, merkleTree: null })
+          .rpc();
+        await program.methods
+          .setPinRootPerScope(false)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Sealed Scopes", () => {
+    it("should reject grace-window counts into a closed scope while sealing is on", async () => {
+      await program.methods
+        .setScopeGraceSeconds(new anchor.BN(3600))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+      await program.methods
+        .setSealClosedScopes(true)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const before = await program.account.censusState.fetch(censusStatePda);
+        const closedScope = before.currentScope.toNumber();
+        const [scopeSnapshotPda] = scopeSnapshotPdaFor(closedScope);
+        await program.methods
+          .advanceScope()
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            scopeSnapshot: scopeSnapshotPda,
+          })
+          .rpc();
+
+        const after = await program.account.censusState.fetch(censusStatePda);
+        expect(after.lastClosedScope.toNumber()).to.equal(closedScope);
+
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        const externalNullifier = Buffer.alloc(32);
+        before.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+        const dryRun = await program.methods
+          .dryRunAttestation(
+            new anchor.BN(Math.floor(Date.now() / 1000)),
+            Array(16).fill(5),
+            after.merkleRoot,
+            [...nullifierHash],
+            [...externalNullifier],
+            Array(32).fill(0),
+            Array(64).fill(0),
+            null,
+            null
+          )
+          .accounts({
+            payer: admin.publicKey,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            previousScopeSnapshot: scopeSnapshotPda,
+            verifierRegistry: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            merkleTree: null,
+            compressionProgram: null,
+            citizenRecord: null,
+            aggregate: null,
+          })
+          .view();
+
+        const sealed = program.idl.errors.find(e => e.name === "ScopeSealed");
+        expect(dryRun.errorCode).to.equal(sealed.code);
+      } finally {
+        await program.methods
+          .setSealClosedScopes(false)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+        await program.methods
+          .setScopeGraceSeconds(new anchor.BN(0))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Counting Open", () => {
+    it("should reject submissions but keep registrations open while counting is closed", async () => {
+      await program.methods
+        .closeCounting()
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const identityCommitment = Buffer.alloc(32);
+        crypto.getRandomValues(identityCommitment);
+        await program.methods
+          .registerCitizen([...identityCommitment], 1)
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            merkleTree: null,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            noop: SPL_NOOP_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            citizenRecord: null,
+          })
+          .rpc();
+
+        const state = await program.account.censusState.fetch(censusStatePda);
+        expect(state.isActive).to.be.true;
+        expect(state.countingOpen).to.be.false;
+
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+        try {
+          await program.methods
+            .submitCensus(
+              [...Buffer.alloc(64)],
+              [...Buffer.alloc(128)],
+              [...Buffer.alloc(64)],
+              [state.merkleRoot, [...nullifierHash], Array(32).fill(0), Array(32).fill(0)],
+              null,
+              null,
+              null,
+              null
+            )
+            .accounts({
+              payer: admin.publicKey,
+              censusState: censusStatePda,
+              nullifier: nullifierPda,
+              systemProgram: SystemProgram.programId,
+              numericAggregate: null,
+              previousScopeSnapshot: null,
+              payerCooldown: null,
+              nullifierBitmap: null,
+              aggregate: null,
+            })
+            .rpc();
+
+          expect.fail("Should have thrown CountingClosed error");
+        } catch (err: any) {
+          expect(err.message).to.include("CountingClosed");
+        }
+      } finally {
+        await program.methods
+          .openCounting()
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should refuse a proof age bound the verification key can't enforce", async () => {
+      try {
+        await program.methods
+          .setMaxProofAge(new anchor.BN(-1))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidMaxProofAge error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidMaxProofAge");
+      }
+
+      // The deployed key has no participation_timestamp input, so a bound
+      // would make every submit_census fail verification
+      try {
+        await program.methods
+          .setMaxProofAge(new anchor.BN(60))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown ProofAgeUnsupported error");
+      } catch (err: any) {
+        expect(err.message).to.include("ProofAgeUnsupported");
+      }
+
+      await program.methods
+        .setMaxProofAge(new anchor.BN(0))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.maxProofAge.toNumber()).to.equal(0);
+    });
+  });
+
+  describe("Scope Calendar", () => {
+    it("should store the scope epoch and calendar mode", async () => {
+      const before = await program.account.censusState.fetch(censusStatePda);
+      const epoch = new anchor.BN(1_704_067_200); // Monday 2024-01-01 00:00 UTC
+
+      await program.methods
+        .setScopeCalendar(epoch, true)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const state = await program.account.censusState.fetch(censusStatePda);
+        expect(state.scopeEpoch.toNumber()).to.equal(epoch.toNumber());
+        expect(state.calendarScopes).to.be.true;
+      } finally {
+        await program.methods
+          .setScopeCalendar(before.scopeEpoch, false)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should reject a scope epoch in the future", async () => {
+      try {
+        await program.methods
+          .setScopeCalendar(new anchor.BN(Math.floor(Date.now() / 1000) + 86_400), true)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidScopeEpoch error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidScopeEpoch");
+      }
+    });
+  });
+
+  describe("Nullifier Bitmap", () => {
+    it("should require the scope's bitmap once it exists and record counts in it", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const [nullifierBitmapPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nullifier_bitmap"), state.currentScope.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .initNullifierBitmap()
+        .accounts({
+          admin: admin.publicKey,
+          censusState: censusStatePda,
+          nullifierBitmap: nullifierBitmapPda,
+        })
+        .rpc();
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+        const nonce = Buffer.alloc(16);
+        crypto.getRandomValues(nonce);
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        nullifierHash[0] = 0; // keep it below the field modulus
+        const externalNullifier = Buffer.alloc(32);
+        state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const signalHash = Buffer.alloc(32);
+
+        const message = Buffer.concat([
+          Buffer.from([0x04]),
+          timestamp.toArrayLike(Buffer, "le", 8),
+          nonce,
+          Buffer.from(state.merkleRoot),
+          nullifierHash,
+          externalNullifier,
+          signalHash,
+          Buffer.from(state.circuitHash),
+        ]);
+        const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: (admin as anchor.Wallet).payer.secretKey,
+          message,
+        });
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+        const submit = (nullifierBitmap: PublicKey | null) =>
+          program.methods
+            .submitAttestation(
+              timestamp,
+              [...nonce],
+              [...state.merkleRoot],
+              [...nullifierHash],
+              [...externalNullifier],
+              [...signalHash],
+              [...ed25519Ix.data.subarray(48, 112)],
+              null,
+              null,
+              null
+            )
+            .accounts({
+              payer: admin.publicKey,
+              verifier: admin.publicKey,
+              censusState: censusStatePda,
+              nullifier: nullifierPda,
+              instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+              systemProgram: SystemProgram.programId,
+              previousScopeSnapshot: null,
+              payerCooldown: null,
+              nullifierBitmap,
+              citizenRecord: null,
+              aggregate: null,
+            })
+            .preInstructions([ed25519Ix])
+            .rpc();
+
+        try {
+          await submit(null);
+          expect.fail("Should have thrown InvalidNullifierBitmap error");
+        } catch (err: any) {
+          expect(err.message).to.include("InvalidNullifierBitmap");
+        }
+
+        await submit(nullifierBitmapPda);
+        const bitmap = await program.account.nullifierBitmap.fetch(nullifierBitmapPda);
+        expect(bitmap.inserted.toNumber()).to.equal(1);
+        expect(bitmap.bits.some((byte: number) => byte !== 0)).to.be.true;
+      } finally {
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+        // Later tests count without the bitmap, so move past its scope
+        const [scopeSnapshotPda] = scopeSnapshotPdaFor(state.currentScope.toNumber());
+        await program.methods
+          .advanceScope()
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            scopeSnapshot: scopeSnapshotPda,
+          })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Scope Population Cap", () => {
+    it("should store max_population_per_scope", async () => {
+      await program.methods
+        .setMaxPopulationPerScope(new anchor.BN(500))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.maxPopulationPerScope.toNumber()).to.equal(500);
+
+      await program.methods
+        .setMaxPopulationPerScope(new anchor.BN(0))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+    });
+  });
+
+  describe("Population Invariant", () => {
+    it("should toggle enforce_population_invariant", async () => {
+      await program.methods
+        .setEnforcePopulationInvariant(true)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      let state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.enforcePopulationInvariant).to.be.true;
+
+      await program.methods
+        .setEnforcePopulationInvariant(false)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.enforcePopulationInvariant).to.be.false;
+    });
+  });
+
+  describe("Population Milestones", () => {
+    it("should store milestones without firing unreached ones", async () => {
+      const milestones = [1_000_000, 5_000_000, 0, 0, 0].map(m => new anchor.BN(m));
+      await program.methods
+        .setMilestones(milestones)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.milestones.map((m: anchor.BN) => m.toNumber())).to.deep.equal([
+        1_000_000, 5_000_000, 0, 0, 0,
+      ]);
+      expect(state.milestonesFired).to.equal(0);
+    });
+  });
+
+  describe("Census Metadata", () => {
+    const [censusMetadataPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("census_metadata")],
+      program.programId
+    );
+    const padUri = (uri: string) => {
+      const bytes = Buffer.alloc(128);
+      bytes.write(uri, "ascii");
+      return [...bytes];
+    };
+
+    it("should store the metadata URI", async () => {
+      const uri = "https://example.org/census.json";
+      await program.methods
+        .setMetadata(padUri(uri))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const metadata = await program.account.censusMetadata.fetch(censusMetadataPda);
+      expect(Buffer.from(metadata.uri).toString("ascii").replace(/\0+$/, "")).to.equal(uri);
+    });
+
+    it("should reject an empty metadata URI", async () => {
+      try {
+        await program.methods
+          .setMetadata(padUri(""))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidMetadataUri error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidMetadataUri");
+      }
+    });
+  });
+
+  describe("Payer Cooldown", () => {
+    it("should reject a negative payer cooldown", async () => {
+      try {
+        await program.methods
+          .setPayerCooldownSeconds(new anchor.BN(-1))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidPayerCooldown error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidPayerCooldown");
+      }
+    });
+
+    it("should store payer_cooldown_seconds", async () => {
+      await program.methods
+        .setPayerCooldownSeconds(new anchor.BN(60))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.payerCooldownSeconds.toNumber()).to.equal(60);
+
+      await program.methods
+        .setPayerCooldownSeconds(new anchor.BN(0))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+    });
+  });
+
+  describe("Root Validity", () => {
+    it("should reject a negative root validity", async () => {
+      try {
+        await program.methods
+          .setRootValiditySeconds(new anchor.BN(-1))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidRootValidity error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidRootValidity");
+      }
+    });
+
+    it("should store root_validity_seconds", async () => {
+      await program.methods
+        .setRootValiditySeconds(new anchor.BN(3600))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.rootValiditySeconds.toNumber()).to.equal(3600);
+
+      await program.methods
+        .setRootValiditySeconds(new anchor.BN(0))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+    });
+  });
+
+  describe("Signal Mode", () => {
+    it("should reject an unknown signal mode", async () => {
+      try {
+        await program.methods
+          .setSignalMode(2)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidSignalMode error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidSignalMode");
+      }
+    });
+
+    it("should switch to opaque-commit signals and back", async () => {
+      await program.methods
+        .setSignalMode(1)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.signalMode).to.equal(1);
+
+      await program.methods
+        .setSignalMode(0)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+    });
+
+    it("should reject a declined signal while a signal is required", async () => {
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+      await program.methods
+        .setRequireSignal(true)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const state = await program.account.censusState.fetch(censusStatePda);
+        const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+        const nonce = Buffer.alloc(16);
+        crypto.getRandomValues(nonce);
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        nullifierHash[0] = 0; // keep it below the field modulus
+        const externalNullifier = Buffer.alloc(32);
+        state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const signalHash = Buffer.alloc(32); // SIGNAL_DECLINED
+
+        const message = Buffer.concat([
+          Buffer.from([0x04]),
+          timestamp.toArrayLike(Buffer, "le", 8),
+          nonce,
+          Buffer.from(state.merkleRoot),
+          nullifierHash,
+          externalNullifier,
+          signalHash,
+          Buffer.from(state.circuitHash),
+        ]);
+        const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: (admin as anchor.Wallet).payer.secretKey,
+          message,
+        });
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+
+        await program.methods
+          .submitAttestation(
+            timestamp,
+            [...nonce],
+            [...state.merkleRoot],
+            [...nullifierHash],
+            [...externalNullifier],
+            [...signalHash],
+            [...ed25519Ix.data.subarray(48, 112)],
+            null,
+            null,
+            null
+          )
+          .accounts({
+            payer: admin.publicKey,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            previousScopeSnapshot: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            merkleTree: null,
+            compressionProgram: null,
+            verifierRegistry: null,
+            citizenRecord: null,
+            aggregate: null,
+          })
+          .preInstructions([ed25519Ix])
+          .rpc();
+
+        expect.fail("Should have thrown SignalRequired error");
+      } catch (err: any) {
+        expect(err.message).to.include("SignalRequired");
+      } finally {
+        await program.methods
+          .setRequireSignal(false)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should report count-time rejections from dry_run_attestation too", async () => {
+      // A correctly signed attestation passes every check_attestation step;
+      // only the count's require_signal check rejects it
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+      await program.methods
+        .setRequireSignal(true)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const state = await program.account.censusState.fetch(censusStatePda);
+        const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+        const nonce = Buffer.alloc(16);
+        crypto.getRandomValues(nonce);
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        const externalNullifier = Buffer.alloc(32);
+        state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const signalHash = Buffer.alloc(32); // SIGNAL_DECLINED
+
+        const message = Buffer.concat([
+          Buffer.from([0x04]),
+          timestamp.toArrayLike(Buffer, "le", 8),
+          nonce,
+          Buffer.from(state.merkleRoot),
+          nullifierHash,
+          externalNullifier,
+          signalHash,
+          Buffer.from(state.circuitHash),
+        ]);
+        const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: (admin as anchor.Wallet).payer.secretKey,
+          message,
+        });
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+
+        const result = await program.methods
+          .dryRunAttestation(
+            timestamp,
+            [...nonce],
+            [...state.merkleRoot],
+            [...nullifierHash],
+            [...externalNullifier],
+            [...signalHash],
+            [...ed25519Ix.data.subarray(48, 112)],
+            null,
+            null
+          )
+          .accounts({
+            payer: admin.publicKey,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            previousScopeSnapshot: null,
+            verifierRegistry: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            merkleTree: null,
+            compressionProgram: null,
+            citizenRecord: null,
+            aggregate: null,
+          })
+          .preInstructions([ed25519Ix])
+          .view();
+
+        const signalRequired = program.idl.errors.find(e => e.name === "SignalRequired");
+        expect(result.passed).to.be.false;
+        expect(result.errorCode).to.equal(signalRequired.code);
+      } finally {
+        await program.methods
+          .setRequireSignal(false)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Paused Census", () => {
+    it("should reject both submission paths with CensusNotActive while paused", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const nullifierHash = Buffer.alloc(32);
+      crypto.getRandomValues(nullifierHash);
+      const externalNullifier = Buffer.alloc(32);
+      state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+      const [nullifierPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nullifier"), nullifierHash],
+        program.programId
+      );
+
+      await program.methods
+        .pauseCensus()
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const attempts = [
+          program.methods
+            .submitAttestation(
+              new anchor.BN(Math.floor(Date.now() / 1000)),
+              Array(16).fill(6),
+              [...state.merkleRoot],
+              [...nullifierHash],
+              [...externalNullifier],
+              Array(32).fill(0),
+              Array(64).fill(0),
+              null,
+              null
+            )
+            .accounts({
+              payer: admin.publicKey,
+              verifier: admin.publicKey,
+              censusState: censusStatePda,
+              nullifier: nullifierPda,
+              instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+              systemProgram: SystemProgram.programId,
+              previousScopeSnapshot: null,
+              payerCooldown: null,
+              nullifierBitmap: null,
+              merkleTree: null,
+              compressionProgram: null,
+              verifierRegistry: null,
+              citizenRecord: null,
+              aggregate: null,
+            }),
+          program.methods
+            .submitCensus(
+              [...Buffer.alloc(64)],
+              [...Buffer.alloc(128)],
+              [...Buffer.alloc(64)],
+              [state.merkleRoot, [...nullifierHash], Array(32).fill(0), [...externalNullifier]],
+              null,
+              null,
+              null,
+              null
+            )
+            .accounts({
+              payer: admin.publicKey,
+              censusState: censusStatePda,
+              nullifier: nullifierPda,
+              systemProgram: SystemProgram.programId,
+              numericAggregate: null,
+              previousScopeSnapshot: null,
+              payerCooldown: null,
+              nullifierBitmap: null,
+              aggregate: null,
+            }),
+        ];
+
+        for (const attempt of attempts) {
+          try {
+            await attempt.rpc();
+            expect.fail("Should have thrown CensusNotActive error");
+          } catch (err: any) {
+            expect(err.message).to.include("CensusNotActive");
+          }
+        }
+      } finally {
+        await program.methods
+          .resumeCensus()
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Adjust Population", () => {
+    it("should reject adjust_population while the census is active", async () => {
+      try {
+        await program.methods
+          .adjustPopulation(new anchor.BN(0), 1)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown CensusNotPaused error");
+      } catch (err: any) {
+        expect(err.message).to.include("CensusNotPaused");
+      }
+    });
+
+    it("should overwrite current_population while paused", async () => {
+      await program.methods
+        .pauseCensus()
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        await program.methods
+          .adjustPopulation(new anchor.BN(42), 1)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        const state = await program.account.censusState.fetch(censusStatePda);
+        expect(state.currentPopulation.toNumber()).to.equal(42);
+      } finally {
+        await program.methods
+          .resumeCensus()
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Strict Membership", () => {
+    it("should refuse strict membership without a linked tree", async () => {
+      // No tree is linked here, so there is nothing to prove leaves against
+      try {
+        await program.methods
+          .setStrictMembership(true)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidMerkleTree error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidMerkleTree");
+      }
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.strictMembership).to.be.false;
+    });
+  });
+
+  describe("Tree Authority", () => {
+    it("should reject set_tree_authority while the census is active", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      try {
+        await program.methods
+          .setTreeAuthority(Keypair.generate().publicKey)
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            merkleTree: state.merkleTree,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown CensusNotPaused error");
+      } catch (err: any) {
+        expect(err.message).to.include("CensusNotPaused");
+      }
+    });
+
+    it("should reject replace_commitment while the census is active", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      try {
+        await program.methods
+          .replaceCommitment(new anchor.BN(0), Array(32).fill(1), Array(32).fill(2))
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            merkleTree: state.merkleTree,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            noop: SPL_NOOP_PROGRAM_ID,
+          })
+          .rpc();
+
+        expect.fail("Should have thrown CensusNotPaused error");
+      } catch (err: any) {
+        expect(err.message).to.include("CensusNotPaused");
+      }
+    });
+  });
+
+  describe("Rotate External Nullifier", () => {
+    it("should open a transition scope on rotation", async () => {
+      const before = await program.account.censusState.fetch(censusStatePda);
+
+      await program.methods
+        .rotateExternalNullifier(1)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.externalNullifierVersion).to.equal(1);
+      expect(state.previousExternalNullifierVersion).to.equal(0);
+      expect(state.externalNullifierTransitionScope.toNumber()).to.equal(
+        before.currentScope.toNumber()
+      );
+    });
+
+    it("should reject rotating to the same or an unknown version", async () => {
+      for (const version of [1, 3]) {
+        try {
+          await program.methods
+            .rotateExternalNullifier(version)
+            .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+            .rpc();
+
+          expect.fail("Should have thrown InvalidExternalNullifierVersion error");
+        } catch (err: any) {
+          expect(err.message).to.include("InvalidExternalNullifierVersion");
+        }
+      }
+    });
+  });
+
+  describe("Membership Expiry", () => {
+    it("should record an expiry at registration and extend it on renewal", async () => {
+      await program.methods
+        .setMembershipDuration(new anchor.BN(3600))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const identityCommitment = Buffer.alloc(32);
+        crypto.getRandomValues(identityCommitment);
+        identityCommitment[0] = 0; // keep it below the field modulus
+        const [citizenRecordPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("citizen_record"), identityCommitment],
+          program.programId
+        );
+        const register = (citizenRecord: PublicKey | null) =>
+          program.methods
+            .registerCitizen([...identityCommitment], 1)
+            .accounts({
+              admin: admin.publicKey,
+              censusState: censusStatePda,
+              merkleTree: null,
+              systemProgram: SystemProgram.programId,
+              citizenRecord,
+            })
+            .rpc();
+
+        try {
+          await register(null);
+          expect.fail("Should have thrown CitizenRecordMissing error");
+        } catch (err: any) {
+          expect(err.message).to.include("CitizenRecordMissing");
+        }
+
+        await register(citizenRecordPda);
+        const registered = await program.account.citizenRecord.fetch(citizenRecordPda);
+        expect(registered.identityCommitment).to.deep.equal([...identityCommitment]);
+        expect(registered.commitmentVersion).to.equal(1);
+        expect(registered.expiresAt.toNumber()).to.be.closeTo(
+          Math.floor(Date.now() / 1000) + 3600,
+          60
+        );
+
+        await program.methods
+          .renewMembership([...identityCommitment])
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            citizenRecord: citizenRecordPda,
+          })
+          .rpc();
+        const renewed = await program.account.citizenRecord.fetch(citizenRecordPda);
+        expect(renewed.expiresAt.toNumber()).to.equal(registered.expiresAt.toNumber() + 3600);
+      } finally {
+        await program.methods
+          .setMembershipDuration(new anchor.BN(0))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Import Genesis", () => {
+    it("should reject import_genesis once citizens are registered", async () => {
+      try {
+        await program.methods
+          .importGenesis(Array(32).fill(7), new anchor.BN(100), new anchor.BN(100))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown GenesisAlreadyImported error");
+      } catch (err: any) {
+        expect(err.message).to.include("GenesisAlreadyImported");
+      }
+    });
+
+    it("should reject more registrations than imported leaves", async () => {
+      try {
+        await program.methods
+          .importGenesis(Array(32).fill(7), new anchor.BN(10), new anchor.BN(11))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidGenesisCounts error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidGenesisCounts");
+      }
+    });
+  });
+
+  describe("Event Replay", () => {
+    // Every mutation of these fields must emit an event an indexer can
+    // replay; a silent state change makes the replay drift from the account
+    it("should reconstruct population, registrations and scope from events", async () => {
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      const events: { name: string; data: any }[] = [];
+      const record = async (signature: string) => {
+        const tx = await provider.connection.getTransaction(signature, {
+          commitment: "confirmed",
+          maxSupportedTransactionVersion: 0,
+        });
+        for (const event of parser.parseLogs(tx.meta.logMessages)) {
+          events.push(event);
+        }
+      };
+
+      const baseline = await program.account.censusState.fetch(censusStatePda);
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        for (let i = 0; i < 3; i++) {
+          const identityCommitment = Buffer.alloc(32);
+          crypto.getRandomValues(identityCommitment);
+          await record(
+            await program.methods
+              .registerCitizen([...identityCommitment], 1)
+              .accounts({
+                admin: admin.publicKey,
+                censusState: censusStatePda,
+                merkleTree: null,
+                compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+                noop: SPL_NOOP_PROGRAM_ID,
+                systemProgram: SystemProgram.programId,
+                citizenRecord: null,
+              })
+              .rpc({ commitment: "confirmed" })
+          );
+        }
+
+        for (let i = 0; i < 2; i++) {
+          const state = await program.account.censusState.fetch(censusStatePda);
+          const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+          const nonce = Buffer.alloc(16);
+          crypto.getRandomValues(nonce);
+          const nullifierHash = Buffer.alloc(32);
+          crypto.getRandomValues(nullifierHash);
+          nullifierHash[0] = 0; // keep it below the field modulus
+          const externalNullifier = Buffer.alloc(32);
+          state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+          const signalHash = Buffer.alloc(32);
+          signalHash[31] = 1;
+
+          const message = Buffer.concat([
+            Buffer.from([0x04]),
+            timestamp.toArrayLike(Buffer, "le", 8),
+            nonce,
+            Buffer.from(state.merkleRoot),
+            nullifierHash,
+            externalNullifier,
+            signalHash,
+            Buffer.from(state.circuitHash),
+          ]);
+          const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: (admin as anchor.Wallet).payer.secretKey,
+            message,
+          });
+          const signature = ed25519Ix.data.subarray(48, 112);
+          const [nullifierPda] = PublicKey.findProgramAddressSync(
+            [Buffer.from("nullifier"), nullifierHash],
+            program.programId
+          );
+
+          await record(
+            await program.methods
+              .submitAttestation(
+                timestamp,
+                [...nonce],
+                [...state.merkleRoot],
+                [...nullifierHash],
+                [...externalNullifier],
+                [...signalHash],
+                [...signature],
+                null,
+                null,
+                null
+              )
+              .accounts({
+                payer: admin.publicKey,
+                verifier: admin.publicKey,
+                censusState: censusStatePda,
+                nullifier: nullifierPda,
+                instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+                systemProgram: SystemProgram.programId,
+                previousScopeSnapshot: null,
+                payerCooldown: null,
+                nullifierBitmap: null,
+                merkleTree: null,
+                compressionProgram: null,
+                verifierRegistry: null,
+                citizenRecord: null,
+                aggregate: null,
+              })
+              .preInstructions([ed25519Ix])
+              .rpc({ commitment: "confirmed" })
+          );
+        }
+
+        const [scopeSnapshotPda] = scopeSnapshotPdaFor(baseline.currentScope.toNumber());
+        await record(
+          await program.methods
+            .advanceScope()
+            .accounts({
+              admin: admin.publicKey,
+              censusState: censusStatePda,
+              scopeSnapshot: scopeSnapshotPda,
+            })
+            .rpc({ commitment: "confirmed" })
+        );
+      } finally {
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+
+      // Replay onto the baseline
+      let totalRegistered = baseline.totalRegistered.toNumber();
+      let population = baseline.currentPopulation.toNumber();
+      let scope = baseline.currentScope.toNumber();
+      for (const { name, data } of events) {
+        switch (name) {
+          case "citizenRegistered":
+            totalRegistered += 1;
+            break;
+          case "censusCounted":
+            expect(data.scope.toNumber()).to.equal(scope);
+            population = data.newPopulation.toNumber();
+            break;
+          case "scopeAdvanced":
+            expect(data.oldScope.toNumber()).to.equal(scope);
+            expect(data.finalPopulation.toNumber()).to.equal(population);
+            scope = data.newScope.toNumber();
+            population = 0;
+            break;
+        }
+      }
+
+      const after = await program.account.censusState.fetch(censusStatePda);
+      expect(events.filter((e) => e.name === "censusCounted")).to.have.length(2);
+      expect(after.totalRegistered.toNumber()).to.equal(totalRegistered);
+      expect(after.currentPopulation.toNumber()).to.equal(population);
+      expect(after.currentScope.toNumber()).to.equal(scope);
+    });
+  });
+
+  describe("Batch Close Nullifiers", () => {
+    it("should close only nullifiers whose scope can no longer count", async () => {
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const nullifierHash = Buffer.alloc(32);
+      crypto.getRandomValues(nullifierHash);
+      nullifierHash[0] = 0; // keep it below the field modulus
+      const [nullifierPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("nullifier"), nullifierHash],
+        program.programId
+      );
+
+      try {
+        const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+        const nonce = Buffer.alloc(16);
+        crypto.getRandomValues(nonce);
+        const externalNullifier = Buffer.alloc(32);
+        state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const signalHash = Buffer.alloc(32);
+        signalHash[31] = 1;
+
+        const message = Buffer.concat([
+          Buffer.from([0x04]),
+          timestamp.toArrayLike(Buffer, "le", 8),
+          nonce,
+          Buffer.from(state.merkleRoot),
+          nullifierHash,
+          externalNullifier,
+          signalHash,
+          Buffer.from(state.circuitHash),
+        ]);
+        const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: (admin as anchor.Wallet).payer.secretKey,
+          message,
+        });
+
+        await program.methods
+          .submitAttestation(
+            timestamp,
+            [...nonce],
+            [...state.merkleRoot],
+            [...nullifierHash],
+            [...externalNullifier],
+            [...signalHash],
+            [...ed25519Ix.data.subarray(48, 112)],
+            null,
+            null,
+            null
+          )
+          .accounts({
+            payer: admin.publicKey,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            previousScopeSnapshot: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            merkleTree: null,
+            compressionProgram: null,
+            verifierRegistry: null,
+            citizenRecord: null,
+            aggregate: null,
+          })
+          .preInstructions([ed25519Ix])
+          .rpc();
+      } finally {
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+
+      const sweep = () =>
+        program.methods
+          .batchCloseNullifiers()
+          .accounts({ censusState: censusStatePda, rentSink: state.rentSink })
+          .remainingAccounts([{ pubkey: nullifierPda, isSigner: false, isWritable: true }]);
+      const sweepResult = async () => {
+        const simulation = await sweep().simulate();
+        const prefix = `Program return: ${program.programId.toBase58()} `;
+        const returnLog = simulation.raw.find((log: string) => log.startsWith(prefix));
+        return program.coder.types.decode(
+          "NullifierSweep",
+          Buffer.from(returnLog.slice(prefix.length), "base64")
+        );
+      };
+
+      // Still the current scope: skipped, not failed
+      const early = await sweepResult();
+      expect(early.closed).to.equal(0);
+      expect(early.skipped).to.equal(1);
+
+      const [scopeSnapshotPda] = scopeSnapshotPdaFor(state.currentScope.toNumber());
+      await program.methods
+        .advanceScope()
+        .accounts({
+          admin: admin.publicKey,
+          censusState: censusStatePda,
+          scopeSnapshot: scopeSnapshotPda,
+        })
+        .rpc();
+
+      const rent = await provider.connection.getBalance(nullifierPda);
+      const late = await sweepResult();
+      expect(late.closed).to.equal(1);
+      expect(late.lamportsReclaimed.toNumber()).to.equal(rent);
+
+      await sweep().rpc();
+      expect(await provider.connection.getAccountInfo(nullifierPda)).to.be.null;
+    });
+
+    it("should refund only to the rent sink", async () => {
+      try {
+        await program.methods
+          .batchCloseNullifiers()
+          .accounts({ censusState: censusStatePda, rentSink: Keypair.generate().publicKey })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidRentSink error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidRentSink");
+      }
+    });
+  });
+
+  describe("Participation Events", () => {
+    it("should emit ParticipationRecorded for the payer only when enabled", async () => {
+      const parser = new anchor.EventParser(program.programId, program.coder);
+      const attest = async () => {
+        const state = await program.account.censusState.fetch(censusStatePda);
+        const timestamp = new anchor.BN(Math.floor(Date.now() / 1000));
+        const nonce = Buffer.alloc(16);
+        crypto.getRandomValues(nonce);
+        const nullifierHash = Buffer.alloc(32);
+        crypto.getRandomValues(nullifierHash);
+        nullifierHash[0] = 0; // keep it below the field modulus
+        const externalNullifier = Buffer.alloc(32);
+        state.currentScope.toArrayLike(Buffer, "le", 8).copy(externalNullifier);
+        const signalHash = Buffer.alloc(32);
+        signalHash[31] = 1;
+
+        const message = Buffer.concat([
+          Buffer.from([0x04]),
+          timestamp.toArrayLike(Buffer, "le", 8),
+          nonce,
+          Buffer.from(state.merkleRoot),
+          nullifierHash,
+          externalNullifier,
+          signalHash,
+          Buffer.from(state.circuitHash),
+        ]);
+        const ed25519Ix = Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: (admin as anchor.Wallet).payer.secretKey,
+          message,
+        });
+        const [nullifierPda] = PublicKey.findProgramAddressSync(
+          [Buffer.from("nullifier"), nullifierHash],
+          program.programId
+        );
+
+        const signature = await program.methods
+          .submitAttestation(
+            timestamp,
+            [...nonce],
+            [...state.merkleRoot],
+            [...nullifierHash],
+            [...externalNullifier],
+            [...signalHash],
+            [...ed25519Ix.data.subarray(48, 112)],
+            null,
+            null,
+            null
+          )
+          .accounts({
+            payer: admin.publicKey,
+            verifier: admin.publicKey,
+            censusState: censusStatePda,
+            nullifier: nullifierPda,
+            instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+            systemProgram: SystemProgram.programId,
+            previousScopeSnapshot: null,
+            payerCooldown: null,
+            nullifierBitmap: null,
+            merkleTree: null,
+            compressionProgram: null,
+            verifierRegistry: null,
+            citizenRecord: null,
+            aggregate: null,
+          })
+          .preInstructions([ed25519Ix])
+          .rpc({ commitment: "confirmed" });
+        const tx = await provider.connection.getTransaction(signature, {
+          commitment: "confirmed",
+          maxSupportedTransactionVersion: 0,
+        });
+        return [...parser.parseLogs(tx.meta.logMessages)].filter(
+          (event) => event.name === "participationRecorded"
+        );
+      };
+
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        expect(await attest()).to.have.length(0);
+
+        await program.methods
+          .setEmitParticipationEvents(true)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        const events = await attest();
+        expect(events).to.have.length(1);
+        expect(events[0].data.payer.toBase58()).to.equal(admin.publicKey.toBase58());
+      } finally {
+        await program.methods
+          .setEmitParticipationEvents(false)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Lifecycle", () => {
+    it("should register, publish a root, count an attestation and advance", async () => {
+      const before = await program.account.censusState.fetch(censusStatePda);
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        // register_citizen
+        const identityCommitment = Buffer.alloc(32);
+        crypto.getRandomValues(identityCommitment);
+        const registerEvents = await eventsOf(
+          await program.methods
+            .registerCitizen([...identityCommitment], 1)
+            .accounts({
+              admin: admin.publicKey,
+              censusState: censusStatePda,
+              merkleTree: null,
+              compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+              noop: SPL_NOOP_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+              citizenRecord: null,
+            })
+            .rpc({ commitment: "confirmed" })
+        );
+        const registered = registerEvents.find((e) => e.name === "citizenRegistered");
+        expect(Buffer.from(registered.data.commitment).equals(identityCommitment)).to.be.true;
+        expect(registered.data.leafIndex.toNumber()).to.equal(before.leafCount.toNumber());
+        expect(registered.data.newRoot).to.be.null;
+
+        // set_merkle_root
+        const root = Buffer.alloc(32);
+        crypto.getRandomValues(root);
+        root[0] = 0;
+        await program.methods
+          .setMerkleRoot([...root])
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda, merkleTree: null })
+          .rpc();
+        const rooted = await program.account.censusState.fetch(censusStatePda);
+        expect(Buffer.from(rooted.merkleRoot).equals(root)).to.be.true;
+        expect(rooted.rootVersion.toNumber()).to.equal(before.rootVersion.toNumber() + 1);
+
+        // submit_attestation
+        const attestation = await buildAttestation();
+        const countEvents = await eventsOf(
+          await submitAttestationFor(attestation).rpc({ commitment: "confirmed" })
+        );
+        const counted = countEvents.find((e) => e.name === "censusCounted");
+        expect(counted.data.newPopulation.toNumber()).to.equal(
+          rooted.currentPopulation.toNumber() + 1
+        );
+        expect(countEvents.map((e) => e.name)).to.include("censusAttested");
+
+        // advance_scope
+        const [scopeSnapshotPda] = scopeSnapshotPdaFor(rooted.currentScope.toNumber());
+        const advanceEvents = await eventsOf(
+          await program.methods
+            .advanceScope()
+            .accounts({
+              admin: admin.publicKey,
+              censusState: censusStatePda,
+              scopeSnapshot: scopeSnapshotPda,
+            })
+            .rpc({ commitment: "confirmed" })
+        );
+        const advanced = advanceEvents.find((e) => e.name === "scopeAdvanced");
+        expect(advanced.data.newScope.toNumber()).to.equal(rooted.currentScope.toNumber() + 1);
+        expect(advanced.data.finalPopulation.toNumber()).to.equal(
+          counted.data.newPopulation.toNumber()
+        );
+
+        const after = await program.account.censusState.fetch(censusStatePda);
+        expect(after.totalRegistered.toNumber()).to.equal(before.totalRegistered.toNumber() + 1);
+        expect(after.currentPopulation.toNumber()).to.equal(0);
+      } finally {
+        await program.methods
+          .setMerkleRoot(before.merkleRoot)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda, merkleTree: null })
+          .rpc();
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should reject a double nullifier, a wrong root and an expired attestation", async () => {
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      const expectError = async (
+        attestation: Awaited<ReturnType<typeof buildAttestation>>,
+        code: string
+      ) => {
+        try {
+          await submitAttestationFor(attestation).rpc();
+          expect.fail(`Should have thrown ${code} error`);
+        } catch (err: any) {
+          expect(err.message).to.include(code);
+        }
+      };
+
+      try {
+        const counted = await buildAttestation();
+        await submitAttestationFor(counted).rpc();
+        await expectError(
+          await buildAttestation({ nullifierHash: counted.nullifierHash }),
+          "NullifierAlreadyUsed"
+        );
+
+        await expectError(
+          await buildAttestation({ merkleRoot: Array(32).fill(7) }),
+          "MerkleRootMismatch"
+        );
+
+        await expectError(
+          await buildAttestation({ timestamp: new anchor.BN(Math.floor(Date.now() / 1000) - 3600) }),
+          "AttestationExpired"
+        );
+
+        const state = await program.account.censusState.fetch(censusStatePda);
+        await expectError(
+          await buildAttestation({ scope: state.currentScope.addn(1) }),
+          "AttestationForFutureScope"
+        );
+      } finally {
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should emit gapless, strictly increasing leaf indices", async () => {
+      const before = await program.account.censusState.fetch(censusStatePda);
+      const indices: number[] = [];
+      for (let i = 0; i < 3; i++) {
+        const identityCommitment = Buffer.alloc(32);
+        crypto.getRandomValues(identityCommitment);
+        const events = await eventsOf(
+          await program.methods
+            .registerCitizen([...identityCommitment], 1)
+            .accounts({
+              admin: admin.publicKey,
+              censusState: censusStatePda,
+              merkleTree: null,
+              compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+              noop: SPL_NOOP_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+              citizenRecord: null,
+            })
+            .rpc({ commitment: "confirmed" })
+        );
+        indices.push(events.find((e) => e.name === "citizenRegistered").data.leafIndex.toNumber());
+      }
+
+      const first = before.leafCount.toNumber();
+      expect(indices).to.deep.equal([first, first + 1, first + 2]);
+      const after = await program.account.censusState.fetch(censusStatePda);
+      expect(after.leafCount.toNumber()).to.equal(first + 3);
+    });
+
+    it("should not checkpoint roots without a linked tree", async () => {
+      await program.methods
+        .setRootCheckpointInterval(new anchor.BN(1))
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        const state = await program.account.censusState.fetch(censusStatePda);
+        expect(state.rootCheckpointInterval.toNumber()).to.equal(1);
+
+        const identityCommitment = Buffer.alloc(32);
+        crypto.getRandomValues(identityCommitment);
+        const events = await eventsOf(
+          await program.methods
+            .registerCitizen([...identityCommitment], 1)
+            .accounts({
+              admin: admin.publicKey,
+              censusState: censusStatePda,
+              merkleTree: null,
+              compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+              noop: SPL_NOOP_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+              citizenRecord: null,
+            })
+            .rpc({ commitment: "confirmed" })
+        );
+        const registered = events.find((e) => e.name === "citizenRegistered");
+        expect(registered.data.newRoot).to.be.null;
+      } finally {
+        await program.methods
+          .setRootCheckpointInterval(new anchor.BN(0))
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+
+    it("should reject admin instructions from another key", async () => {
+      const fakeAdmin = Keypair.generate();
+      const airdropSig = await provider.connection.requestAirdrop(
+        fakeAdmin.publicKey,
+        LAMPORTS_PER_SOL
+      );
+      await provider.connection.confirmTransaction(airdropSig);
+
+      try {
+        await program.methods
+          .setMerkleRoot(Array(32).fill(1))
+          .accounts({ admin: fakeAdmin.publicKey, censusState: censusStatePda, merkleTree: null })
+          .signers([fakeAdmin])
+          .rpc();
+
+        expect.fail("Should have thrown UnauthorizedAdmin error");
+      } catch (err: any) {
+        expect(err.message).to.include("UnauthorizedAdmin");
+      }
+    });
+  });
+
+  describe("Verifier Registry", () => {
+    const [verifierRegistryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("verifier_registry")],
+      program.programId
+    );
+
+    it("should grow and shrink the registry with its verifier set", async () => {
+      const verifiers = [Keypair.generate().publicKey, Keypair.generate().publicKey];
+      await program.methods
+        .initVerifierRegistry()
+        .accounts({
+          admin: admin.publicKey,
+          censusState: censusStatePda,
+          verifierRegistry: verifierRegistryPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      try {
+        const state = await program.account.censusState.fetch(censusStatePda);
+        expect(state.verifierRegistry.toBase58()).to.equal(verifierRegistryPda.toBase58());
+
+        for (const verifier of verifiers) {
+          await program.methods
+            .addRegistryVerifier(verifier)
+            .accounts({
+              admin: admin.publicKey,
+              censusState: censusStatePda,
+              verifierRegistry: verifierRegistryPda,
+              systemProgram: SystemProgram.programId,
+            })
+            .rpc();
+        }
+        let info = await provider.connection.getAccountInfo(verifierRegistryPda);
+        expect(info.data.length).to.equal(8 + 1 + 4 + 32 * verifiers.length);
+
+        const { rentSink } = await program.account.censusState.fetch(censusStatePda);
+        const sinkBefore = await provider.connection.getBalance(rentSink);
+        const registryBefore = info.lamports;
+        await program.methods
+          .removeRegistryVerifier(verifiers[0])
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            verifierRegistry: verifierRegistryPda,
+            rentSink,
+          })
+          .rpc();
+        const registry = await program.account.verifierRegistry.fetch(verifierRegistryPda);
+        expect(registry.verifiers.map(v => v.toBase58())).to.deep.equal([verifiers[1].toBase58()]);
+        info = await provider.connection.getAccountInfo(verifierRegistryPda);
+        expect(info.data.length).to.equal(8 + 1 + 4 + 32);
+        // The freed slot's rent lands in the rent sink (the admin pays the fee,
+        // so this holds even when the sink is the admin)
+        const freed = registryBefore - info.lamports;
+        expect(freed).to.be.greaterThan(0);
+        const sinkAfter = await provider.connection.getBalance(rentSink);
+        expect(sinkAfter).to.be.at.least(sinkBefore + freed - 10_000);
+      } finally {
+        await program.methods
+          .setVerifierRegistry(PublicKey.default)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+      const state = await program.account.censusState.fetch(censusStatePda);
+      expect(state.verifierRegistry.toBase58()).to.equal(PublicKey.default.toBase58());
+    });
+
+    it("should reject linking an arbitrary registry", async () => {
+      try {
+        await program.methods
+          .setVerifierRegistry(Keypair.generate().publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+
+        expect.fail("Should have thrown InvalidVerifierRegistry error");
+      } catch (err: any) {
+        expect(err.message).to.include("InvalidVerifierRegistry");
+      }
+    });
+  });
+
+  // Runs last: linking a tree is permanent, and every earlier test registers
+  // against the unlinked census
+  describe("Linked Tree", () => {
+    const depthSizePair = { maxDepth: TREE_DEPTH, maxBufferSize: MAX_BUFFER_SIZE } as const;
+    const treeKeypair = Keypair.generate();
+    const treeRoot = async () =>
+      [...(await ConcurrentMerkleTreeAccount.fromAccountAddress(
+        provider.connection,
+        treeKeypair.publicKey
+      )).getCurrentRoot()];
+    const setActive = (active: boolean) =>
+      (active ? program.methods.resumeCensus() : program.methods.pauseCensus())
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+    before(async () => {
+      const tx = new anchor.web3.Transaction().add(
+        await createAllocTreeIx(
+          provider.connection,
+          treeKeypair.publicKey,
+          admin.publicKey,
+          depthSizePair,
+          CANOPY_DEPTH
+        ),
+        createInitEmptyMerkleTreeIx(treeKeypair.publicKey, admin.publicKey, depthSizePair)
+      );
+      await provider.sendAndConfirm(tx, [treeKeypair]);
+    });
+
+    it("should migrate only with the new tree's own root", async () => {
+      const migrate = (root: number[]) =>
+        program.methods
+          .migrateMerkleTree(treeKeypair.publicKey, root)
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            newMerkleTree: treeKeypair.publicKey,
+          })
+          .rpc();
+
+      await setActive(false);
+      try {
+        try {
+          await migrate(Array(32).fill(7));
+          expect.fail("Should have thrown InvalidMerkleRoot error");
+        } catch (err: any) {
+          expect(err.message).to.include("InvalidMerkleRoot");
+        }
+
+        const root = await treeRoot();
+        await migrate(root);
+        const state = await program.account.censusState.fetch(censusStatePda);
+        expect(state.merkleTree.toBase58()).to.equal(treeKeypair.publicKey.toBase58());
+        expect(state.merkleRoot).to.deep.equal(root);
+      } finally {
+        await setActive(true);
+      }
+    });
+
+    it("should append registrations itself and stop once it hands the tree off", async () => {
+      const register = (identityCommitment: Buffer) =>
+        program.methods
+          .registerCitizen([...identityCommitment], 1)
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            merkleTree: treeKeypair.publicKey,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+            noop: SPL_NOOP_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+            citizenRecord: null,
+          })
+          .rpc();
+      const newCommitment = () => {
+        const commitment = Buffer.alloc(32);
+        crypto.getRandomValues(commitment);
+        commitment[0] = 0; // keep it below the field modulus
+        return commitment;
+      };
+
+      // The census PDA must own the tree to append to it
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          createTransferAuthorityIx(treeKeypair.publicKey, admin.publicKey, censusStatePda)
+        )
+      );
+
+      const before = await program.account.censusState.fetch(censusStatePda);
+      const commitment = newCommitment();
+      await register(commitment);
+
+      const after = await program.account.censusState.fetch(censusStatePda);
+      const tree = await ConcurrentMerkleTreeAccount.fromAccountAddress(
+        provider.connection,
+        treeKeypair.publicKey
+      );
+      expect(after.leafCount.toNumber()).to.equal(before.leafCount.toNumber() + 1);
+      expect(tree.tree.rightMostPath.index).to.equal(after.leafCount.toNumber());
+      expect(tree.tree.rightMostPath.leaf.toBuffer()).to.deep.equal(commitment);
+
+      // Once handed off, the census can no longer append
+      await setActive(false);
+      try {
+        await program.methods
+          .setTreeAuthority(admin.publicKey)
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            merkleTree: treeKeypair.publicKey,
+            compressionProgram: SPL_ACCOUNT_COMPRESSION_PROGRAM_ID,
+          })
+          .rpc();
+      } finally {
+        await setActive(true);
+      }
+
+      try {
+        await register(newCommitment());
+        expect.fail("Should have thrown TreeAuthorityMismatch error");
+      } catch (err: any) {
+        expect(err.message).to.include("TreeAuthorityMismatch");
+      }
+    });
+
+    it("should require an inclusion proof once strict membership is on", async () => {
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+      await program.methods
+        .setStrictMembership(true)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        await submitAttestationFor(await buildAttestation()).rpc();
+        expect.fail("Should have thrown MembershipProofRequired error");
+      } catch (err: any) {
+        expect(err.message).to.include("MembershipProofRequired");
+      } finally {
+        await program.methods
+          .setStrictMembership(false)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
+    });
+  });
+
+  describe("Census Aggregate", () => {
+    it("should tally counts into the scope's aggregate and read them back", async () => {
+      const state = await program.account.censusState.fetch(censusStatePda);
+      const [aggregatePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("aggregate"), state.currentScope.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      await program.methods
+        .addVerifier(admin.publicKey)
+        .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+        .rpc();
+
+      try {
+        await program.methods
+          .initAggregate()
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            aggregate: aggregatePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+
+        // Once the scope has an aggregate, counts must update it
+        try {
+          await submitAttestationFor(await buildAttestation()).rpc();
+          expect.fail("Should have thrown InvalidAggregate error");
+        } catch (err: any) {
+          expect(err.message).to.include("InvalidAggregate");
+        }
+
+        // buildAttestation signals 1, attribute slot 0
+        await submitAttestationFor(await buildAttestation(), aggregatePda).rpc();
+
+        const summary = await program.methods
+          .readAggregate(state.currentScope)
+          .accounts({ aggregate: aggregatePda })
+          .view();
+        expect(summary.scope.toNumber()).to.equal(state.currentScope.toNumber());
+        expect(summary.participantCount.toNumber()).to.equal(1);
+        expect(summary.attributeCounts.map((c: anchor.BN) => c.toNumber())).to.deep.equal([
+          1, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+        ]);
+      } finally {
+        // Leave the aggregated scope behind so later counts need no aggregate
+        const [scopeSnapshotPda] = scopeSnapshotPdaFor(state.currentScope.toNumber());
+        await program.methods
+          .advanceScope()
+          .accounts({
+            admin: admin.publicKey,
+            censusState: censusStatePda,
+            scopeSnapshot: scopeSnapshotPda,
+          })
+          .rpc();
+        await program.methods
+          .removeVerifier(admin.publicKey)
+          .accounts({ admin: admin.publicKey, censusState: censusStatePda })
+          .rpc();
+      }
     });
   });
 });